@@ -0,0 +1,316 @@
+# German texts of the user interface
+
+# Start
+safe-mode = Nach { $count } fehlgeschlagenen Starts im abgesicherten Modus gestartet: Benachrichtigungen, Discord, der Simulator, die Übersetzung, das Drucken, das Tray-Symbol und der METAR-Verlauf sind aus und nichts wird automatisch geladen.
+launch-arguments-ignored = Startargumente werden ignoriert: { $error }
+
+# Main view
+reload-data = Daten neu laden
+shortcut = Tastenkürzel: { $key }
+hide-to-tray = In den Infobereich
+loading-flight-plan = Flugplan wird geladen...
+reload-flight-plan = Flugplan neu laden
+suppress-updates = Automatische Aktualisierungen aussetzen
+compact-view = Kompaktansicht
+quiet-hours-active = Ruhezeit { $hours }, automatische Aktualisierungen sind pausiert
+resume-now = Jetzt fortsetzen
+atis-online = ATIS von { $icao } ist jetzt online
+fetching-new-atis = Die neue ATIS wird abgerufen
+loading-data = Daten werden geladen...
+load-departure-metar = METAR Abflug
+load-arrival-metar = METAR Ankunft
+load-departure-atis = ATIS Abflug
+load-arrival-atis = ATIS Ankunft
+load-simbrief = SimBrief
+dismiss = Ausblenden
+open-log-folder = Log-Ordner öffnen
+flight-plan = Flugplan
+enroute = Strecke
+vertical-profile = Vertikalprofil
+route-map = Streckenkarte
+refresh-interval = Die Daten werden { $phase } alle { $minutes } Minuten aktualisiert, letzte Anfrage um: { $local }lcl ({ $utc }z)
+refresh-phase-ground = vor dem Abflug
+refresh-phase-cruise = im Reiseflug
+refresh-phase-arrival = nahe der Ankunft
+next-update = Nächste Aktualisierung in { $time }
+weather-statistics = Wetterstatistik
+tools = Werkzeuge
+
+# Credentials
+set-credentials = Zugangsdaten festlegen
+field-username = den Benutzernamen
+field-api-key = den API-Schlüssel
+credentials-cleared = Beim Bearbeiten ist für { $field } etwas schiefgelaufen, die eingegebenen Zugangsdaten wurden geleert. Die gespeicherten sind unverändert.
+profile = Profil:
+none = Keins
+delete-profile = Profil löschen
+save-as-profile = Aktuelle Zugangsdaten als Profil speichern:
+save-profile = Profil speichern
+username = Benutzername:
+api-key = API-Schlüssel:
+save = Speichern
+testing = Wird geprüft...
+test-credentials = Zugangsdaten prüfen
+test-credentials-hint = Prüft die eingegebenen Werte vor dem Speichern
+credentials-saved = Erfolg! Die Daten wurden gespeichert.
+get-avwx-key = AVWX-API-Schlüssel holen
+open-config-folder = Konfigurationsordner öffnen
+report-problem = Problem melden
+
+# Settings
+settings = Einstellungen
+export-disclaimer = Hinweis beim Export:
+network = Netzwerk:
+simulator = Simulator:
+off = Aus
+simulator-hint = Folgt dem Flugzeug, z. B. um das Wetter am Zielflughafen für den Anflug zu aktualisieren
+deepl-key = DeepL-API-Schlüssel (übersetzt nicht englische ATIS):
+api-headers = Anfrage-Header der API-Schlüssel:
+provider = Anbieter
+header = Header
+prefix = Präfix
+reset = Zurücksetzen
+quiet-hours = Ruhezeit:
+quiet-hours-example = z. B. 23:00-07:00
+quiet-hours-invalid = HH:MM-HH:MM in Ortszeit verwenden
+quiet-hours-hint = Keine automatischen Aktualisierungen in diesen Stunden, Ortszeit
+download-folder = Download-Ordner:
+copy-flight-plan-to = Kopiere .pln/.fms nach:
+copy-flight-plan-hint = Flugplanordner des Simulators, leer für keine Kopie
+export-briefings-to = Briefings exportieren nach:
+download-folder-hint = Download-Ordner
+crosswind-limit = Seitenwindlimit:
+language = Sprache:
+ui-scale = Skalierung:
+ui-scale-hint = Strg + und Strg - zoomen ebenfalls
+metar-age-warning = Vor METARs warnen, die älter sind als:
+metar-age-alert = Alarm nach:
+cache-duration = Antworten wiederverwenden für:
+cache-duration-hint = Spart API-Aufrufe beim Neuladen, 0 um immer abzurufen
+proxy = Proxy:
+proxy-url = URL:
+proxy-url-hint = z. B. http://proxy:8080 oder socks5://proxy:1080, leer für HTTPS_PROXY
+password = Passwort:
+api-endpoints = API-Endpunkte, z. B. für eine selbst gehostete AVWX-Instanz oder einen Spiegel:
+refresh-every = Aktualisieren alle
+refresh-before-departure = vor dem Abflug,
+refresh-in-cruise = im Reiseflug und
+refresh-within = innerhalb von
+refresh-of-eta = vor der ETA oder
+refresh-of-arrival = vom Ziel
+retry-attempts = Versuche pro Anfrage:
+retry-attempts-hint = Wiederholt Zeitüberschreitungen und Serverfehler mit wachsenden Pausen
+log-files-keep = Behalte
+log-files-rotate = alte Log-Dateien, beginne eine neue nach
+applies-next-start = Gilt ab dem nächsten Start
+auto-print = Briefing drucken, wenn ein neuer Flugplan geladen wird
+keep-history = METAR-Verlauf zwischen Starts behalten
+desktop-notifications = Desktop-Benachrichtigungen (neue ATIS, IFR oder Gewitter am Ziel, fehlgeschlagene Aktualisierung)
+check-for-updates = Beim Start nach einer neuen Version suchen (fragt GitHub)
+discord-presence = Flug in Discord anzeigen, Anwendungs-ID:
+discord-app-id-hint = Von discord.com/developers
+logs = Logs
+data-provided-by = Daten bereitgestellt von { $sources }.
+invalid-proxy = Ungültiger Proxy: { $error }
+
+# Updates
+new-version = Version { $version } ist verfügbar, dies ist { $current }.
+open-release = Release-Seite öffnen
+
+# Status
+test-simbrief-ok = OK, letzter Flugplan { $origin } nach { $destination }
+test-avwx-ok = OK, Token akzeptiert
+nearest-airport-failed = Der nächste Flughafen wurde nicht gefunden: { $error }
+saved-and-copied = { $path } gespeichert und nach { $copy } kopiert
+saved = { $path } gespeichert
+flight-plan-failed = Flugplan konnte nicht geladen werden: { $error }
+refresh-failed = Aktualisierung fehlgeschlagen
+update-failed = Daten konnten nicht aktualisiert werden: { $error }
+refresh-skipped = Automatische Aktualisierung übersprungen, heute nur noch { $remaining } AVWX-Aufrufe übrig
+
+# Flight timer
+flight-timer = Flugzeit:
+timer-off-block = Off-Block
+timer-takeoff = Start
+timer-landing = Landung
+timer-on-block = On-Block
+block-flight-time = Block { $block } / Flug { $flight }
+print-briefing = Briefing drucken
+export-briefing = Briefing exportieren
+export-failed = Export fehlgeschlagen: { $error }
+
+# Safe mode
+reset-settings = Einstellungen zurücksetzen
+settings-reset = Einstellungen zurückgesetzt, die alten liegen in { $backup }. Neu starten, um die Standardwerte zu verwenden.
+settings-reset-failed = Einstellungen konnten nicht zurückgesetzt werden: { $error }
+continue = Fortfahren
+
+# Notifications
+new-atis-at = Neue ATIS in { $icao }
+new-atis-information = Information { $letter } (vorher { $previous }), neu briefen
+flight-rules-worsened = { $icao } ist jetzt { $rules }
+check-destination-weather = Wetter am Zielflughafen prüfen
+thunderstorm-sigmet = Gewitter-SIGMET in { $icao }
+check-destination-hazards = Gefahren am Zielflughafen prüfen
+
+# Airports
+departure = Abflug
+arrival = Ankunft
+alternate = Ausweichflughafen
+stop-monitoring = Nicht mehr beobachten
+monitor = Beobachten
+monitor-hint = Einen Tab hinzufügen, der mit den anderen aktualisiert wird
+monitored = Beobachtet
+airport-data-failed = Daten von { $icao } konnten nicht abgerufen werden: { $error }
+loading-airport = { $icao } wird geladen...
+invalid-icao = "{ $entry }" ist kein gültiger ICAO-Code
+departure-entry = Abflug:
+arrival-entry = Ankunft:
+favorites = Favoriten
+recent = Zuletzt
+remove-favorite = { $icao } aus den Favoriten entfernen
+add-favorite = { $icao } zu den Favoriten hinzufügen
+brief = Briefen
+swap = Tauschen
+enter-both-airports = Beide Flughäfen eingeben
+
+# Compact view
+no-data-yet = Noch keine Daten
+full-view = Vollansicht
+reload = Neu laden
+pin-on-top = Im Vordergrund
+pin-on-top-hint = Das Fenster über anderen Fenstern halten, z. B. einem Simulator im Fenstermodus
+
+# Simulator
+connecting = Verbinde...
+distance-to = , { $distance } NM bis { $icao }
+flight-phase-departure = Abflug
+flight-phase-enroute = Reiseflug
+flight-phase-approach = Anflug
+flight-phase-arrived = Angekommen
+use-nearest-airport = Nächsten Flughafen als Abflug verwenden
+
+# Problem report
+report-question = Was haben Sie gemacht und was ist schiefgelaufen?
+report-contents = Der Bericht enthält die App-Version, Ihr Betriebssystem, die Logs und die letzten API-Antworten. API-Schlüssel und Ihr SimBrief-Benutzername werden entfernt.
+create-report = Bericht erstellen und GitHub öffnen
+report-failed = Der Bericht konnte nicht erstellt werden: { $error }
+
+# Crash report
+crash-title = Get Flight Data ist abgestürzt
+crash-text = Die App ist beim letzten Mal abgestürzt. Die Details stehen in { $file }, bitte hängen Sie die Datei an, wenn Sie das Problem melden.
+open-crash-report = Absturzbericht öffnen
+close = Schließen
+
+# Flight plan
+new-atis = Neue ATIS-Information { $letter } (vorher { $previous }), neu briefen
+callsign = Rufzeichen
+from-to = Von / Nach
+cruise-level = Reiseflughöhe
+cost-index = Cost Index
+block-fuel = Blocktreibstoff
+etd-eta = ETD / ETA
+route = Route:
+downloads = Downloads:
+download-failed = Download fehlgeschlagen: { $error }
+
+# Weather
+copy-metar = METAR kopieren
+copy-atis = ATIS kopieren
+quantity-wind = Wind
+quantity-visibility = Sicht
+trend = { $name } { $current } { $arrow } (vorher { $previous })
+since-previous-metar = Seit dem vorigen METAR: { $changes }
+previous-metars = Vorige METARs
+hazards = Gefahren
+
+# Winds aloft
+no-positions = Keine Positionen im Flugplan
+winds-failed = Höhenwinde konnten nicht geladen werden: { $error }
+winds-at = Winde in { $level } ({ $pressure } hPa):
+more-headwind = { $speed } kt mehr Gegenwind als geplant
+more-tailwind = { $speed } kt mehr Rückenwind als geplant
+later-arrival = Spätere Ankunft gegen { $revised } statt { $eta } erwartet
+earlier-arrival = Frühere Ankunft gegen { $revised } statt { $eta } erwartet
+enroute-time = , ETE { $time } bei { $speed } kt TAS
+
+# Route map
+aircraft = Flugzeug
+route-line = Route
+
+# Airport details
+forecast-at-eta = um { $time }Z: { $conditions }
+forecast-at-eta-hint = Erwartet zur ETA des Flugplans, laut TAF
+icing-possible = Vereisung möglich: sichtbare Feuchtigkeit zwischen +2 und -20 °C
+icing-severe = Starke Vereisung: gefrierender Niederschlag oder Nebel
+traffic = { $inbound } ankommend, { $outbound } abfliegend
+charts = Karten
+metar-age = METAR vor { $age } beobachtet ({ $time }z)
+qnh-trend = Drucktendenz: { $trend }
+qnh-trend-hint = Änderung seit dem vorigen METAR, auf langen Flügen den Höhenmesser nachstellen
+density-altitude = Dichtehöhe { $altitude } ft (Platz { $elevation } ft)
+density-altitude-hint = Aus Temperatur und QNH des METAR, eine Dichtehöhe über der Platzhöhe verlängert die Startrollstrecke und verringert die Steigrate
+part-failed = Nicht geladen, { $error }
+no-atis = Keine ATIS
+runways-in-use = Pisten in Betrieb: { $runways }
+runway-winds = Pistenwinde
+runway-tailwind = { $speed } kt Rücken
+runway-headwind = { $speed } kt Gegen
+runway-crosswind-left = { $speed } kt von links
+runway-crosswind-right = { $speed } kt von rechts
+
+# Tools
+runway-heading = Pistenrichtung:
+wind = Wind:
+gusting = in Böen
+tailwind = { $speed } kt Rückenwind
+headwind = { $speed } kt Gegenwind
+crosswind-left = { $speed } kt Seitenwind von links
+crosswind-right = { $speed } kt Seitenwind von rechts
+offline = Offline: Daten von { $time }z, { $minutes } Minuten alt
+
+# Logs
+log-level = Anzeigen:
+copy = Kopieren
+log-note = Die letzten { $lines } Zeilen seit dem Start, alles steht in { $file }
+nothing-logged = Noch nichts geloggt
+avwx-calls = AVWX-Aufrufe heute: { $used } von { $limit }, { $remaining } übrig
+
+# Sun
+sun-times = { $icao }: Morgendämmerung { $dawn }, Sonnenaufgang { $sunrise }, Sonnenuntergang { $sunset }, Abenddämmerung { $dusk }
+daylight-day = { $event } bei Tageslicht
+daylight-twilight = { $event } in der Dämmerung
+daylight-night = { $event } bei Nacht
+coverage-tower = TWR online
+coverage-approach = nur APP
+coverage-center = nur CTR
+coverage-unicom = UNICOM 122.800
+station-logged-on = { $callsign } hat sich gerade angemeldet
+station-logged-off = { $callsign } hat sich abgemeldet
+taf-changes = Änderungen
+taf-risk-probability = { $group } — { $percent }% Risiko von { $rules } zur ETA
+taf-risk-expected = { $group } — { $rules } zur ETA erwartet
+taf-risk-temporary = { $group } — vorübergehendes Risiko von { $rules } zur ETA
+
+# Airport info
+airport-info = Flughafeninfo
+airport-info-failed = Flughafeninfo konnte nicht geladen werden: { $error }
+elevation = Höhe: { $elevation }
+
+# Statistics
+statistics-session-only = Nur METARs dieser Sitzung werden gezählt, für mehr den METAR-Verlauf in den Einstellungen behalten.
+no-metars-recorded = Noch keine METARs aufgezeichnet
+last-days = Letzte { $days } Tage:
+average-wind = Mittlerer Wind { $wind }
+metar-count = { $count } METARs
+metar-count-since = { $count } seit { $since }
+
+# ATC
+no-atc = Keine { $network }-Lotsen online
+online-atc = Lotsen online
+coverage-estimated = (Abdeckung geschätzt)
+
+# Tray
+tray-show = Anzeigen
+tray-refresh = Jetzt aktualisieren
+tray-quit = Beenden
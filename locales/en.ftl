@@ -0,0 +1,316 @@
+# English texts of the user interface, the reference for the translations
+
+# Start
+safe-mode = Started in safe mode after { $count } failed starts: notifications, Discord, the simulator, translation, printing, the tray icon and the METAR history are off and nothing is loaded automatically.
+launch-arguments-ignored = Ignoring launch arguments: { $error }
+
+# Main view
+reload-data = Reload data
+shortcut = Shortcut: { $key }
+hide-to-tray = Hide to tray
+loading-flight-plan = Loading Flight Plan...
+reload-flight-plan = Reload Flight Plan
+suppress-updates = Suppress automatic updates
+compact-view = Compact view
+quiet-hours-active = Quiet hours { $hours }, automatic updates are paused
+resume-now = Resume now
+atis-online = { $icao } ATIS is now online
+fetching-new-atis = Fetching the new ATIS
+loading-data = Loading data...
+load-departure-metar = Departure METAR
+load-arrival-metar = Arrival METAR
+load-departure-atis = Departure ATIS
+load-arrival-atis = Arrival ATIS
+load-simbrief = SimBrief
+dismiss = Dismiss
+open-log-folder = Open log folder
+flight-plan = Flight Plan
+enroute = Enroute
+vertical-profile = Vertical profile
+route-map = Route map
+refresh-interval = Data will be refreshed every { $minutes } minutes { $phase }, last request time was at: { $local }lcl ({ $utc }z)
+refresh-phase-ground = before departure
+refresh-phase-cruise = in cruise
+refresh-phase-arrival = near the arrival
+next-update = Next update in { $time }
+weather-statistics = Weather statistics
+tools = Tools
+
+# Credentials
+set-credentials = Set Credentials
+field-username = username
+field-api-key = API key
+credentials-cleared = Something went wrong while editing { $field }, the entered credentials were cleared. The stored ones are unchanged.
+profile = Profile:
+none = None
+delete-profile = Delete profile
+save-as-profile = Save current credentials as profile:
+save-profile = Save profile
+username = Username:
+api-key = API Key:
+save = Save
+testing = Testing...
+test-credentials = Test credentials
+test-credentials-hint = Checks the entered values before saving them
+credentials-saved = Success! Data has been saved.
+get-avwx-key = Get an AVWX API Key
+open-config-folder = Open config folder
+report-problem = Report a problem
+
+# Settings
+settings = Settings
+export-disclaimer = Export disclaimer:
+network = Network:
+simulator = Simulator:
+off = Off
+simulator-hint = Follows the aircraft, e.g. to refresh the arrival weather for the approach
+deepl-key = DeepL API key (translates non-English ATIS):
+api-headers = Request headers of the API keys:
+provider = Provider
+header = Header
+prefix = Prefix
+reset = Reset
+quiet-hours = Quiet hours:
+quiet-hours-example = e.g. 23:00-07:00
+quiet-hours-invalid = Use HH:MM-HH:MM in local time
+quiet-hours-hint = No automatic updates during these hours, local time
+download-folder = Download folder:
+copy-flight-plan-to = Copy .pln/.fms to:
+copy-flight-plan-hint = Simulator flight plan folder, empty to not copy
+export-briefings-to = Export briefings to:
+download-folder-hint = Download folder
+crosswind-limit = Crosswind limit:
+language = Language:
+ui-scale = UI scale:
+ui-scale-hint = Ctrl + and Ctrl - zoom as well
+metar-age-warning = Warn about METARs older than:
+metar-age-alert = alert after:
+cache-duration = Reuse responses for:
+cache-duration-hint = Saves API calls when reloading, 0 to always fetch
+proxy = Proxy:
+proxy-url = URL:
+proxy-url-hint = e.g. http://proxy:8080 or socks5://proxy:1080, empty for HTTPS_PROXY
+password = Password:
+api-endpoints = API endpoints, e.g. for a self-hosted AVWX instance or a mirror:
+refresh-every = Refresh every
+refresh-before-departure = before departure,
+refresh-in-cruise = in cruise and
+refresh-within = within
+refresh-of-eta = of the ETA or
+refresh-of-arrival = of the arrival
+retry-attempts = Tries per request:
+retry-attempts-hint = Repeats timeouts and server errors with increasing pauses
+log-files-keep = Keep
+log-files-rotate = old log files, start a new one after
+applies-next-start = Applies on the next start
+auto-print = Print the briefing when a new flight plan is loaded
+keep-history = Keep the METAR history between starts
+desktop-notifications = Desktop notifications (new ATIS, IFR or thunderstorms at destination, failed refresh)
+check-for-updates = Check for a new version on startup (asks GitHub)
+discord-presence = Show the flight in Discord, application ID:
+discord-app-id-hint = From discord.com/developers
+logs = Logs
+data-provided-by = Data provided by { $sources }.
+invalid-proxy = Invalid proxy: { $error }
+
+# Updates
+new-version = Version { $version } is available, this is { $current }.
+open-release = Open release page
+
+# Status
+test-simbrief-ok = OK, latest flight plan { $origin } to { $destination }
+test-avwx-ok = OK, token accepted
+nearest-airport-failed = Failed to find the nearest airport: { $error }
+saved-and-copied = Saved { $path } and copied to { $copy }
+saved = Saved { $path }
+flight-plan-failed = Failed to load flight plan: { $error }
+refresh-failed = Refresh failed
+update-failed = Failed to update data: { $error }
+refresh-skipped = Skipped the automatic refresh, only { $remaining } AVWX calls left today
+
+# Flight timer
+flight-timer = Flight timer:
+timer-off-block = Off block
+timer-takeoff = Takeoff
+timer-landing = Landing
+timer-on-block = On block
+block-flight-time = Block { $block } / Flight { $flight }
+print-briefing = Print briefing
+export-briefing = Export briefing
+export-failed = Export failed: { $error }
+
+# Safe mode
+reset-settings = Reset settings
+settings-reset = Settings reset, the old ones are in { $backup }. Restart to use the defaults.
+settings-reset-failed = Failed to reset settings: { $error }
+continue = Continue
+
+# Notifications
+new-atis-at = New ATIS at { $icao }
+new-atis-information = Information { $letter } (was { $previous }), re-brief
+flight-rules-worsened = { $icao } is now { $rules }
+check-destination-weather = Check the weather at your destination
+thunderstorm-sigmet = Thunderstorm SIGMET at { $icao }
+check-destination-hazards = Check the hazards at your destination
+
+# Airports
+departure = Departure
+arrival = Arrival
+alternate = Alternate
+stop-monitoring = Stop monitoring
+monitor = Monitor
+monitor-hint = Add a tab refreshed with the others
+monitored = Monitored
+airport-data-failed = Failed to get data of { $icao }: { $error }
+loading-airport = Loading { $icao }...
+invalid-icao = "{ $entry }" is not a valid ICAO code
+departure-entry = Departure:
+arrival-entry = Arrival:
+favorites = Favorites
+recent = Recent
+remove-favorite = Remove { $icao } from favorites
+add-favorite = Add { $icao } to favorites
+brief = Brief
+swap = Swap
+enter-both-airports = Enter both airports
+
+# Compact view
+no-data-yet = No data yet
+full-view = Full view
+reload = Reload
+pin-on-top = Pin on top
+pin-on-top-hint = Keep the window above other windows, e.g. a windowed simulator
+
+# Simulator
+connecting = Connecting...
+distance-to = , { $distance } NM to { $icao }
+flight-phase-departure = Departure
+flight-phase-enroute = Enroute
+flight-phase-approach = Approach
+flight-phase-arrived = Arrived
+use-nearest-airport = Use nearest airport as departure
+
+# Problem report
+report-question = What were you doing and what went wrong?
+report-contents = The report contains the app version, your OS, the logs and the last API responses. API keys and your SimBrief username are removed.
+create-report = Create report and open GitHub
+report-failed = Failed to create the report: { $error }
+
+# Crash report
+crash-title = Get Flight Data crashed
+crash-text = The app crashed the last time it ran. The details are in { $file }, please attach it when reporting the problem.
+open-crash-report = Open crash report
+close = Close
+
+# Flight plan
+new-atis = New ATIS information { $letter } (was { $previous }), re-brief
+callsign = Callsign
+from-to = From / To
+cruise-level = Cruise level
+cost-index = Cost index
+block-fuel = Block fuel
+etd-eta = ETD / ETA
+route = Route:
+downloads = Downloads:
+download-failed = Download failed: { $error }
+
+# Weather
+copy-metar = Copy METAR
+copy-atis = Copy ATIS
+quantity-wind = Wind
+quantity-visibility = Visibility
+trend = { $name } { $current } { $arrow } (was { $previous })
+since-previous-metar = Since the previous METAR: { $changes }
+previous-metars = Previous METARs
+hazards = Hazards
+
+# Winds aloft
+no-positions = No positions in the flight plan
+winds-failed = Failed to load winds aloft: { $error }
+winds-at = Winds at { $level } ({ $pressure } hPa):
+more-headwind = { $speed } kt more headwind than planned
+more-tailwind = { $speed } kt more tailwind than planned
+later-arrival = Expect a later arrival around { $revised } instead of { $eta }
+earlier-arrival = Expect an earlier arrival around { $revised } instead of { $eta }
+enroute-time = , ETE { $time } at { $speed } kt TAS
+
+# Route map
+aircraft = Aircraft
+route-line = Route
+
+# Airport details
+forecast-at-eta = at { $time }Z: { $conditions }
+forecast-at-eta-hint = Expected at the ETA of the flight plan, according to the TAF
+icing-possible = Icing possible: visible moisture between +2 and -20 °C
+icing-severe = Severe icing: freezing precipitation or fog
+traffic = { $inbound } inbound, { $outbound } outbound
+charts = Charts
+metar-age = METAR observed { $age } ago ({ $time }z)
+qnh-trend = Pressure trend: { $trend }
+qnh-trend-hint = Change since the previous METAR, keep the altimeter setting current on long flights
+density-altitude = Density altitude { $altitude } ft (field { $elevation } ft)
+density-altitude-hint = From the METAR temperature and QNH, a density altitude above the field lengthens the takeoff roll and reduces the climb rate
+part-failed = Not loaded, { $error }
+no-atis = No ATIS
+runways-in-use = Runways in use: { $runways }
+runway-winds = Runway winds
+runway-tailwind = { $speed } kt tail
+runway-headwind = { $speed } kt head
+runway-crosswind-left = { $speed } kt from the left
+runway-crosswind-right = { $speed } kt from the right
+
+# Tools
+runway-heading = Runway heading:
+wind = Wind:
+gusting = gusting
+tailwind = { $speed } kt tailwind
+headwind = { $speed } kt headwind
+crosswind-left = { $speed } kt crosswind from the left
+crosswind-right = { $speed } kt crosswind from the right
+offline = Offline: showing the data from { $time }z, { $minutes } minutes old
+
+# Logs
+log-level = Show:
+copy = Copy
+log-note = The last { $lines } lines since the start, everything is in { $file }
+nothing-logged = Nothing logged yet
+avwx-calls = AVWX calls today: { $used } of { $limit }, { $remaining } left
+
+# Sun
+sun-times = { $icao }: dawn { $dawn }, sunrise { $sunrise }, sunset { $sunset }, dusk { $dusk }
+daylight-day = { $event } in daylight
+daylight-twilight = { $event } in twilight
+daylight-night = { $event } at night
+coverage-tower = TWR online
+coverage-approach = APP only
+coverage-center = CTR only
+coverage-unicom = UNICOM 122.800
+station-logged-on = { $callsign } just logged on
+station-logged-off = { $callsign } logged off
+taf-changes = changes
+taf-risk-probability = { $group } — { $percent }% risk of { $rules } at ETA
+taf-risk-expected = { $group } — { $rules } expected at ETA
+taf-risk-temporary = { $group } — temporary risk of { $rules } at ETA
+
+# Airport info
+airport-info = Airport info
+airport-info-failed = Failed to load airport info: { $error }
+elevation = Elevation: { $elevation }
+
+# Statistics
+statistics-session-only = Only METARs of this session are counted, keep the METAR history in the settings for more.
+no-metars-recorded = No METARs recorded yet
+last-days = Last { $days } days:
+average-wind = Average wind { $wind }
+metar-count = { $count } METARs
+metar-count-since = { $count } since { $since }
+
+# ATC
+no-atc = No { $network } ATC online
+online-atc = Online ATC
+coverage-estimated = (coverage estimated)
+
+# Tray
+tray-show = Show
+tray-refresh = Refresh now
+tray-quit = Quit
@@ -0,0 +1,316 @@
+# French texts of the user interface
+
+# Start
+safe-mode = Démarré en mode sans échec après { $count } démarrages échoués : les notifications, Discord, le simulateur, la traduction, l'impression, l'icône de la barre des tâches et l'historique des METAR sont désactivés et rien n'est chargé automatiquement.
+launch-arguments-ignored = Arguments de lancement ignorés : { $error }
+
+# Main view
+reload-data = Recharger les données
+shortcut = Raccourci : { $key }
+hide-to-tray = Réduire dans la barre des tâches
+loading-flight-plan = Chargement du plan de vol...
+reload-flight-plan = Recharger le plan de vol
+suppress-updates = Suspendre les mises à jour automatiques
+compact-view = Vue compacte
+quiet-hours-active = Heures calmes { $hours }, les mises à jour automatiques sont en pause
+resume-now = Reprendre maintenant
+atis-online = L'ATIS de { $icao } est maintenant en ligne
+fetching-new-atis = Récupération de la nouvelle ATIS
+loading-data = Chargement des données...
+load-departure-metar = METAR départ
+load-arrival-metar = METAR arrivée
+load-departure-atis = ATIS départ
+load-arrival-atis = ATIS arrivée
+load-simbrief = SimBrief
+dismiss = Ignorer
+open-log-folder = Ouvrir le dossier des journaux
+flight-plan = Plan de vol
+enroute = En route
+vertical-profile = Profil vertical
+route-map = Carte de la route
+refresh-interval = Les données sont actualisées toutes les { $minutes } minutes { $phase }, dernière requête à : { $local }lcl ({ $utc }z)
+refresh-phase-ground = avant le départ
+refresh-phase-cruise = en croisière
+refresh-phase-arrival = près de l'arrivée
+next-update = Prochaine mise à jour dans { $time }
+weather-statistics = Statistiques météo
+tools = Outils
+
+# Credentials
+set-credentials = Définir les identifiants
+field-username = le nom d'utilisateur
+field-api-key = la clé API
+credentials-cleared = Une erreur s'est produite en modifiant { $field }, les identifiants saisis ont été effacés. Ceux enregistrés sont inchangés.
+profile = Profil :
+none = Aucun
+delete-profile = Supprimer le profil
+save-as-profile = Enregistrer les identifiants actuels comme profil :
+save-profile = Enregistrer le profil
+username = Nom d'utilisateur :
+api-key = Clé API :
+save = Enregistrer
+testing = Test en cours...
+test-credentials = Tester les identifiants
+test-credentials-hint = Vérifie les valeurs saisies avant de les enregistrer
+credentials-saved = Succès ! Les données ont été enregistrées.
+get-avwx-key = Obtenir une clé API AVWX
+open-config-folder = Ouvrir le dossier de configuration
+report-problem = Signaler un problème
+
+# Settings
+settings = Paramètres
+export-disclaimer = Avertissement à l'export :
+network = Réseau :
+simulator = Simulateur :
+off = Désactivé
+simulator-hint = Suit l'avion, par ex. pour actualiser la météo d'arrivée pour l'approche
+deepl-key = Clé API DeepL (traduit les ATIS non anglaises) :
+api-headers = En-têtes de requête des clés API :
+provider = Fournisseur
+header = En-tête
+prefix = Préfixe
+reset = Réinitialiser
+quiet-hours = Heures calmes :
+quiet-hours-example = par ex. 23:00-07:00
+quiet-hours-invalid = Utilisez HH:MM-HH:MM en heure locale
+quiet-hours-hint = Pas de mises à jour automatiques pendant ces heures, heure locale
+download-folder = Dossier de téléchargement :
+copy-flight-plan-to = Copier .pln/.fms vers :
+copy-flight-plan-hint = Dossier des plans de vol du simulateur, vide pour ne pas copier
+export-briefings-to = Exporter les briefings vers :
+download-folder-hint = Dossier de téléchargement
+crosswind-limit = Limite de vent traversier :
+language = Langue :
+ui-scale = Échelle de l'interface :
+ui-scale-hint = Ctrl + et Ctrl - zooment aussi
+metar-age-warning = Avertir des METAR plus anciens que :
+metar-age-alert = alerte après :
+cache-duration = Réutiliser les réponses pendant :
+cache-duration-hint = Économise des appels API lors du rechargement, 0 pour toujours récupérer
+proxy = Proxy :
+proxy-url = URL :
+proxy-url-hint = par ex. http://proxy:8080 ou socks5://proxy:1080, vide pour HTTPS_PROXY
+password = Mot de passe :
+api-endpoints = Points d'accès API, par ex. pour une instance AVWX auto-hébergée ou un miroir :
+refresh-every = Actualiser toutes les
+refresh-before-departure = avant le départ,
+refresh-in-cruise = en croisière et
+refresh-within = à moins de
+refresh-of-eta = de l'ETA ou
+refresh-of-arrival = de l'arrivée
+retry-attempts = Essais par requête :
+retry-attempts-hint = Répète les délais dépassés et les erreurs serveur avec des pauses croissantes
+log-files-keep = Garder
+log-files-rotate = anciens fichiers journaux, en commencer un nouveau après
+applies-next-start = S'applique au prochain démarrage
+auto-print = Imprimer le briefing quand un nouveau plan de vol est chargé
+keep-history = Conserver l'historique des METAR entre les démarrages
+desktop-notifications = Notifications de bureau (nouvelle ATIS, IFR ou orages à destination, échec d'actualisation)
+check-for-updates = Rechercher une nouvelle version au démarrage (interroge GitHub)
+discord-presence = Afficher le vol dans Discord, ID d'application :
+discord-app-id-hint = Depuis discord.com/developers
+logs = Journaux
+data-provided-by = Données fournies par { $sources }.
+invalid-proxy = Proxy invalide : { $error }
+
+# Updates
+new-version = La version { $version } est disponible, celle-ci est la { $current }.
+open-release = Ouvrir la page de la version
+
+# Status
+test-simbrief-ok = OK, dernier plan de vol de { $origin } à { $destination }
+test-avwx-ok = OK, jeton accepté
+nearest-airport-failed = Impossible de trouver l'aéroport le plus proche : { $error }
+saved-and-copied = { $path } enregistré et copié vers { $copy }
+saved = { $path } enregistré
+flight-plan-failed = Impossible de charger le plan de vol : { $error }
+refresh-failed = Échec de l'actualisation
+update-failed = Impossible de mettre à jour les données : { $error }
+refresh-skipped = Actualisation automatique ignorée, plus que { $remaining } appels AVWX aujourd'hui
+
+# Flight timer
+flight-timer = Chronomètre de vol :
+timer-off-block = Départ du poste
+timer-takeoff = Décollage
+timer-landing = Atterrissage
+timer-on-block = Arrivée au poste
+block-flight-time = Bloc { $block } / Vol { $flight }
+print-briefing = Imprimer le briefing
+export-briefing = Exporter le briefing
+export-failed = Échec de l'export : { $error }
+
+# Safe mode
+reset-settings = Réinitialiser les paramètres
+settings-reset = Paramètres réinitialisés, les anciens sont dans { $backup }. Redémarrez pour utiliser les valeurs par défaut.
+settings-reset-failed = Impossible de réinitialiser les paramètres : { $error }
+continue = Continuer
+
+# Notifications
+new-atis-at = Nouvelle ATIS à { $icao }
+new-atis-information = Information { $letter } (avant { $previous }), refaire le briefing
+flight-rules-worsened = { $icao } est maintenant { $rules }
+check-destination-weather = Vérifiez la météo à destination
+thunderstorm-sigmet = SIGMET orage à { $icao }
+check-destination-hazards = Vérifiez les dangers à destination
+
+# Airports
+departure = Départ
+arrival = Arrivée
+alternate = Dégagement
+stop-monitoring = Arrêter la surveillance
+monitor = Surveiller
+monitor-hint = Ajouter un onglet actualisé avec les autres
+monitored = Surveillé
+airport-data-failed = Impossible d'obtenir les données de { $icao } : { $error }
+loading-airport = Chargement de { $icao }...
+invalid-icao = « { $entry } » n'est pas un code OACI valide
+departure-entry = Départ :
+arrival-entry = Arrivée :
+favorites = Favoris
+recent = Récents
+remove-favorite = Retirer { $icao } des favoris
+add-favorite = Ajouter { $icao } aux favoris
+brief = Briefer
+swap = Inverser
+enter-both-airports = Saisissez les deux aéroports
+
+# Compact view
+no-data-yet = Pas encore de données
+full-view = Vue complète
+reload = Recharger
+pin-on-top = Épingler au premier plan
+pin-on-top-hint = Garder la fenêtre au-dessus des autres, par ex. d'un simulateur fenêtré
+
+# Simulator
+connecting = Connexion...
+distance-to = , { $distance } NM jusqu'à { $icao }
+flight-phase-departure = Départ
+flight-phase-enroute = En route
+flight-phase-approach = Approche
+flight-phase-arrived = Arrivé
+use-nearest-airport = Utiliser l'aéroport le plus proche comme départ
+
+# Problem report
+report-question = Que faisiez-vous et qu'est-ce qui n'a pas fonctionné ?
+report-contents = Le rapport contient la version de l'application, votre système, les journaux et les dernières réponses des API. Les clés API et votre nom d'utilisateur SimBrief sont retirés.
+create-report = Créer le rapport et ouvrir GitHub
+report-failed = Impossible de créer le rapport : { $error }
+
+# Crash report
+crash-title = Get Flight Data a planté
+crash-text = L'application a planté lors de sa dernière exécution. Les détails sont dans { $file }, merci de le joindre en signalant le problème.
+open-crash-report = Ouvrir le rapport de plantage
+close = Fermer
+
+# Flight plan
+new-atis = Nouvelle information ATIS { $letter } (avant { $previous }), refaire le briefing
+callsign = Indicatif
+from-to = De / À
+cruise-level = Niveau de croisière
+cost-index = Cost index
+block-fuel = Carburant bloc
+etd-eta = ETD / ETA
+route = Route :
+downloads = Téléchargements :
+download-failed = Échec du téléchargement : { $error }
+
+# Weather
+copy-metar = Copier le METAR
+copy-atis = Copier l'ATIS
+quantity-wind = Vent
+quantity-visibility = Visibilité
+trend = { $name } { $current } { $arrow } (avant { $previous })
+since-previous-metar = Depuis le METAR précédent : { $changes }
+previous-metars = METAR précédents
+hazards = Dangers
+
+# Winds aloft
+no-positions = Aucune position dans le plan de vol
+winds-failed = Impossible de charger les vents en altitude : { $error }
+winds-at = Vents au { $level } ({ $pressure } hPa) :
+more-headwind = { $speed } kt de vent de face de plus que prévu
+more-tailwind = { $speed } kt de vent arrière de plus que prévu
+later-arrival = Arrivée plus tardive prévue vers { $revised } au lieu de { $eta }
+earlier-arrival = Arrivée plus précoce prévue vers { $revised } au lieu de { $eta }
+enroute-time = , ETE { $time } à { $speed } kt TAS
+
+# Route map
+aircraft = Avion
+route-line = Route
+
+# Airport details
+forecast-at-eta = à { $time }Z : { $conditions }
+forecast-at-eta-hint = Prévu à l'ETA du plan de vol, selon le TAF
+icing-possible = Givrage possible : humidité visible entre +2 et -20 °C
+icing-severe = Givrage sévère : précipitations ou brouillard verglaçants
+traffic = { $inbound } à l'arrivée, { $outbound } au départ
+charts = Cartes
+metar-age = METAR observé il y a { $age } ({ $time }z)
+qnh-trend = Tendance de pression : { $trend }
+qnh-trend-hint = Variation depuis le METAR précédent, à reporter sur l'altimètre lors des longs vols
+density-altitude = Altitude densité { $altitude } ft (terrain { $elevation } ft)
+density-altitude-hint = D'après la température et le QNH du METAR, une altitude densité au-dessus du terrain allonge le roulement au décollage et réduit le taux de montée
+part-failed = Non chargé, { $error }
+no-atis = Pas d'ATIS
+runways-in-use = Pistes en service : { $runways }
+runway-winds = Vents par piste
+runway-tailwind = { $speed } kt arrière
+runway-headwind = { $speed } kt de face
+runway-crosswind-left = { $speed } kt de la gauche
+runway-crosswind-right = { $speed } kt de la droite
+
+# Tools
+runway-heading = Cap de la piste :
+wind = Vent :
+gusting = rafales à
+tailwind = { $speed } kt de vent arrière
+headwind = { $speed } kt de vent de face
+crosswind-left = { $speed } kt de vent traversier de la gauche
+crosswind-right = { $speed } kt de vent traversier de la droite
+offline = Hors ligne : données de { $time }z, vieilles de { $minutes } minutes
+
+# Logs
+log-level = Afficher :
+copy = Copier
+log-note = Les { $lines } dernières lignes depuis le démarrage, tout est dans { $file }
+nothing-logged = Rien de journalisé pour l'instant
+avwx-calls = Appels AVWX aujourd'hui : { $used } sur { $limit }, { $remaining } restants
+
+# Sun
+sun-times = { $icao } : aube { $dawn }, lever { $sunrise }, coucher { $sunset }, crépuscule { $dusk }
+daylight-day = { $event } de jour
+daylight-twilight = { $event } au crépuscule
+daylight-night = { $event } de nuit
+coverage-tower = TWR en ligne
+coverage-approach = APP seulement
+coverage-center = CTR seulement
+coverage-unicom = UNICOM 122.800
+station-logged-on = { $callsign } vient de se connecter
+station-logged-off = { $callsign } s'est déconnecté
+taf-changes = des changements
+taf-risk-probability = { $group } — risque de { $rules } à { $percent } % à l'ETA
+taf-risk-expected = { $group } — { $rules } prévu à l'ETA
+taf-risk-temporary = { $group } — risque temporaire de { $rules } à l'ETA
+
+# Airport info
+airport-info = Infos aéroport
+airport-info-failed = Impossible de charger les infos de l'aéroport : { $error }
+elevation = Altitude : { $elevation }
+
+# Statistics
+statistics-session-only = Seuls les METAR de cette session sont comptés, conservez l'historique des METAR dans les paramètres pour plus.
+no-metars-recorded = Aucun METAR enregistré pour l'instant
+last-days = { $days } derniers jours :
+average-wind = Vent moyen { $wind }
+metar-count = { $count } METAR
+metar-count-since = { $count } depuis { $since }
+
+# ATC
+no-atc = Aucun contrôleur { $network } en ligne
+online-atc = Contrôleurs en ligne
+coverage-estimated = (couverture estimée)
+
+# Tray
+tray-show = Afficher
+tray-refresh = Actualiser maintenant
+tray-quit = Quitter
@@ -0,0 +1,223 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use reqwest::Url;
+use tracing::{debug, info, warn};
+
+use crate::error::{GfdError, Result};
+use crate::logic::{endpoints, parse_json, send_authorized_request, send_request, ApiKey, Position};
+use crate::runways::{runways_from_json, Runway};
+
+const TIME_ZONE_URI: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// The static data of an airport from the AVWX station endpoint.
+pub struct AirportInfo {
+    pub icao: String,
+    pub name: String,
+    /// City and country, e.g. `Frankfurt-am-Main, DE`
+    pub location: String,
+    /// Elevation in feet, `None` if unknown
+    pub elevation_ft: Option<i32>,
+    pub runways: Vec<Runway>,
+    /// The time zone from Open-Meteo, `None` if it couldn't be looked up
+    pub time_zone: Option<TimeZone>,
+}
+
+/// The time zone of a place, with the offset it has right now.
+#[derive(Clone, Debug)]
+pub struct TimeZone {
+    /// e.g. `Europe/Berlin`
+    pub name: String,
+    /// e.g. `CEST`
+    pub abbreviation: String,
+    pub offset: FixedOffset,
+}
+
+impl TimeZone {
+    /// Converts a UTC time to the local time of the zone.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{FixedOffset, TimeZone as _, Utc};
+    /// use get_flight_data::airport::TimeZone;
+    ///
+    /// let berlin = TimeZone {
+    ///     name: String::from("Europe/Berlin"),
+    ///     abbreviation: String::from("CEST"),
+    ///     offset: FixedOffset::east_opt(2 * 3600).unwrap(),
+    /// };
+    /// let time = Utc.with_ymd_and_hms(2024, 6, 21, 12, 0, 0).unwrap();
+    /// assert_eq!(berlin.local(time).format("%H:%M").to_string(), "14:00");
+    /// ```
+    pub fn local(&self, time: DateTime<Utc>) -> DateTime<FixedOffset> {
+        time.with_timezone(&self.offset)
+    }
+}
+
+/// Fetches the name, elevation and runways of an airport from AVWX.
+///
+/// # Arguments
+///
+/// * `icao` - The ICAO code of the airport.
+/// * `token` - The AVWX API token.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or AVWX doesn't know the airport.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::airport::fetch_airport_info;
+/// use get_flight_data::logic::{ApiKey, Source};
+///
+/// let token = ApiKey::new(Source::Avwx, "my-avwx-token");
+/// let info = fetch_airport_info("EDDF", &token).await?;
+/// println!("{} with {} runways", info.name, info.runways.len());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_airport_info(icao: &str, token: &ApiKey) -> Result<AirportInfo> {
+    let avwx_uri = format!("{}station/{icao}", endpoints().avwx);
+
+    debug!("Calling avwx API for station info of {icao}");
+    let response = send_authorized_request(&avwx_uri, token).await?;
+    debug!("Got station info of {icao} as JSON");
+
+    let json = parse_json(&response, &format!("Station info of {icao}"))?;
+    if let Some(message) = json["error"].as_str() {
+        return Err(GfdError::Api {
+            source: String::from("AVWX"),
+            message: message.to_string(),
+        });
+    }
+
+    let text = |field: &str| json[field].as_str().unwrap_or_default().trim().to_string();
+    let location = [text("city"), text("country")]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let position = json["latitude"]
+        .as_f64()
+        .zip(json["longitude"].as_f64())
+        .map(|(latitude, longitude)| Position { latitude, longitude });
+    let time_zone = match position {
+        Some(position) => fetch_time_zone(position)
+            .await
+            .map_err(|err| warn!("Failed to look up time zone of {icao}: {err}"))
+            .ok(),
+        None => None,
+    };
+
+    Ok(AirportInfo {
+        icao: icao.to_string(),
+        name: text("name"),
+        location,
+        elevation_ft: json["elevation_ft"].as_i64().map(|elevation| elevation as i32),
+        runways: runways_from_json(&json),
+        time_zone,
+    })
+}
+
+/// Finds the airport with a METAR closest to a position on AVWX, e.g. where the aircraft is parked.
+///
+/// # Arguments
+///
+/// * `position` - The position to search around.
+/// * `token` - The AVWX API token.
+///
+/// # Returns
+///
+/// The ICAO code of the airport.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or AVWX knows no airport nearby.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::airport::nearest_airport;
+/// use get_flight_data::logic::{ApiKey, Position, Source};
+///
+/// let token = ApiKey::new(Source::Avwx, "my-avwx-token");
+/// let icao = nearest_airport(Position { latitude: 50.03, longitude: 8.57 }, &token).await?;
+/// assert_eq!(icao, "EDDF");
+/// # Ok(())
+/// # }
+/// ```
+pub async fn nearest_airport(position: Position, token: &ApiKey) -> Result<String> {
+    // Only airports that report weather, others have nothing to brief
+    let avwx_uri = format!("{}station/near/{:.4},{:.4}?n=1&airport=true&reporting=true",
+                           endpoints().avwx, position.latitude, position.longitude);
+
+    debug!("Calling avwx API for the airport nearest to {position:?}");
+    let response = send_authorized_request(&avwx_uri, token).await?;
+    let json = parse_json(&response, "Nearest station")?;
+    if let Some(message) = json["error"].as_str() {
+        return Err(GfdError::Api {
+            source: String::from("AVWX"),
+            message: message.to_string(),
+        });
+    }
+
+    let icao = json[0]["station"]["icao"]
+        .as_str()
+        .filter(|icao| !icao.is_empty())
+        .ok_or_else(|| GfdError::MissingField {
+            source: String::from("Nearest station"),
+            field: String::from("icao"),
+        })?;
+    info!("Nearest airport: {icao}");
+    Ok(icao.to_string())
+}
+
+/// Looks up the time zone of a place and its current offset from UTC on Open-Meteo.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response lacks the time zone.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::airport::fetch_time_zone;
+/// use get_flight_data::logic::Position;
+///
+/// let time_zone = fetch_time_zone(Position { latitude: 50.03, longitude: 8.57 }).await?;
+/// println!("{} ({})", time_zone.name, time_zone.abbreviation);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_time_zone(position: Position) -> Result<TimeZone> {
+    let uri = Url::parse_with_params(TIME_ZONE_URI, &[
+        ("latitude", position.latitude.to_string()),
+        ("longitude", position.longitude.to_string()),
+        ("timezone", String::from("auto")),
+        ("forecast_days", String::from("1")),
+    ]).expect("Open-Meteo URI should be valid");
+
+    let response = send_request(uri.as_str()).await?;
+    let json = parse_json(&response, "Open-Meteo")?;
+    if let Some(reason) = json["reason"].as_str() {
+        return Err(GfdError::Api { source: String::from("Open-Meteo"), message: reason.to_string() });
+    }
+
+    let missing = |field: &str| GfdError::MissingField {
+        source: String::from("Open-Meteo"),
+        field: field.to_string(),
+    };
+    let offset = json["utc_offset_seconds"]
+        .as_i64()
+        .and_then(|seconds| FixedOffset::east_opt(seconds as i32))
+        .ok_or_else(|| missing("utc_offset_seconds"))?;
+    let name = json["timezone"].as_str().ok_or_else(|| missing("timezone"))?.to_string();
+    let abbreviation = json["timezone_abbreviation"].as_str().unwrap_or(&name).to_string();
+    info!("Time zone at {}, {}: {name}", position.latitude, position.longitude);
+
+    Ok(TimeZone { name, abbreviation, offset })
+}
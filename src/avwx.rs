@@ -0,0 +1,228 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::error::{GfdError, Result};
+use crate::logic::{parse_json, Metar, Position};
+use crate::runways::{Runway, Wind};
+use crate::units::KNOTS_PER_MPS;
+
+/// The parts of an AVWX METAR (`/api/metar/{icao}?options=info`) we use.
+#[derive(Deserialize, Debug)]
+pub struct MetarReport {
+    pub raw: String,
+    /// `VFR`, `MVFR`, `IFR` or `LIFR`
+    pub flight_rules: String,
+    pub altimeter: Option<Number>,
+    pub wind_direction: Option<Number>,
+    pub wind_speed: Option<Number>,
+    pub wind_gust: Option<Number>,
+    pub visibility: Option<Number>,
+    /// In °C
+    pub temperature: Option<Number>,
+    pub dewpoint: Option<Number>,
+    #[serde(default)]
+    pub clouds: Vec<Cloud>,
+    /// Precipitation and obscurations, e.g. `-RA` or `BR`
+    #[serde(default)]
+    pub wx_codes: Vec<WxCode>,
+    pub time: Option<Timestamp>,
+    #[serde(default)]
+    pub units: Units,
+    /// The station, only sent with `options=info`
+    pub info: Option<StationInfo>,
+}
+
+/// A reported value, `value` is `None` for e.g. a variable wind direction.
+#[derive(Deserialize, Debug)]
+pub struct Number {
+    /// As written in the METAR, e.g. `VRB` or `9999`
+    #[serde(default)]
+    pub repr: String,
+    pub value: Option<f64>,
+}
+
+/// A cloud layer.
+#[derive(Clone, PartialEq, Deserialize, Debug)]
+pub struct Cloud {
+    /// `FEW`, `SCT`, `BKN`, `OVC` or `VV` for a vertical visibility
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Base in hundreds of feet above ground, `None` if unknown
+    pub altitude: Option<u32>,
+    /// e.g. `CB` or `TCU`
+    pub modifier: Option<String>,
+}
+
+/// A weather phenomenon of a METAR or TAF.
+#[derive(Clone, PartialEq, Deserialize, Debug)]
+pub struct WxCode {
+    /// As written in the report, e.g. `-FZRA`
+    pub repr: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Timestamp {
+    pub dt: Option<DateTime<Utc>>,
+}
+
+/// The units of the reported values, they differ between stations.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Units {
+    /// e.g. `inHg` or `hPa`
+    pub altimeter: String,
+    /// `kt`, `m/s` or `km/h`
+    pub wind_speed: String,
+    /// `m` or `sm`
+    pub visibility: String,
+}
+
+/// The station data AVWX adds to a report with `options=info`.
+#[derive(Deserialize, Debug)]
+pub struct StationInfo {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub elevation_ft: Option<f64>,
+    #[serde(default)]
+    pub runways: Vec<StationRunway>,
+}
+
+/// A runway of an AVWX station.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct StationRunway {
+    pub ident1: String,
+    pub ident2: String,
+    /// True headings of both ends in degrees
+    pub bearing1: Option<f64>,
+    pub bearing2: Option<f64>,
+    pub length_ft: Option<f64>,
+    pub width_ft: Option<f64>,
+    pub surface: Option<String>,
+}
+
+impl StationRunway {
+    pub fn runway(&self) -> Runway {
+        Runway {
+            idents: [self.ident1.clone(), self.ident2.clone()],
+            bearings: [self.bearing1, self.bearing2],
+            length_ft: self.length_ft.map(|length| length as u32),
+            width_ft: self.width_ft.map(|width| width as u32),
+            surface: self.surface.clone(),
+        }
+    }
+}
+
+impl MetarReport {
+    /// The surface wind converted to knots, `None` if the METAR reports no wind speed.
+    pub fn wind(&self) -> Option<Wind> {
+        // AVWX reports the wind of some stations in meters per second
+        let factor = match self.units.wind_speed.as_str() {
+            "m/s" => KNOTS_PER_MPS,
+            _ => 1.0,
+        };
+        Some(Wind {
+            direction: value(&self.wind_direction),
+            speed: value(&self.wind_speed)? * factor,
+            gust: value(&self.wind_gust).map(|gust| gust * factor),
+        })
+    }
+
+    /// Converts the report into the METAR shown in the briefing.
+    pub fn metar(self) -> Metar {
+        let position = self.info.as_ref().and_then(|info| {
+            Some(Position { latitude: info.latitude?, longitude: info.longitude? })
+        });
+        let elevation_ft = self.info.as_ref().and_then(|info| info.elevation_ft);
+        let runways = self.info
+            .as_ref()
+            .map(|info| info.runways.iter().map(StationRunway::runway).collect())
+            .unwrap_or_default();
+
+        Metar {
+            wind: self.wind(),
+            raw: self.raw.trim().to_string(),
+            flight_rules: self.flight_rules.trim().to_string(),
+            position,
+            elevation_ft,
+            qnh: value(&self.altimeter),
+            wind_speed: value(&self.wind_speed),
+            visibility: value(&self.visibility),
+            temperature: value(&self.temperature),
+            dewpoint: value(&self.dewpoint),
+            clouds: self.clouds,
+            weather: self.wx_codes.into_iter().map(|code| code.repr).collect(),
+            runways,
+            time: self.time.and_then(|time| time.dt),
+        }
+    }
+}
+
+fn value(number: &Option<Number>) -> Option<f64> {
+    number.as_ref()?.value
+}
+
+/// Parses an AVWX METAR response.
+///
+/// # Arguments
+///
+/// * `response` - The body of the response.
+/// * `icao` - The ICAO code of the station, used in the error message.
+///
+/// # Errors
+///
+/// Returns the AVWX error message as [`GfdError::Api`], e.g. for an invalid token,
+/// or [`GfdError::InvalidJson`] if the METAR lacks a required field like `raw`.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::avwx::parse_metar;
+/// use get_flight_data::error::GfdError;
+///
+/// let response = r#"{
+///     "raw": "EDDB 251820Z AUTO 24010MPS 9999 VCSH SCT027 BKN039CB 09/06 Q1005 NOSIG",
+///     "flight_rules": "VFR",
+///     "altimeter": { "repr": "Q1005", "value": 1005 },
+///     "wind_direction": { "repr": "240", "value": 240 },
+///     "wind_speed": { "repr": "10", "value": 10 },
+///     "wind_gust": null,
+///     "temperature": { "repr": "09", "value": 9 },
+///     "dewpoint": { "repr": "06", "value": 6 },
+///     "wx_codes": [{ "repr": "VCSH", "value": "Vicinity Showers" }],
+///     "clouds": [
+///         { "repr": "SCT027", "type": "SCT", "altitude": 27, "modifier": null },
+///         { "repr": "BKN039CB", "type": "BKN", "altitude": 39, "modifier": "CB" }
+///     ],
+///     "time": { "repr": "251820Z", "dt": "2024-02-25T18:20:00Z" },
+///     "units": { "altimeter": "hPa", "wind_speed": "m/s", "visibility": "m" }
+/// }"#;
+/// let metar = parse_metar(response, "EDDB").unwrap();
+/// assert_eq!(metar.flight_rules, "VFR");
+/// assert_eq!(metar.qnh, Some(1005.0));
+/// assert_eq!(metar.clouds[1].modifier.as_deref(), Some("CB"));
+/// assert_eq!((metar.temperature, metar.dewpoint), (Some(9.0), Some(6.0)));
+/// assert_eq!(metar.weather, ["VCSH"]);
+/// assert_eq!(metar.wind.unwrap().speed.round(), 19.0);
+/// assert!(metar.time.is_some());
+///
+/// let invalid_token = parse_metar(r#"{ "error": "Token is not active" }"#, "EDDB");
+/// assert!(matches!(invalid_token, Err(GfdError::Api { message, .. }) if message == "Token is not active"));
+///
+/// let without_raw = parse_metar(r#"{ "flight_rules": "VFR" }"#, "EDDB");
+/// assert!(matches!(without_raw, Err(GfdError::InvalidJson { .. })));
+/// ```
+pub fn parse_metar(response: &str, icao: &str) -> Result<Metar> {
+    let source = format!("METAR of {icao}");
+    let json = parse_json(response, &source)?;
+    if let Some(message) = json["error"].as_str() {
+        return Err(GfdError::Api {
+            source: String::from("AVWX"),
+            message: message.to_string(),
+        });
+    }
+
+    let report: MetarReport = serde_json::from_value(json)
+        .map_err(|error| GfdError::InvalidJson { source, error })?;
+    Ok(report.metar())
+}
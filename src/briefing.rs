@@ -0,0 +1,199 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::logic::{attribution, AirportData, FlightPlan, Source};
+use crate::timer::FlightTimer;
+
+/// Formats the flight plan and the data of both airports as a plain text briefing,
+/// e.g. for printing.
+///
+/// # Arguments
+///
+/// * `flight_plan` - The SimBrief flight plan, `None` if the airports were entered directly.
+/// * `departure` - The data of the departure airport.
+/// * `arrival` - The data of the arrival airport.
+/// * `timer` - The flight timer, its times are added if it was started.
+/// * `disclaimer` - Footer added to the end, may be empty.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::briefing::briefing;
+/// use get_flight_data::logic::{update_data, HttpClient};
+///
+/// let (departure, arrival) = update_data(&HttpClient, "EDDF", "LOWW").await?;
+/// println!("{}", briefing(None, &departure, &arrival, None, "For simulation use only."));
+/// # Ok(())
+/// # }
+/// ```
+pub fn briefing(
+    flight_plan: Option<&FlightPlan>,
+    departure: &AirportData,
+    arrival: &AirportData,
+    timer: Option<&FlightTimer>,
+    disclaimer: &str,
+) -> String {
+    formatted_briefing(BriefingFormat::Text, flight_plan, departure, arrival, timer, disclaimer)
+}
+
+/// The formats a briefing can be exported in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BriefingFormat {
+    Text,
+    Markdown,
+}
+
+impl BriefingFormat {
+    pub const ALL: [BriefingFormat; 2] = [BriefingFormat::Markdown, BriefingFormat::Text];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BriefingFormat::Text => "Text",
+            BriefingFormat::Markdown => "Markdown",
+        }
+    }
+
+    /// The file extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BriefingFormat::Text => "txt",
+            BriefingFormat::Markdown => "md",
+        }
+    }
+
+    /// Finds a format by its extension, Markdown for unknown ones.
+    pub fn from_extension(extension: &str) -> Self {
+        match extension.trim() {
+            "txt" => BriefingFormat::Text,
+            _ => BriefingFormat::Markdown,
+        }
+    }
+}
+
+/// Formats the briefing like [`briefing`], in the given format.
+/// In Markdown, the sections get headings and their contents are kept in code blocks,
+/// so METAR, TAF and ATIS keep their layout.
+pub fn formatted_briefing(
+    format: BriefingFormat,
+    flight_plan: Option<&FlightPlan>,
+    departure: &AirportData,
+    arrival: &AirportData,
+    timer: Option<&FlightTimer>,
+    disclaimer: &str,
+) -> String {
+    // Headings with their contents
+    let mut sections: Vec<(String, String)> = Vec::new();
+
+    let title = match flight_plan {
+        Some(plan) => format!("Briefing {} {} - {}", plan.callsign, plan.origin, plan.destination),
+        None => format!("Briefing {} - {}", departure.icao, arrival.icao),
+    };
+    sections.push((title, format!("Generated {}", Utc::now().format("%Y-%m-%d %H:%Mz"))));
+
+    if let Some(plan) = flight_plan {
+        let time = |time: Option<DateTime<Utc>>| time
+            .map(|time| time.format("%H:%Mz").to_string())
+            .unwrap_or_else(|| String::from("-"));
+        let fuel = match plan.block_fuel {
+            Some(fuel) => format!("{fuel} {}", plan.fuel_units),
+            None => String::from("-"),
+        };
+
+        sections.push((format!("Flight plan {}", attribution(&[Source::SimBrief])), format!("\
+            Route: {}\n\
+            Cruise level: {}\n\
+            Alternate: {}\n\
+            Cost index: {}\n\
+            Block fuel: {fuel}\n\
+            ETD / ETA: {} / {}",
+            plan.route,
+            plan.cruise_level(),
+            plan.alternate.as_deref().unwrap_or("-"),
+            plan.cost_index,
+            time(plan.etd),
+            time(plan.eta))));
+    }
+
+    sections.push((String::from("Departure"), departure.summary.clone()));
+    let arrival_taf = match &arrival.taf {
+        Some(taf) => format!("\nTAF {}: {}", attribution(&[Source::Avwx]), taf.raw),
+        None => String::new(),
+    };
+    sections.push((String::from("Arrival"), format!("{}{arrival_taf}", arrival.summary)));
+
+    if let Some(timer) = timer.filter(|timer| timer.off_block.is_some()) {
+        sections.push((String::from("Times"), timer.summary(Utc::now())));
+    }
+
+    let disclaimer = disclaimer.trim();
+    match format {
+        BriefingFormat::Text => {
+            let line_separator = "-".repeat(60);
+            let mut blocks: Vec<String> = sections
+                .into_iter()
+                .map(|(heading, content)| format!("{heading}\n{content}"))
+                .collect();
+            if !disclaimer.is_empty() {
+                blocks.push(disclaimer.to_string());
+            }
+            blocks.join(&format!("\n\n{line_separator}\n\n"))
+        }
+        BriefingFormat::Markdown => {
+            let mut sections = sections.into_iter();
+            let mut blocks = Vec::new();
+            if let Some((title, generated)) = sections.next() {
+                blocks.push(format!("# {title}\n\n{generated}"));
+            }
+            blocks.extend(sections.map(|(heading, content)| {
+                format!("## {heading}\n\n```text\n{content}\n```")
+            }));
+            if !disclaimer.is_empty() {
+                blocks.push(format!("_{disclaimer}_"));
+            }
+            blocks.join("\n\n") + "\n"
+        }
+    }
+}
+
+/// The name of an exported briefing, e.g. `briefing_EDDF_LOWW_20240621_0930.md`.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use get_flight_data::briefing::{file_name, BriefingFormat};
+///
+/// let time = Utc.with_ymd_and_hms(2024, 6, 21, 9, 30, 0).unwrap();
+/// let name = file_name("EDDF", "LOWW", BriefingFormat::Markdown, time);
+/// assert_eq!(name, "briefing_EDDF_LOWW_20240621_0930.md");
+/// assert!(file_name("EDDF", "LOWW", BriefingFormat::Text, time).ends_with(".txt"));
+/// ```
+pub fn file_name(departure: &str, arrival: &str, format: BriefingFormat, time: DateTime<Utc>) -> String {
+    format!("briefing_{departure}_{arrival}_{}.{}", time.format("%Y%m%d_%H%M"), format.extension())
+}
+
+/// Writes a briefing to a file in a folder, e.g. to attach it to a PIREP.
+///
+/// # Arguments
+///
+/// * `text` - The formatted briefing.
+/// * `folder` - The folder to write to, created if missing.
+/// * `file_name` - The name of the file, see [`file_name`].
+///
+/// # Returns
+///
+/// The path of the written file.
+///
+/// # Errors
+///
+/// Returns an error if the folder can't be created or the file can't be written.
+pub fn export(text: &str, folder: &Path, file_name: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(folder)?;
+    let path = folder.join(file_name);
+    fs::write(&path, text)?;
+    Ok(path)
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+/// How long responses are reused by default, in seconds.
+pub const DEFAULT_TTL: u64 = 60;
+
+// Responses by URI with the time they were received
+static RESPONSES: Mutex<Option<HashMap<String, (Instant, String)>>> = Mutex::new(None);
+static TTL: AtomicU64 = AtomicU64::new(DEFAULT_TTL);
+
+/// Sets how long a response is reused for the same URI, zero to not reuse responses.
+pub fn set_ttl(ttl: Duration) {
+    TTL.store(ttl.as_secs(), Ordering::Relaxed);
+    if ttl.is_zero() {
+        clear();
+    }
+}
+
+/// Forgets all responses, so the next requests fetch fresh data.
+pub fn clear() {
+    if let Ok(mut responses) = RESPONSES.lock() {
+        *responses = None;
+    }
+}
+
+fn ttl() -> Duration {
+    Duration::from_secs(TTL.load(Ordering::Relaxed))
+}
+
+/// Returns the response to a URI if it was received within the TTL.
+pub(crate) fn get(uri: &str) -> Option<String> {
+    let responses = RESPONSES.lock().ok()?;
+    let (received, response) = responses.as_ref()?.get(uri)?;
+    if received.elapsed() >= ttl() {
+        return None;
+    }
+    debug!("Reusing the response of {uri} from {}s ago", received.elapsed().as_secs());
+    Some(response.clone())
+}
+
+/// Keeps a successful response for the TTL, dropping expired ones.
+pub(crate) fn insert(uri: &str, response: &str) {
+    let ttl = ttl();
+    if ttl.is_zero() {
+        return;
+    }
+    let Ok(mut responses) = RESPONSES.lock() else {
+        return;
+    };
+    let responses = responses.get_or_insert_with(HashMap::new);
+    responses.retain(|_, (received, _)| received.elapsed() < ttl);
+    responses.insert(uri.to_string(), (Instant::now(), response.to_string()));
+}
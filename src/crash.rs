@@ -0,0 +1,78 @@
+//! Crash reports, written to `gfd_err.log` when the app panics and offered to the user on the next start.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use tracing::{info, warn};
+
+use crate::json_operations::get_file_dir;
+use crate::logging::LogLine;
+use crate::report::system_info;
+
+const MARKER_FILE_NAME: &str = "crash_report_pending";
+/// The last log lines included in a crash report.
+pub const REPORT_LOG_LINES: usize = 50;
+
+/// Describes a panic with everything needed to look into it.
+///
+/// # Arguments
+///
+/// * `message` - The panic message.
+/// * `location` - Where the panic happened, e.g. `src/main.rs:42:5`, `None` if unknown.
+/// * `backtrace` - The captured backtrace, empty if none was captured.
+/// * `log_lines` - The last lines logged before the panic, oldest first.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::crash::crash_report;
+///
+/// let report = crash_report("index out of bounds", Some("src/logic.rs:42:5"), "", &[]);
+/// assert!(report.contains("A panic occurred: index out of bounds"));
+/// assert!(report.contains("Panic happened at src/logic.rs:42:5"));
+/// assert!(report.contains("get_flight_data "));
+/// ```
+pub fn crash_report(message: &str, location: Option<&str>, backtrace: &str, log_lines: &[LogLine]) -> String {
+    let location = location.map_or_else(
+        || String::from("The panic location cannot be determined"),
+        |location| format!("Panic happened at {location}"),
+    );
+    let backtrace = match backtrace.trim() {
+        "" => "No backtrace captured",
+        backtrace => backtrace,
+    };
+    let log_lines: Vec<String> = log_lines.iter().map(LogLine::to_string).collect();
+    let log_lines = if log_lines.is_empty() { String::from("Nothing logged") } else { log_lines.join("\n") };
+
+    format!("A panic occurred: {message}\n{location}\n{}\n\nBacktrace:\n{backtrace}\n\n\
+             Last log lines:\n{log_lines}\n",
+            system_info())
+}
+
+/// Remembers that a crash report was written, so the next start can offer it.
+pub fn mark_pending() -> io::Result<()> {
+    fs::write(marker_path()?, "")
+}
+
+/// Checks whether the last run crashed and forgets it, so the report is offered once.
+pub fn take_pending() -> bool {
+    let Ok(path) = marker_path() else {
+        return false;
+    };
+    match fs::remove_file(path) {
+        Ok(()) => {
+            info!("The last run crashed, offering the crash report");
+            true
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+        Err(err) => {
+            warn!("Failed to remove {MARKER_FILE_NAME}: {err}");
+            false
+        }
+    }
+}
+
+fn marker_path() -> io::Result<PathBuf> {
+    Ok(get_file_dir()?.join(MARKER_FILE_NAME))
+}
@@ -1,35 +1,167 @@
+use std::env::current_dir;
+#[cfg(target_os = "windows")]
 use std::env::current_exe;
-use crate::logic::LOG_DIR;
+use std::fs;
+use std::io;
+use std::path::{MAIN_SEPARATOR, Path, PathBuf};
+use std::sync::Mutex;
+
+use directories::{ProjectDirs, UserDirs};
+use tracing::{info, warn};
+
+use crate::json_operations::FILE_NAME;
+use crate::logic::{ERROR_FILE_NAME, LOGFILE_NAME, LOG_DIR};
 
-/// Returns the path to the log directory.
+static CONFIG_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Returns the path to the log directory, ending with a path separator.
+///
+/// This is the `logs` directory in the local data directory of the OS,
+/// e.g. `%LOCALAPPDATA%\HaDeSMonsta\get_flight_data\data\logs\` on Windows
+/// or `~/.local/share/get_flight_data/logs/` on Linux.
 ///
 /// # Panics
 ///
-/// - If Getting the path to the executable fails
+/// - If the OS has no home directory and getting the path to the executable fails
 /// - If the executable does not have a parent directory.
 pub fn get_log_dir() -> String {
-    get_dir(LOG_DIR)
+    let dir = match project_dirs() {
+        Some(dirs) => dirs.data_local_dir().join(LOG_DIR),
+        None => legacy_log_dir(),
+    };
+    with_separator(&dir)
 }
 
-// TODO move config to dir
-#[allow(dead_code)]
+/// Returns the path to the config directory, ending with a path separator.
+///
+/// This is the config directory of the OS,
+/// e.g. `%APPDATA%\HaDeSMonsta\get_flight_data\config\` on Windows
+/// or `~/.config/get_flight_data/` on Linux.
+///
+/// A directory set with [`set_config_dir`] is returned instead.
+///
+/// # Panics
+///
+/// - If the OS has no home directory and the working directory can't be determined.
 pub fn get_config_dir() -> String {
-    get_dir("config")
+    if let Some(dir) = CONFIG_DIR.lock().ok().and_then(|dir| dir.clone()) {
+        return with_separator(&dir);
+    }
+    let dir = match project_dirs() {
+        Some(dirs) => dirs.config_dir().to_path_buf(),
+        None => legacy_config_dir(),
+    };
+    with_separator(&dir)
+}
+
+/// Keeps the user data, the AVWX usage and the weather history in the given directory
+/// instead of the config directory of the OS, e.g. so tests don't touch the real files.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to use, `None` to go back to the config directory of the OS.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::MAIN_SEPARATOR;
+/// use get_flight_data::dir_manager::{get_config_dir, set_config_dir};
+///
+/// let dir = std::env::temp_dir().join("gfd_example");
+/// set_config_dir(Some(dir.clone()));
+/// assert_eq!(get_config_dir(), format!("{}{MAIN_SEPARATOR}", dir.display()));
+/// set_config_dir(None);
+/// assert_ne!(get_config_dir(), format!("{}{MAIN_SEPARATOR}", dir.display()));
+/// ```
+pub fn set_config_dir(dir: Option<PathBuf>) {
+    if let Ok(mut current) = CONFIG_DIR.lock() {
+        *current = dir;
+    }
+}
+
+/// Returns the downloads folder of the OS, OFP files are saved there unless the user chose another one.
+/// Falls back to the working directory if the OS has none.
+pub fn default_download_dir() -> PathBuf {
+    UserDirs::new()
+        .and_then(|dirs| dirs.download_dir().map(Path::to_path_buf))
+        .unwrap_or_else(legacy_config_dir)
+}
+
+/// Creates the config and log directories and moves files
+/// from the locations used by older versions into them.
+/// Files already present in the new locations are never overwritten.
+///
+/// # Errors
+///
+/// Returns an error if one of the directories can't be created.
+/// Failing to move a file is only logged.
+pub fn prepare_dirs() -> io::Result<()> {
+    let config_dir = PathBuf::from(get_config_dir());
+    let log_dir = PathBuf::from(get_log_dir());
+    fs::create_dir_all(&config_dir)?;
+    fs::create_dir_all(&log_dir)?;
+
+    migrate_file(&legacy_config_dir(), &config_dir, FILE_NAME);
+    // Runs before logging is set up, so the old log is continued and rotated like any other
+    for file_name in [LOGFILE_NAME, ERROR_FILE_NAME] {
+        migrate_file(&legacy_log_dir(), &log_dir, file_name);
+    }
+
+    Ok(())
+}
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "HaDeSMonsta", "get_flight_data")
+}
+
+fn with_separator(dir: &Path) -> String {
+    let path_string = dir.to_string_lossy();
+    if path_string.ends_with(MAIN_SEPARATOR) {
+        path_string.into_owned()
+    } else {
+        format!("{path_string}{MAIN_SEPARATOR}")
+    }
+}
+
+/// Moves a file, unless the destination already exists.
+fn migrate_file(from_dir: &Path, to_dir: &Path, file_name: &str) {
+    let from = from_dir.join(file_name);
+    let to = to_dir.join(file_name);
+    if from == to || !from.is_file() || to.exists() {
+        return;
+    }
+
+    // Renaming fails across drives, so fall back to copying
+    let result = fs::rename(&from, &to).or_else(|_| {
+        fs::copy(&from, &to)?;
+        fs::remove_file(&from)
+    });
+
+    match result {
+        Ok(()) => info!("Moved {} to {}", from.display(), to.display()),
+        Err(err) => warn!("Failed to move {} to {}: {err}", from.display(), to.display()),
+    }
+}
+
+/// Older versions read `userdata.json` relative to the working directory.
+fn legacy_config_dir() -> PathBuf {
+    current_dir().expect("Failed to get current directory")
 }
 
+/// Older versions logged next to the executable on Windows.
 #[cfg(target_os = "windows")]
-fn get_dir(to_add: &str) -> String {
+fn legacy_log_dir() -> PathBuf {
     let mut path = current_exe()
         .expect("Failed to get current executable")
         .parent()
         .unwrap()
         .to_path_buf();
-    path.push(to_add);
-    let path_string = path.to_str().unwrap();
-    format!("{path_string}\\")
+    path.push(LOG_DIR);
+    path
 }
 
-#[cfg(target_os = "unix")]
-fn get_dir(to_add: &str) -> String {
-    format!("{to_add}/")
+/// Older versions logged relative to the working directory on unix.
+#[cfg(unix)]
+fn legacy_log_dir() -> PathBuf {
+    PathBuf::from(LOG_DIR)
 }
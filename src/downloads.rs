@@ -0,0 +1,82 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::error::Result;
+use crate::logic::{http_client, OfpFile};
+
+/// Where a file of the OFP was saved.
+pub struct Download {
+    pub path: PathBuf,
+    /// The copy in the simulator's flight plan folder, `None` if it wasn't copied
+    pub copied_to: Option<PathBuf>,
+}
+
+/// Checks whether a file can be loaded by a simulator, i.e. is a `.pln` or `.fms` flight plan.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::Path;
+/// use get_flight_data::downloads::is_sim_flight_plan;
+///
+/// assert!(is_sim_flight_plan(Path::new("EDDFLOWW_MFS.pln")));
+/// assert!(is_sim_flight_plan(Path::new("EDDFLOWW.FMS")));
+/// assert!(!is_sim_flight_plan(Path::new("EDDFLOWW_PDF.pdf")));
+/// ```
+pub fn is_sim_flight_plan(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("pln") || extension.eq_ignore_ascii_case("fms"))
+}
+
+/// Downloads a file of the SimBrief OFP, keeping the name SimBrief gave it.
+///
+/// # Arguments
+///
+/// * `file` - The file, as listed in [`crate::logic::FlightPlan::files`].
+/// * `folder` - The folder to save it in, created if missing.
+/// * `sim_folder` - The simulator's flight plan folder, `.pln` and `.fms` files are also copied there.
+///
+/// # Errors
+///
+/// Returns an error if the download fails or a file can't be written.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use std::path::Path;
+/// use get_flight_data::downloads::download;
+/// use get_flight_data::logic::{update_fp, HttpClient};
+///
+/// let flight_plan = update_fp(&HttpClient).await?;
+/// for file in &flight_plan.files {
+///     let download = download(file, Path::new("ofp"), None).await?;
+///     println!("{} saved to {}", file.name, download.path.display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn download(file: &OfpFile, folder: &Path, sim_folder: Option<&Path>) -> Result<Download> {
+    info!("Downloading {} from {}", file.name, file.url);
+    let content = http_client()?.get(&file.url).send().await?.error_for_status()?.bytes().await?;
+
+    fs::create_dir_all(folder)?;
+    let path = folder.join(file.file_name());
+    fs::write(&path, &content)?;
+    info!("Saved {} to {}", file.name, path.display());
+
+    let copied_to = match sim_folder {
+        Some(sim_folder) if is_sim_flight_plan(&path) => {
+            let copy = sim_folder.join(file.file_name());
+            fs::copy(&path, &copy)?;
+            info!("Copied {} to {}", file.name, copy.display());
+            Some(copy)
+        }
+        _ => None,
+    };
+
+    Ok(Download { path, copied_to })
+}
@@ -0,0 +1,262 @@
+//! A briefing engine for embedding this crate in other tools, e.g. another egui sim app.
+//!
+//! The engine is configured once with a builder and either refreshed on demand
+//! or spawned on a tokio runtime, sending [`EngineEvent`]s to a channel the UI polls each frame.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use get_flight_data::engine::{BriefingEngine, EngineEvent};
+//! use get_flight_data::logic::{ApiKey, HttpClient, Network, Source};
+//!
+//! let runtime = tokio::runtime::Runtime::new().unwrap();
+//! let engine = BriefingEngine::for_airports("EDDF", "LOWW")
+//!     .avwx_key(ApiKey::new(Source::Avwx, "my-avwx-token"))
+//!     .network(Network::Vatsim)
+//!     .interval(Duration::from_secs(120))
+//!     .build();
+//! let events = engine.spawn(HttpClient, runtime.handle());
+//!
+//! // In the UI, e.g. once per frame
+//! while let Ok(event) = events.try_recv() {
+//!     match event {
+//!         EngineEvent::Updated(briefing) => println!("{}", briefing.text),
+//!         EngineEvent::NewAtis { icao, letter } => println!("{icao} information {letter}"),
+//!         EngineEvent::Failed(err) => eprintln!("Refresh failed: {err}"),
+//!     }
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::runtime::Handle;
+use tracing::{info, warn};
+
+use crate::briefing::briefing;
+use crate::error::{GfdError, Result};
+use crate::logic::{fetch_data, fetch_ofp, AirportData, ApiClient, ApiKey, FlightPlan, Network, Providers};
+
+/// How often a spawned engine refreshes unless configured otherwise, like the app does.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Where the engine gets the airports from.
+#[derive(Clone)]
+enum Route {
+    Airports { departure: String, arrival: String },
+    SimBrief { username: String },
+}
+
+/// Everything fetched during one refresh.
+pub struct Briefing {
+    /// The SimBrief flight plan, `None` if the engine was built for airports
+    pub flight_plan: Option<FlightPlan>,
+    pub departure: AirportData,
+    pub arrival: AirportData,
+    /// The plain text briefing, as printed by the app
+    pub text: String,
+    /// When the refresh finished
+    pub time: DateTime<Utc>,
+}
+
+/// What a spawned engine reports.
+pub enum EngineEvent {
+    /// A refresh finished
+    Updated(Box<Briefing>),
+    /// The ATIS of an airport changed its information letter since the last refresh,
+    /// sent before the briefing containing it
+    NewAtis { icao: String, letter: char },
+    /// A refresh failed, the engine tries again after the interval
+    Failed(GfdError),
+}
+
+/// Configures a [`BriefingEngine`], created by [`BriefingEngine::for_airports`]
+/// or [`BriefingEngine::for_simbrief`].
+pub struct BriefingEngineBuilder {
+    route: Route,
+    providers: Providers,
+    interval: Duration,
+    disclaimer: String,
+}
+
+impl BriefingEngineBuilder {
+    /// Sets all providers at once, e.g. [`Providers::from_user_data`] to share the settings of the app.
+    pub fn providers(mut self, providers: Providers) -> Self {
+        self.providers = providers;
+        self
+    }
+
+    /// Sets the AVWX token, none by default, so no METAR can be fetched without it.
+    pub fn avwx_key(mut self, key: ApiKey) -> Self {
+        self.providers.avwx = key;
+        self
+    }
+
+    /// Sets the network ATIS, ATC and traffic come from, VATSIM by default.
+    pub fn network(mut self, network: Network) -> Self {
+        self.providers.network = network;
+        self
+    }
+
+    /// Sets the DeepL key to translate non-English ATIS with, `None` to not translate, which is the default.
+    pub fn translation_key(mut self, key: Option<ApiKey>) -> Self {
+        self.providers.translation = key;
+        self
+    }
+
+    /// Sets how often a spawned engine refreshes, [`DEFAULT_INTERVAL`] by default.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the footer of the text briefing, empty by default.
+    pub fn disclaimer(mut self, disclaimer: &str) -> Self {
+        self.disclaimer = disclaimer.to_string();
+        self
+    }
+
+    pub fn build(self) -> BriefingEngine {
+        BriefingEngine {
+            route: self.route,
+            providers: self.providers,
+            interval: self.interval,
+            disclaimer: self.disclaimer,
+        }
+    }
+}
+
+/// Fetches briefings for one flight with fixed providers.
+#[derive(Clone)]
+pub struct BriefingEngine {
+    route: Route,
+    providers: Providers,
+    interval: Duration,
+    disclaimer: String,
+}
+
+impl BriefingEngine {
+    /// Starts configuring an engine for two airports.
+    ///
+    /// # Arguments
+    ///
+    /// * `departure` - The ICAO code of the departure airport.
+    /// * `arrival` - The ICAO code of the arrival airport.
+    pub fn for_airports(departure: &str, arrival: &str) -> BriefingEngineBuilder {
+        BriefingEngine::builder(Route::Airports {
+            departure: departure.trim().to_uppercase(),
+            arrival: arrival.trim().to_uppercase(),
+        })
+    }
+
+    /// Starts configuring an engine for the latest SimBrief flight plan of a user,
+    /// fetched again on every refresh.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The SimBrief username.
+    pub fn for_simbrief(username: &str) -> BriefingEngineBuilder {
+        BriefingEngine::builder(Route::SimBrief { username: username.to_string() })
+    }
+
+    fn builder(route: Route) -> BriefingEngineBuilder {
+        BriefingEngineBuilder {
+            route,
+            // The user data of the app is only read when asked for, see `providers`
+            providers: Providers::default(),
+            interval: DEFAULT_INTERVAL,
+            disclaimer: String::new(),
+        }
+    }
+
+    /// Fetches a briefing once.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Sends the requests, [`HttpClient`](crate::logic::HttpClient) for the real APIs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the flight plan or the airport data can't be fetched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> get_flight_data::error::Result<()> {
+    /// use get_flight_data::engine::BriefingEngine;
+    /// use get_flight_data::logic::{HttpClient, Providers};
+    ///
+    /// let engine = BriefingEngine::for_simbrief("my-simbrief-name")
+    ///     .providers(Providers::from_user_data())
+    ///     .build();
+    /// let briefing = engine.refresh(&HttpClient).await?;
+    /// println!("{} to {}", briefing.departure.icao, briefing.arrival.icao);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn refresh(&self, client: &impl ApiClient) -> Result<Briefing> {
+        let (flight_plan, departure, arrival) = match &self.route {
+            Route::Airports { departure, arrival } => (None, departure.clone(), arrival.clone()),
+            Route::SimBrief { username } => {
+                let flight_plan = fetch_ofp(client, username).await?;
+                let (origin, destination) = (flight_plan.origin.clone(), flight_plan.destination.clone());
+                (Some(flight_plan), origin, destination)
+            }
+        };
+
+        let (departure, arrival) = fetch_data(client, &departure, &arrival, &self.providers).await?;
+        let text = briefing(flight_plan.as_ref(), &departure, &arrival, None, &self.disclaimer);
+        Ok(Briefing { flight_plan, departure, arrival, text, time: Utc::now() })
+    }
+
+    /// Refreshes right away and then every interval on the runtime, until the receiver is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Sends the requests, [`HttpClient`](crate::logic::HttpClient) for the real APIs.
+    /// * `runtime` - The tokio runtime to run the refreshes on.
+    ///
+    /// # Returns
+    ///
+    /// The receiver of the events, meant to be polled with `try_recv` without blocking the UI.
+    pub fn spawn<C>(self, client: C, runtime: &Handle) -> mpsc::Receiver<EngineEvent>
+    where
+        C: ApiClient + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        runtime.spawn(async move {
+            let mut letters: HashMap<String, char> = HashMap::new();
+            loop {
+                let events = match self.refresh(&client).await {
+                    Ok(briefing) => {
+                        let mut events: Vec<_> = [&briefing.departure, &briefing.arrival]
+                            .into_iter()
+                            .filter_map(|airport| {
+                                let letter = airport.atis_letter?;
+                                let previous = letters.insert(airport.icao.clone(), letter);
+                                let changed = previous.is_some_and(|previous| previous != letter);
+                                changed.then(|| EngineEvent::NewAtis { icao: airport.icao.clone(), letter })
+                            })
+                            .collect();
+                        events.push(EngineEvent::Updated(Box::new(briefing)));
+                        events
+                    }
+                    Err(err) => {
+                        warn!("Briefing engine refresh failed: {err}");
+                        vec![EngineEvent::Failed(err)]
+                    }
+                };
+
+                if events.into_iter().any(|event| tx.send(event).is_err()) {
+                    info!("Briefing engine stopped, nobody is listening");
+                    return;
+                }
+                tokio::time::sleep(self.interval).await;
+            }
+        });
+        rx
+    }
+}
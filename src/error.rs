@@ -0,0 +1,61 @@
+use std::fmt::{Display, Formatter};
+
+/// Everything that can go wrong while fetching and parsing flight data.
+#[derive(Debug)]
+pub enum GfdError {
+    /// The HTTP request failed, timed out or returned an error status
+    Request(reqwest::Error),
+    /// A response could not be parsed, `source` names the response
+    InvalidJson { source: String, error: serde_json::Error },
+    /// A response is valid JSON, but lacks a field we need
+    MissingField { source: String, field: String },
+    /// An API answered with an error message instead of data
+    Api { source: String, message: String },
+    /// The ATIS list did not contain a station for the requested purpose
+    UnexpectedAtis(String),
+    /// A downloaded file could not be saved
+    Io(std::io::Error),
+    /// A configured URL can't be parsed
+    InvalidUrl { url: String, message: String },
+}
+
+pub type Result<T> = std::result::Result<T, GfdError>;
+
+impl Display for GfdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GfdError::Request(err) => write!(f, "Request failed: {err}"),
+            GfdError::InvalidJson { source, error } =>
+                write!(f, "{source} response is not valid JSON: {error}"),
+            GfdError::MissingField { source, field } =>
+                write!(f, "{source} response is missing the field \"{field}\""),
+            GfdError::Api { source, message } => write!(f, "{source} returned an error: {message}"),
+            GfdError::UnexpectedAtis(msg) => write!(f, "Unexpected ATIS response: {msg}"),
+            GfdError::Io(err) => write!(f, "Failed to save file: {err}"),
+            GfdError::InvalidUrl { url, message } => write!(f, "Invalid URL \"{url}\": {message}"),
+        }
+    }
+}
+
+impl std::error::Error for GfdError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GfdError::Request(err) => Some(err),
+            GfdError::InvalidJson { error, .. } => Some(error),
+            GfdError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for GfdError {
+    fn from(err: reqwest::Error) -> Self {
+        GfdError::Request(err)
+    }
+}
+
+impl From<std::io::Error> for GfdError {
+    fn from(err: std::io::Error) -> Self {
+        GfdError::Io(err)
+    }
+}
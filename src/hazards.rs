@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use crate::error::{GfdError, Result};
+use crate::logic::{ApiClient, Position};
+
+// International SIGMETs, and SIGMETs and AIRMETs of the US, which are published separately
+const ISIGMET_URI: &str = "https://aviationweather.gov/api/data/isigmet?format=json";
+const AIRSIGMET_URI: &str = "https://aviationweather.gov/api/data/airsigmet?format=json";
+
+/// An active SIGMET or AIRMET.
+#[derive(Clone)]
+pub struct Sigmet {
+    /// `SIGMET` or `AIRMET`
+    pub kind: String,
+    /// e.g. `TS`, `TURB` or `CONVECTIVE`
+    pub hazard: String,
+    /// The FIR it was issued for, `None` for US SIGMETs and AIRMETs
+    pub fir: Option<String>,
+    pub raw: String,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_to: Option<DateTime<Utc>>,
+    /// The corners of the affected area, empty if not published
+    pub area: Vec<Position>,
+}
+
+impl Sigmet {
+    /// Whether the SIGMET warns of thunderstorms.
+    pub fn is_convective(&self) -> bool {
+        matches!(self.hazard.as_str(), "CONVECTIVE" | "TS" | "TSGR")
+    }
+
+    /// Formats the validity, e.g. `12:00z-16:00z`.
+    pub fn validity(&self) -> String {
+        let time = |time: Option<DateTime<Utc>>| time
+            .map(|time| time.format("%H:%Mz").to_string())
+            .unwrap_or_else(|| String::from("?"));
+        format!("{}-{}", time(self.valid_from), time(self.valid_to))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct International {
+    fir_id: Option<String>,
+    #[serde(default)]
+    hazard: String,
+    #[serde(default)]
+    raw_sigmet: String,
+    valid_time_from: Option<i64>,
+    valid_time_to: Option<i64>,
+    #[serde(default)]
+    coords: Vec<Coordinate>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Domestic {
+    air_sigmet_type: Option<String>,
+    #[serde(default)]
+    hazard: String,
+    #[serde(default)]
+    raw_air_sigmet: String,
+    valid_time_from: Option<i64>,
+    valid_time_to: Option<i64>,
+    #[serde(default)]
+    coords: Vec<Coordinate>,
+}
+
+#[derive(Deserialize)]
+struct Coordinate {
+    lat: f64,
+    lon: f64,
+}
+
+/// Fetches all active SIGMETs and AIRMETs from aviationweather.gov.
+///
+/// # Arguments
+///
+/// * `client` - Sends the requests, [`HttpClient`](crate::logic::HttpClient) for the real APIs.
+///
+/// # Errors
+///
+/// Returns an error if one of the requests fails or a response can't be parsed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::logic::HttpClient;
+///
+/// let sigmets = get_flight_data::hazards::fetch_sigmets(&HttpClient).await?;
+/// println!("{} SIGMETs and AIRMETs active", sigmets.len());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_sigmets(client: &impl ApiClient) -> Result<Vec<Sigmet>> {
+    debug!("Calling aviationweather.gov for SIGMETs");
+    let international: Vec<International> =
+        parse(&client.get(ISIGMET_URI).await?, "International SIGMETs")?;
+    let domestic: Vec<Domestic> = parse(&client.get(AIRSIGMET_URI).await?, "US SIGMETs")?;
+    debug!("Got {} international and {} US SIGMETs/AIRMETs", international.len(), domestic.len());
+
+    let international = international.into_iter().map(|sigmet| Sigmet {
+        kind: String::from("SIGMET"),
+        hazard: sigmet.hazard,
+        fir: sigmet.fir_id,
+        raw: sigmet.raw_sigmet.trim().to_string(),
+        valid_from: time(sigmet.valid_time_from),
+        valid_to: time(sigmet.valid_time_to),
+        area: area(&sigmet.coords),
+    });
+    let domestic = domestic.into_iter().map(|sigmet| Sigmet {
+        kind: sigmet.air_sigmet_type.unwrap_or_else(|| String::from("SIGMET")),
+        hazard: sigmet.hazard,
+        fir: None,
+        raw: sigmet.raw_air_sigmet.trim().to_string(),
+        valid_from: time(sigmet.valid_time_from),
+        valid_to: time(sigmet.valid_time_to),
+        area: area(&sigmet.coords),
+    });
+
+    Ok(international.chain(domestic).collect())
+}
+
+/// Selects the SIGMETs and AIRMETs affecting an airport.
+///
+/// With a known position, those whose area contains the airport are selected.
+/// Without one, or for SIGMETs without an area, those issued for a FIR
+/// of the ICAO region of the airport are (`EDGG` for `EDDF`).
+///
+/// # Arguments
+///
+/// * `sigmets` - All active SIGMETs, as returned by [`fetch_sigmets`].
+/// * `icao` - The ICAO code of the airport.
+/// * `position` - The position of the airport, if known.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::hazards::{affecting, Sigmet};
+/// use get_flight_data::logic::Position;
+///
+/// let corner = |latitude, longitude| Position { latitude, longitude };
+/// let sigmet = Sigmet {
+///     kind: String::from("SIGMET"),
+///     hazard: String::from("TS"),
+///     fir: Some(String::from("EDGG")),
+///     raw: String::from("EDGG SIGMET 1 VALID 121200/121600 EDZF- EMBD TS OBS"),
+///     valid_from: None,
+///     valid_to: None,
+///     area: vec![corner(49.0, 8.0), corner(51.0, 8.0), corner(51.0, 10.0), corner(49.0, 10.0)],
+/// };
+///
+/// let frankfurt = corner(50.03, 8.57);
+/// let munich = corner(48.35, 11.79);
+/// assert_eq!(affecting(&[sigmet.clone()], "EDDF", Some(frankfurt)).len(), 1);
+/// assert!(affecting(&[sigmet.clone()], "EDDM", Some(munich)).is_empty());
+/// assert_eq!(affecting(&[sigmet], "EDDM", None).len(), 1);
+/// ```
+pub fn affecting(sigmets: &[Sigmet], icao: &str, position: Option<Position>) -> Vec<Sigmet> {
+    let icao = icao.trim().to_uppercase();
+    let region = icao.get(..2).unwrap_or_default();
+
+    let affected: Vec<Sigmet> = sigmets
+        .iter()
+        .filter(|sigmet| match position {
+            Some(position) if !sigmet.area.is_empty() => contains(&sigmet.area, position),
+            _ => !region.is_empty() && sigmet.fir.as_deref().is_some_and(|fir| fir.starts_with(region)),
+        })
+        .cloned()
+        .collect();
+
+    info!("{} SIGMETs/AIRMETs affect {icao}", affected.len());
+    affected
+}
+
+/// Checks whether a position is inside a polygon, by counting the edges a ray to the east crosses.
+fn contains(area: &[Position], position: Position) -> bool {
+    let mut inside = false;
+    let mut previous = area[area.len() - 1];
+    for &corner in area {
+        let crosses = (corner.latitude > position.latitude) != (previous.latitude > position.latitude);
+        if crosses {
+            let longitude = corner.longitude + (position.latitude - corner.latitude)
+                * (previous.longitude - corner.longitude) / (previous.latitude - corner.latitude);
+            if position.longitude < longitude {
+                inside = !inside;
+            }
+        }
+        previous = corner;
+    }
+    inside
+}
+
+fn parse<T: DeserializeOwned>(response: &str, source: &str) -> Result<Vec<T>> {
+    serde_json::from_str(response).map_err(|error| GfdError::InvalidJson {
+        source: source.to_string(),
+        error,
+    })
+}
+
+fn time(timestamp: Option<i64>) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(timestamp?, 0)
+}
+
+fn area(coords: &[Coordinate]) -> Vec<Position> {
+    coords
+        .iter()
+        .map(|coordinate| Position { latitude: coordinate.lat, longitude: coordinate.lon })
+        .collect()
+}
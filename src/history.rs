@@ -0,0 +1,195 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::json_operations::get_file_dir;
+
+pub const HISTORY_FILE_NAME: &str = "metar_history.json";
+// A week of METARs for the statistics, with an upper bound for stations issuing many specials
+const KEPT_DAYS: i64 = 7;
+const KEPT_OBSERVATIONS: usize = 1000;
+
+/// The values of a METAR we track, in the units AVWX reports for the station.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Observation {
+    pub raw: String,
+    pub qnh: Option<f64>,
+    /// In knots, without gusts
+    pub wind_speed: Option<f64>,
+    pub visibility: Option<f64>,
+    /// e.g. `IFR`, empty in histories of older versions
+    #[serde(default)]
+    pub flight_rules: String,
+    /// When the METAR was issued, `None` in histories of older versions
+    #[serde(default)]
+    pub time: Option<DateTime<Utc>>,
+}
+
+/// How a value changed since the previous METAR.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Steady,
+}
+
+impl Trend {
+    fn between(previous: f64, current: f64) -> Self {
+        if current > previous {
+            Trend::Rising
+        } else if current < previous {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        }
+    }
+
+    pub fn arrow(&self) -> &'static str {
+        match self {
+            Trend::Rising => "↑",
+            Trend::Falling => "↓",
+            Trend::Steady => "→",
+        }
+    }
+}
+
+/// A value of the METAR that is tracked.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Quantity {
+    Qnh,
+    /// The wind speed without gusts
+    Wind,
+    Visibility,
+}
+
+/// A tracked value of the current METAR compared with the previous one.
+#[derive(Debug)]
+pub struct Change {
+    pub quantity: Quantity,
+    pub current: f64,
+    pub previous: f64,
+    pub trend: Trend,
+}
+
+/// The last METARs of each airport, newest last.
+#[derive(Default, Serialize, Deserialize)]
+pub struct MetarHistory {
+    observations: HashMap<String, VecDeque<Observation>>,
+}
+
+impl MetarHistory {
+    /// Adds a METAR of an airport, unless it is the same as the last one.
+    /// METARs older than `KEPT_DAYS` are dropped, and at most `KEPT_OBSERVATIONS` are kept.
+    pub fn record(&mut self, icao: &str, observation: Observation) {
+        let observations = self.observations.entry(icao.to_string()).or_default();
+        if observations.back().is_some_and(|last| last.raw == observation.raw) {
+            return;
+        }
+
+        let oldest = Utc::now() - TimeDelta::days(KEPT_DAYS);
+        let outdated = |observation: &Observation| observation.time.is_some_and(|time| time < oldest);
+        while observations.front().is_some_and(outdated) || observations.len() >= KEPT_OBSERVATIONS {
+            observations.pop_front();
+        }
+        observations.push_back(observation);
+    }
+
+    /// The airports with recorded METARs, sorted by ICAO code.
+    pub fn airports(&self) -> Vec<&str> {
+        let mut airports: Vec<&str> = self.observations.keys().map(String::as_str).collect();
+        airports.sort_unstable();
+        airports
+    }
+
+    /// The recorded METARs of an airport, newest last.
+    pub fn observations(&self, icao: &str) -> impl DoubleEndedIterator<Item = &Observation> {
+        self.observations.get(icao).into_iter().flatten()
+    }
+
+    /// Compares QNH, wind and visibility of the last two METARs of an airport.
+    ///
+    /// # Returns
+    ///
+    /// The values known in both METARs, empty if there is no previous METAR.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::history::{MetarHistory, Observation, Quantity, Trend};
+    ///
+    /// let observation = |raw: &str, qnh, wind_speed| Observation {
+    ///     raw: raw.to_string(),
+    ///     qnh: Some(qnh),
+    ///     wind_speed: Some(wind_speed),
+    ///     visibility: None,
+    ///     ..Observation::default()
+    /// };
+    ///
+    /// let mut history = MetarHistory::default();
+    /// history.record("EDDF", observation("EDDF 121220Z 24008KT Q1015", 1015.0, 8.0));
+    /// history.record("EDDF", observation("EDDF 121250Z 24014KT Q1013", 1013.0, 14.0));
+    ///
+    /// let changes = history.trends("EDDF");
+    /// assert_eq!(changes[0].quantity, Quantity::Qnh);
+    /// assert_eq!(changes[0].trend, Trend::Falling);
+    /// assert_eq!(changes[1].trend, Trend::Rising);
+    /// ```
+    pub fn trends(&self, icao: &str) -> Vec<Change> {
+        let mut observations = self.observations(icao).rev();
+        let (Some(current), Some(previous)) = (observations.next(), observations.next()) else {
+            return Vec::new();
+        };
+
+        let values = [
+            (Quantity::Qnh, current.qnh, previous.qnh),
+            (Quantity::Wind, current.wind_speed, previous.wind_speed),
+            (Quantity::Visibility, current.visibility, previous.visibility),
+        ];
+        values
+            .into_iter()
+            .filter_map(|(quantity, current, previous)| {
+                let (current, previous) = (current?, previous?);
+                Some(Change { quantity, current, previous, trend: Trend::between(previous, current) })
+            })
+            .collect()
+    }
+
+    /// Compares the QNH of the last two METARs of an airport, see [`MetarHistory::trends`].
+    ///
+    /// # Returns
+    ///
+    /// The change, `None` if there is no previous METAR or one of them has no QNH.
+    pub fn qnh_trend(&self, icao: &str) -> Option<Change> {
+        self.trends(icao).into_iter().find(|change| change.quantity == Quantity::Qnh)
+    }
+
+    /// Reads the history saved by [`MetarHistory::save`], an empty one if there is none.
+    pub fn load() -> Self {
+        let Ok(content) = file_path().and_then(fs::read_to_string) else {
+            return MetarHistory::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|err| {
+            warn!("Ignoring invalid {HISTORY_FILE_NAME}: {err}");
+            MetarHistory::default()
+        })
+    }
+
+    /// Saves the history next to `userdata.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn save(&self) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self).expect("History should be serializable");
+        fs::write(file_path()?, content)
+    }
+}
+
+fn file_path() -> io::Result<PathBuf> {
+    Ok(get_file_dir()?.join(HISTORY_FILE_NAME))
+}
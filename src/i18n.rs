@@ -0,0 +1,233 @@
+//! Translations of the user interface.
+//!
+//! The texts live in one file per language in `locales/`, compiled into the binary.
+//! They use the basic syntax of [Fluent](https://projectfluent.org):
+//!
+//! ```text
+//! # A comment
+//! reload-data = Reload data
+//! next-update = Next update in { $time }
+//! long-text = The first line
+//!     and the second one
+//! ```
+//!
+//! Lines indented below a message continue it on a new line.
+//! A message missing in a translation is taken from the English file.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{Mutex, OnceLock};
+
+use tracing::debug;
+
+// Set from the user data when the app starts
+static LANGUAGE: Mutex<Language> = Mutex::new(Language::English);
+static CATALOGS: OnceLock<HashMap<Language, Catalog>> = OnceLock::new();
+
+/// The languages the user interface is available in.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Language {
+    English,
+    German,
+    French,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::French];
+
+    /// The ISO 639-1 code, e.g. `de`, also the name of the file in `locales/`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+        }
+    }
+
+    /// The name of the language in itself, as shown in the settings.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::French => "Français",
+        }
+    }
+
+    /// Finds a language by its code, English for an unknown one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::i18n::Language;
+    ///
+    /// assert_eq!(Language::from_code("de"), Language::German);
+    /// assert_eq!(Language::from_code(" FR "), Language::French);
+    /// assert_eq!(Language::from_code("xx"), Language::English);
+    /// ```
+    pub fn from_code(code: &str) -> Self {
+        let code = code.trim().to_lowercase();
+        Language::ALL.into_iter().find(|language| language.code() == code).unwrap_or(Language::English)
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::German => include_str!("../locales/de.ftl"),
+            Language::French => include_str!("../locales/fr.ftl"),
+        }
+    }
+}
+
+/// The messages of one language by their key.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Reads the messages of a file in the syntax described in the [module docs](self).
+    ///
+    /// Lines that are neither a message, a continuation nor a comment are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::i18n::Catalog;
+    ///
+    /// let catalog = Catalog::parse("# Buttons\nreload-data = Reload data\nhelp = First\n    second\n");
+    /// assert_eq!(catalog.get("reload-data"), Some("Reload data"));
+    /// assert_eq!(catalog.get("help"), Some("First\nsecond"));
+    /// assert_eq!(catalog.get("missing"), None);
+    /// ```
+    pub fn parse(source: &str) -> Self {
+        let mut messages = HashMap::new();
+        let mut current: Option<(String, String)> = None;
+        for line in source.lines() {
+            let continued = line.starts_with([' ', '\t']) && !line.trim().is_empty();
+            if let (true, Some((_, value))) = (continued, current.as_mut()) {
+                value.push('\n');
+                value.push_str(line.trim());
+                continue;
+            }
+            if let Some((key, value)) = current.take() {
+                messages.insert(key, value);
+            }
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                current = Some((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        if let Some((key, value)) = current {
+            messages.insert(key, value);
+        }
+        Catalog { messages }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    /// The keys of all messages, e.g. to check a translation for missing ones.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+}
+
+/// Sets the language of the texts returned by [`tr`] and [`tr_args`].
+pub fn set_language(language: Language) {
+    *LANGUAGE.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = language;
+}
+
+pub fn language() -> Language {
+    *LANGUAGE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The catalog of a language as compiled into the binary.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::i18n::{catalog, Language};
+///
+/// // Every translation is complete
+/// for language in Language::ALL {
+///     for key in catalog(Language::English).keys() {
+///         assert!(catalog(language).get(key).is_some(), "{key} is missing in {}", language.code());
+///     }
+/// }
+/// ```
+pub fn catalog(language: Language) -> &'static Catalog {
+    let catalogs = CATALOGS.get_or_init(|| {
+        Language::ALL.into_iter().map(|language| (language, Catalog::parse(language.source()))).collect()
+    });
+    &catalogs[&language]
+}
+
+/// Translates a message into the current language.
+///
+/// # Returns
+///
+/// The message, in English if it is not translated, or the key itself if there is no such message.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::i18n::{self, tr, Language};
+///
+/// i18n::set_language(Language::German);
+/// assert_eq!(tr("reload-data"), "Daten neu laden");
+/// i18n::set_language(Language::English);
+/// assert_eq!(tr("reload-data"), "Reload data");
+/// assert_eq!(tr("no-such-message"), "no-such-message");
+/// ```
+pub fn tr(key: &str) -> String {
+    tr_args(key, &[])
+}
+
+/// Translates a message into the current language and fills in its variables.
+///
+/// # Arguments
+///
+/// * `key` - The key of the message.
+/// * `args` - The values of the variables by name, e.g. `("time", &"4:59")` for `{ $time }`.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::i18n::{self, tr_args, Language};
+///
+/// i18n::set_language(Language::French);
+/// assert_eq!(tr_args("next-update", &[("time", &"4:59")]), "Prochaine mise à jour dans 4:59");
+/// ```
+pub fn tr_args(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let language = language();
+    let message = catalog(language)
+        .get(key)
+        .or_else(|| catalog(Language::English).get(key))
+        .unwrap_or_else(|| {
+            debug!("No message {key} in {}", language.code());
+            key
+        });
+    fill(message, args)
+}
+
+/// Replaces the variables of a message, `{ $name }`, by their values.
+/// Variables without a value are left as they are.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::i18n::fill;
+///
+/// assert_eq!(fill("{ $icao } is now { $rules }", &[("icao", &"EGLL"), ("rules", &"IFR")]), "EGLL is now IFR");
+/// assert_eq!(fill("Hello { $name }", &[]), "Hello { $name }");
+/// ```
+pub fn fill(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = message.to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${name} }}"), &value.to_string());
+    }
+    text
+}
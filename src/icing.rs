@@ -0,0 +1,58 @@
+//! A rough hint at airframe icing from the surface weather, no replacement for icing forecasts.
+
+use std::ops::RangeInclusive;
+
+// Airframe icing needs visible moisture at temperatures around or below freezing,
+// below -20 °C the droplets are mostly frozen already
+const ICING_TEMPERATURES: RangeInclusive<f64> = -20.0..=2.0;
+// With dewpoint this close to the temperature, the air is near saturation and clouds are low
+const SATURATED_SPREAD: f64 = 3.0;
+// Precipitation and obscurations that are visible moisture
+const MOISTURE: [&str; 11] = ["DZ", "RA", "SN", "SG", "PL", "IC", "GR", "GS", "UP", "BR", "FG"];
+
+/// How conducive the weather at an airport is to airframe icing.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum IcingRisk {
+    /// Visible moisture between +2 and -20 °C
+    Possible,
+    /// Freezing precipitation or fog, which ices up anything it touches
+    Severe,
+}
+
+/// Judges the icing risk from the temperature and weather of a METAR or the expected weather of a TAF.
+///
+/// # Arguments
+///
+/// * `temperature` - The temperature in °C, `None` if unknown.
+/// * `dewpoint` - The dewpoint in °C, `None` if unknown.
+/// * `weather` - Precipitation and obscurations as written in the report, e.g. `-RA` or `FZFG`.
+///
+/// # Returns
+///
+/// The risk, `None` if the weather isn't conducive to icing or the temperature is unknown
+/// without freezing precipitation.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::icing::{risk, IcingRisk};
+///
+/// assert_eq!(risk(Some(-1.0), Some(-2.0), &[String::from("-FZDZ")]), Some(IcingRisk::Severe));
+/// assert_eq!(risk(Some(0.0), Some(-5.0), &[String::from("-SN")]), Some(IcingRisk::Possible));
+/// // Near saturation, the clouds are close to the ground
+/// assert_eq!(risk(Some(1.0), Some(0.0), &[]), Some(IcingRisk::Possible));
+/// assert_eq!(risk(Some(9.0), Some(6.0), &[String::from("RA")]), None);
+/// // Showers in the vicinity don't reach the airport
+/// assert_eq!(risk(Some(-3.0), Some(-9.0), &[String::from("VCSH")]), None);
+/// ```
+pub fn risk(temperature: Option<f64>, dewpoint: Option<f64>, weather: &[String]) -> Option<IcingRisk> {
+    let weather: Vec<&str> = weather.iter().map(String::as_str).filter(|code| !code.starts_with("VC")).collect();
+    if weather.iter().any(|code| code.contains("FZ")) {
+        return Some(IcingRisk::Severe);
+    }
+
+    let temperature = temperature.filter(|temperature| ICING_TEMPERATURES.contains(temperature))?;
+    let precipitation = weather.iter().any(|code| MOISTURE.iter().any(|moisture| code.contains(moisture)));
+    let saturated = dewpoint.is_some_and(|dewpoint| temperature - dewpoint <= SATURATED_SPREAD);
+    (precipitation || saturated).then_some(IcingRisk::Possible)
+}
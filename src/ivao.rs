@@ -0,0 +1,251 @@
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use crate::error::{GfdError, Result};
+use crate::logic::{atis_letter, ApiClient, Atis, HttpClient};
+use crate::vatsim::{self, Datafeed, PilotFlightPlan};
+
+const WHAZZUP_URI: &str = "https://api.ivao.aero/v2/tracker/whazzup";
+
+/// The parts of the IVAO Whazzup (v2) we use.
+#[derive(Deserialize)]
+pub struct Whazzup {
+    pub clients: Clients,
+}
+
+/// The connected clients of the Whazzup.
+#[derive(Deserialize)]
+pub struct Clients {
+    pub pilots: Vec<Pilot>,
+    pub atcs: Vec<Atc>,
+}
+
+/// A connected pilot of the Whazzup.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pilot {
+    pub callsign: String,
+    /// `None` if the pilot hasn't filed a flight plan
+    pub flight_plan: Option<FlightPlan>,
+}
+
+/// The filed flight plan of a pilot, the airports may be missing for VFR flights.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlightPlan {
+    pub departure_id: Option<String>,
+    pub arrival_id: Option<String>,
+}
+
+/// A connected ATC station of the Whazzup, IVAO attaches the ATIS to the station.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Atc {
+    pub callsign: String,
+    pub atc_session: AtcSession,
+    pub atis: Option<StationAtis>,
+}
+
+#[derive(Deserialize)]
+pub struct AtcSession {
+    /// In MHz, e.g. `119.9`
+    pub frequency: f64,
+}
+
+/// The ATIS of a station, the first line usually repeats the callsign.
+#[derive(Deserialize)]
+pub struct StationAtis {
+    pub lines: Vec<String>,
+    /// The information letter
+    pub revision: Option<String>,
+}
+
+/// Downloads the current IVAO Whazzup.
+///
+/// # Arguments
+///
+/// * `client` - Sends the request, [`HttpClient`](crate::logic::HttpClient) for the real API.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response is not a valid Whazzup.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::logic::HttpClient;
+///
+/// let whazzup = get_flight_data::ivao::fetch_whazzup(&HttpClient).await?;
+/// println!("{} controllers online", whazzup.clients.atcs.len());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_whazzup(client: &impl ApiClient) -> Result<Whazzup> {
+    debug!("Calling IVAO Whazzup");
+    let response = client.get(WHAZZUP_URI).await?;
+    debug!("Got IVAO Whazzup");
+
+    serde_json::from_str(&response).map_err(|error| GfdError::InvalidJson {
+        source: String::from("IVAO Whazzup"),
+        error,
+    })
+}
+
+/// Converts the Whazzup to the shape of the VATSIM datafeed,
+/// so stations, coverage and traffic are found the same way for both networks.
+///
+/// The facility is derived from the callsign suffix (`EDDF_TWR`),
+/// stations with an ATIS are listed as ATIS as well.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::ivao::{to_datafeed, Whazzup};
+/// use get_flight_data::vatsim::{has_atis, stations_for_airport, traffic};
+///
+/// let whazzup: Whazzup = serde_json::from_str(r#"{ "clients": {
+///     "pilots": [{ "callsign": "DLH1", "flightPlan": { "departureId": "EDDF", "arrivalId": "LOWW" } }],
+///     "atcs": [{
+///         "callsign": "EDDF_TWR",
+///         "atcSession": { "frequency": 119.9 },
+///         "atis": { "lines": ["EDDF_TWR", "Frankfurt Information A"], "revision": "A" }
+///     }]
+/// } }"#).unwrap();
+///
+/// let datafeed = to_datafeed(&whazzup);
+/// assert_eq!(stations_for_airport(&datafeed, "EDDF")[0].frequency, "119.900");
+/// assert!(has_atis(&datafeed, "EDDF"));
+/// assert_eq!(traffic(&datafeed, "EDDF").outbound, 1);
+/// ```
+pub fn to_datafeed(whazzup: &Whazzup) -> Datafeed {
+    let atcs = &whazzup.clients.atcs;
+    let frequency = |atc: &Atc| format!("{:.3}", atc.atc_session.frequency);
+
+    let controllers = atcs
+        .iter()
+        .map(|atc| vatsim::Controller {
+            callsign: atc.callsign.clone(),
+            frequency: frequency(atc),
+            facility: facility(&atc.callsign),
+        })
+        .collect();
+
+    let atis = atcs
+        .iter()
+        .filter(|atc| atc.atis.is_some())
+        .map(|atc| vatsim::Atis {
+            callsign: atc.callsign.clone(),
+            frequency: frequency(atc),
+            atis_code: atc.atis.as_ref().and_then(|atis| atis.revision.clone()),
+            text_atis: atc.atis.as_ref().map(|atis| atis.lines.clone()),
+        })
+        .collect();
+
+    let pilots = whazzup.clients.pilots
+        .iter()
+        .map(|pilot| vatsim::Pilot {
+            callsign: pilot.callsign.clone(),
+            flight_plan: pilot.flight_plan.as_ref().map(|flight_plan| PilotFlightPlan {
+                departure: flight_plan.departure_id.clone().unwrap_or_default(),
+                arrival: flight_plan.arrival_id.clone().unwrap_or_default(),
+            }),
+        })
+        .collect();
+
+    Datafeed { controllers, atis, pilots }
+}
+
+/// Maps the callsign suffix to the numeric facility of the VATSIM datafeed, `0` for others.
+fn facility(callsign: &str) -> u8 {
+    match callsign.rsplit('_').next().unwrap_or_default() {
+        "DEL" => 2,
+        "GND" => 3,
+        "TWR" => 4,
+        "APP" | "DEP" => 5,
+        "CTR" => 6,
+        _ => 0,
+    }
+}
+
+/// Finds the ATIS of an airport in the Whazzup and formats it like the VATSIM one.
+///
+/// IVAO has no separate departure and arrival ATIS,
+/// the ATIS of the lowest station of the airport is used.
+///
+/// # Arguments
+///
+/// * `whazzup` - The current IVAO Whazzup.
+/// * `icao` - The ICAO code of the airport.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::ivao::{atis, Whazzup};
+///
+/// let whazzup: Whazzup = serde_json::from_str(r#"{ "clients": {
+///     "pilots": [],
+///     "atcs": [{
+///         "callsign": "EDDF_TWR",
+///         "atcSession": { "frequency": 119.9 },
+///         "atis": { "lines": ["EDDF_TWR", "Frankfurt Information A", "RWY 25C"], "revision": "A" }
+///     }]
+/// } }"#).unwrap();
+///
+/// let atis = atis(&whazzup, "EDDF");
+/// assert_eq!(atis.text, "Frankfurt Information A\nRWY 25C");
+/// assert_eq!(atis.letter, Some('A'));
+/// ```
+pub fn atis(whazzup: &Whazzup, icao: &str) -> Atis {
+    let icao = icao.trim().to_uppercase();
+
+    let station = whazzup.clients.atcs
+        .iter()
+        .filter(|atc| atc.callsign.split('_').next() == Some(icao.as_str()))
+        .filter_map(|atc| Some((atc, atc.atis.as_ref()?)))
+        .min_by_key(|(atc, _)| facility(&atc.callsign));
+
+    let Some((atc, station_atis)) = station else {
+        return Atis { text: String::from("No IVAO ATIS available"), letter: None, translation: None };
+    };
+
+    let text = station_atis.lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && *line != atc.callsign)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let letter = station_atis.revision
+        .as_deref()
+        .and_then(|revision| revision.trim().chars().next())
+        .filter(char::is_ascii_alphabetic)
+        .map(|letter| letter.to_ascii_uppercase())
+        .or_else(|| atis_letter(&text));
+    info!("Information letter of IVAO ATIS of {icao}: {letter:?}");
+
+    Atis { text, letter, translation: None }
+}
+
+/// Fetches the Whazzup and checks whether an airport has an ATIS online.
+///
+/// # Errors
+///
+/// Returns an error if the Whazzup can't be fetched.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// if get_flight_data::ivao::atis_online("EHAM").await? {
+///     println!("EHAM ATIS is online");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn atis_online(icao: &str) -> Result<bool> {
+    let whazzup = fetch_whazzup(&HttpClient).await?;
+    let online = vatsim::has_atis(&to_datafeed(&whazzup), icao);
+    info!("IVAO ATIS of {icao} online: {online}");
+    Ok(online)
+}
@@ -1,77 +1,304 @@
-use std::fs::{File, OpenOptions};
-use std::io::Write;
-use serde_json::{from_reader, Value};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
 
-const NAME_FIELD: &str = "simBrief_userName";
-const KEY_FIELD: &str = "api_token";
-const FILE_PATH: &str = "userdata.json";
+use crate::dir_manager::get_config_dir;
+use crate::logic::{ApiAuth, ApiKey, Source};
 
+pub const FILE_NAME: &str = "userdata.json";
+// Identifies the API key in the credential store of the OS
+const KEYRING_SERVICE: &str = "get_flight_data";
+const KEYRING_USER: &str = "avwx_api_token";
+const TRANSLATION_KEYRING_USER: &str = "deepl_api_key";
+const PROXY_KEYRING_USER: &str = "proxy_password";
+const PROFILES_FIELD: &str = "profiles";
+const ACTIVE_PROFILE_FIELD: &str = "active_profile";
+const API_AUTH_FIELD: &str = "api_headers";
+const RECENT_AIRPORTS_FIELD: &str = "recent_airports";
+const FAVORITE_AIRPORTS_FIELD: &str = "favorite_airports";
+const EXTRA_AIRPORTS_FIELD: &str = "extra_airports";
+/// Recently briefed airports kept, the oldest is dropped first.
+pub const MAX_RECENT_AIRPORTS: usize = 10;
+
+#[derive(Clone, Copy)]
 pub enum JsonKey {
     Name,
+    /// AVWX API token
     Key,
+    /// Footer added to exported briefings
+    Disclaimer,
+    /// `"true"` if desktop notifications are enabled
+    Notifications,
+    /// DeepL API key for translating non-English ATIS, optional
+    TranslationKey,
+    /// `"true"` if the briefing is printed whenever a new flight plan is loaded
+    AutoPrint,
+    /// The online network, `"VATSIM"`, `"IVAO"` or `"PilotEdge"`
+    Network,
+    /// `"true"` if the METAR history is kept on disk between starts
+    KeepHistory,
+    /// Folder OFP files are downloaded to, empty for the downloads folder of the OS
+    DownloadDir,
+    /// The simulator's flight plan folder downloaded `.pln` and `.fms` files are copied to, optional
+    SimFlightPlanDir,
+    /// Crosswind in knots above which a runway is flagged
+    CrosswindLimit,
+    /// Daily period like `"23:00-07:00"` without automatic updates, empty for none
+    QuietHours,
+    /// Seconds API responses are reused for the same request, `"0"` to always fetch
+    CacheSeconds,
+    /// Tries per request before giving up on timeouts and server errors, `"1"` to never retry
+    RetryAttempts,
+    /// Proxy all requests go through, e.g. `"http://proxy:8080"` or `"socks5://proxy:1080"`,
+    /// empty to use the `HTTPS_PROXY` environment variable if set
+    Proxy,
+    /// Username and password for the proxy, empty if it needs none
+    ProxyUser,
+    ProxyPassword,
+    /// Old log files kept besides the current one
+    LogFilesKept,
+    /// Size in megabytes after which the log file is rotated
+    LogMaxMegabytes,
+    /// API endpoints for self-hosted instances or mirrors, empty for the default
+    AvwxUrl,
+    SimBriefUrl,
+    VatsimAtisUrl,
+    VatsimDatafeedUrl,
+    /// Folder briefings are exported to, empty for the download folder
+    BriefingDir,
+    /// Format of exported briefings, `"md"` or `"txt"`
+    BriefingFormat,
+    /// `"true"` if the flight is shown as Discord Rich Presence
+    DiscordPresence,
+    /// ID of the Discord application the presence is published as
+    DiscordAppId,
+    /// The simulator the aircraft is read from, `"MSFS"` or `"X-Plane"`, empty for none
+    Simulator,
+    /// Minutes between automatic refreshes before departure, in cruise and near the arrival
+    RefreshGroundMinutes,
+    RefreshCruiseMinutes,
+    RefreshArrivalMinutes,
+    /// Minutes before the ETA, and nautical miles from the destination, within which the arrival starts
+    ArrivalWindowMinutes,
+    ArrivalRangeNm,
+    /// Age of a METAR in minutes above which it is shown in yellow and in red
+    MetarAgeWarningMinutes,
+    MetarAgeAlertMinutes,
+    /// Zoom factor of the whole window, 1 for the default size
+    UiScale,
+    /// Code of the language of the user interface, e.g. `de`
+    Language,
+    /// Whether to ask GitHub for a newer release on startup
+    CheckForUpdates,
 }
 
-/// Retrieves JSON data from a given key in a file. If the file does not exist or is not a valid JSON format,
-/// it creates a new JSON file with empty values but valid keys.
-///
-/// # Arguments
+impl JsonKey {
+    const ALL: [JsonKey; 38] = [
+        JsonKey::Name,
+        JsonKey::Key,
+        JsonKey::Disclaimer,
+        JsonKey::Notifications,
+        JsonKey::TranslationKey,
+        JsonKey::AutoPrint,
+        JsonKey::Network,
+        JsonKey::KeepHistory,
+        JsonKey::DownloadDir,
+        JsonKey::SimFlightPlanDir,
+        JsonKey::CrosswindLimit,
+        JsonKey::QuietHours,
+        JsonKey::CacheSeconds,
+        JsonKey::RetryAttempts,
+        JsonKey::Proxy,
+        JsonKey::ProxyUser,
+        JsonKey::ProxyPassword,
+        JsonKey::LogFilesKept,
+        JsonKey::LogMaxMegabytes,
+        JsonKey::AvwxUrl,
+        JsonKey::SimBriefUrl,
+        JsonKey::VatsimAtisUrl,
+        JsonKey::VatsimDatafeedUrl,
+        JsonKey::BriefingDir,
+        JsonKey::BriefingFormat,
+        JsonKey::DiscordPresence,
+        JsonKey::DiscordAppId,
+        JsonKey::Simulator,
+        JsonKey::RefreshGroundMinutes,
+        JsonKey::RefreshCruiseMinutes,
+        JsonKey::RefreshArrivalMinutes,
+        JsonKey::ArrivalWindowMinutes,
+        JsonKey::ArrivalRangeNm,
+        JsonKey::MetarAgeWarningMinutes,
+        JsonKey::MetarAgeAlertMinutes,
+        JsonKey::UiScale,
+        JsonKey::Language,
+        JsonKey::CheckForUpdates,
+    ];
+
+    /// The name of the field in `userdata.json`.
+    fn field(&self) -> &'static str {
+        match self {
+            JsonKey::Name => "simBrief_userName",
+            JsonKey::Key => "api_token",
+            JsonKey::Disclaimer => "disclaimer",
+            JsonKey::Notifications => "notifications",
+            JsonKey::TranslationKey => "deepl_api_key",
+            JsonKey::AutoPrint => "auto_print",
+            JsonKey::Network => "network",
+            JsonKey::KeepHistory => "keep_metar_history",
+            JsonKey::DownloadDir => "download_dir",
+            JsonKey::SimFlightPlanDir => "sim_flight_plan_dir",
+            JsonKey::CrosswindLimit => "crosswind_limit",
+            JsonKey::QuietHours => "quiet_hours",
+            JsonKey::CacheSeconds => "response_cache_seconds",
+            JsonKey::RetryAttempts => "retry_attempts",
+            JsonKey::Proxy => "proxy",
+            JsonKey::ProxyUser => "proxy_user",
+            JsonKey::ProxyPassword => "proxy_password",
+            JsonKey::LogFilesKept => "log_files_kept",
+            JsonKey::LogMaxMegabytes => "log_max_megabytes",
+            JsonKey::AvwxUrl => "avwx_url",
+            JsonKey::SimBriefUrl => "simbrief_url",
+            JsonKey::VatsimAtisUrl => "vatsim_atis_url",
+            JsonKey::VatsimDatafeedUrl => "vatsim_datafeed_url",
+            JsonKey::BriefingDir => "briefing_dir",
+            JsonKey::BriefingFormat => "briefing_format",
+            JsonKey::DiscordPresence => "discord_presence",
+            JsonKey::DiscordAppId => "discord_app_id",
+            JsonKey::Simulator => "simulator",
+            JsonKey::RefreshGroundMinutes => "refresh_ground_minutes",
+            JsonKey::RefreshCruiseMinutes => "refresh_cruise_minutes",
+            JsonKey::RefreshArrivalMinutes => "refresh_arrival_minutes",
+            JsonKey::ArrivalWindowMinutes => "arrival_window_minutes",
+            JsonKey::ArrivalRangeNm => "arrival_range_nm",
+            JsonKey::MetarAgeWarningMinutes => "metar_age_warning_minutes",
+            JsonKey::MetarAgeAlertMinutes => "metar_age_alert_minutes",
+            JsonKey::UiScale => "ui_scale",
+            JsonKey::Language => "language",
+            JsonKey::CheckForUpdates => "check_for_updates",
+        }
+    }
+
+    fn default_value(&self) -> &'static str {
+        match self {
+            JsonKey::Name
+            | JsonKey::Key
+            | JsonKey::TranslationKey
+            | JsonKey::DownloadDir
+            | JsonKey::SimFlightPlanDir
+            | JsonKey::QuietHours
+            | JsonKey::Proxy
+            | JsonKey::ProxyUser
+            | JsonKey::ProxyPassword
+            | JsonKey::AvwxUrl
+            | JsonKey::SimBriefUrl
+            | JsonKey::VatsimAtisUrl
+            | JsonKey::VatsimDatafeedUrl
+            | JsonKey::BriefingDir
+            | JsonKey::DiscordAppId
+            | JsonKey::Simulator => "",
+            JsonKey::Disclaimer => "For simulation use only, not for real world navigation.",
+            // Opt-in, notifications are easily found annoying
+            JsonKey::Notifications
+            | JsonKey::AutoPrint
+            | JsonKey::KeepHistory
+            | JsonKey::DiscordPresence
+            | JsonKey::CheckForUpdates => "false",
+            JsonKey::Network => "VATSIM",
+            JsonKey::CrosswindLimit => "20",
+            JsonKey::CacheSeconds => "60",
+            JsonKey::RetryAttempts => "3",
+            JsonKey::LogFilesKept => "7",
+            JsonKey::LogMaxMegabytes => "10",
+            JsonKey::BriefingFormat => "md",
+            JsonKey::RefreshGroundMinutes | JsonKey::RefreshArrivalMinutes => "5",
+            JsonKey::RefreshCruiseMinutes => "30",
+            JsonKey::ArrivalWindowMinutes => "45",
+            JsonKey::ArrivalRangeNm => "150",
+            JsonKey::MetarAgeWarningMinutes => "75",
+            JsonKey::MetarAgeAlertMinutes => "120",
+            JsonKey::UiScale => "1",
+            JsonKey::Language => "en",
+        }
+    }
+
+    /// The user of the entry in the credential store, `None` for data that is no secret.
+    fn keyring_user(&self) -> Option<&'static str> {
+        match self {
+            JsonKey::Key => Some(KEYRING_USER),
+            JsonKey::TranslationKey => Some(TRANSLATION_KEYRING_USER),
+            JsonKey::ProxyPassword => Some(PROXY_KEYRING_USER),
+            JsonKey::Name
+            | JsonKey::Disclaimer
+            | JsonKey::Notifications
+            | JsonKey::AutoPrint
+            | JsonKey::Network
+            | JsonKey::KeepHistory
+            | JsonKey::DownloadDir
+            | JsonKey::SimFlightPlanDir
+            | JsonKey::CrosswindLimit
+            | JsonKey::QuietHours
+            | JsonKey::CacheSeconds
+            | JsonKey::RetryAttempts
+            | JsonKey::Proxy
+            | JsonKey::ProxyUser
+            | JsonKey::LogFilesKept
+            | JsonKey::LogMaxMegabytes
+            | JsonKey::AvwxUrl
+            | JsonKey::SimBriefUrl
+            | JsonKey::VatsimAtisUrl
+            | JsonKey::VatsimDatafeedUrl
+            | JsonKey::BriefingDir
+            | JsonKey::BriefingFormat
+            | JsonKey::DiscordPresence
+            | JsonKey::DiscordAppId
+            | JsonKey::Simulator
+            | JsonKey::RefreshGroundMinutes
+            | JsonKey::RefreshCruiseMinutes
+            | JsonKey::RefreshArrivalMinutes
+            | JsonKey::ArrivalWindowMinutes
+            | JsonKey::ArrivalRangeNm
+            | JsonKey::MetarAgeWarningMinutes
+            | JsonKey::MetarAgeAlertMinutes
+            | JsonKey::UiScale
+            | JsonKey::Language
+            | JsonKey::CheckForUpdates => None,
+        }
+    }
+}
+
+/// Retrieves the stored user data for a given key.
 ///
-/// * `key` - An enum value representing the key to retrieve from the JSON data.
+/// Everything but the API keys is read from `userdata.json`, the API keys from the credential store of the OS.
+/// An API key found in `userdata.json` is moved to the credential store.
+/// If there is no usable credential store, the API key stays in `userdata.json`.
 ///
-/// # Errors
+/// # Arguments
 ///
-/// This function returns an empty string under the following conditions:
-/// * If the file does not exist, it is created anew with empty values but valid keys.
-/// * If the file exists but cannot be read or written.
-/// * If the file is not a valid JSON file, it is overwritten with a valid JSON structure containing empty values.
+/// * `key` - An enum value representing the key to retrieve.
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,no_run
+/// use get_flight_data::json_operations::{get_json_data, JsonKey};
+///
 /// let json_data = get_json_data(JsonKey::Name);
 /// assert_eq!(json_data, "value");
 /// ```
 pub fn get_json_data(key: JsonKey) -> String {
-    let file = OpenOptions::new()
-        .read(true) // Read access
-        .write(true) // Write access
-        .create(true) // Create if it does not exist
-        .open(&FILE_PATH) // And finally open
-        .unwrap_or_else(|e| panic!("Error opening {}\nError: {e}", FILE_PATH));
-
-    let result: Result<Value, _> = from_reader(&file);
-    let json;
-
-    match result {
-        Ok(value) => json = value,
-        Err(_) => {
-            println!("Error reading from file {FILE_PATH}, will create it");
-            drop(file);
-            let mut file = OpenOptions::new()
-                .write(true)
-                .truncate(true)
-                .open(&FILE_PATH)
-                .expect("Unable to open invalid file again");
-            let default_json = format!(
-                "{{\n\
-                \t\"{NAME_FIELD}\": \"\",\n\
-                \t\"{KEY_FIELD}\": \"\"\n\
-                }}"
-            );
-            write!(file, "{default_json}").expect("Failed writing to file after seeing it's not proper json");
-            return String::new();
-        }
+    match key.keyring_user() {
+        Some(user) => get_secret(key, user),
+        None => read_file_data(key),
     }
-
-    let val = match key {
-        JsonKey::Name => json.get(NAME_FIELD).unwrap().to_string(),
-        JsonKey::Key => json.get(KEY_FIELD).unwrap().to_string(),
-    };
-
-    val[1..val.len() - 1].to_string()
 }
 
-/// Sets the JSON data based on a given key and value.
+/// Sets the stored user data for a given key.
+///
+/// API keys are written to the credential store of the OS and removed from `userdata.json`.
+/// If there is no usable credential store, it is written to `userdata.json` instead.
 ///
 /// # Arguments
 ///
@@ -80,43 +307,493 @@ pub fn get_json_data(key: JsonKey) -> String {
 ///
 /// # Panics
 ///
-/// This function will panic if it is unable to create or write to the file specified by `FILE_PATH`.
+/// This function will panic if it is unable to create or write to the file `FILE_NAME` in the config directory.
 ///
 /// # Examples
 ///
-/// ```
-/// use my_module::JsonKey;
+/// ```rust,no_run
+/// use get_flight_data::json_operations::{set_json_data, JsonKey};
 ///
 /// let key = JsonKey::Name;
 /// let val = String::from("John Doe");
 ///
-/// my_module::set_json_data(key, val);
+/// set_json_data(key, &val);
 /// ```
 pub fn set_json_data(key: JsonKey, val: &str) {
-    let other_val = match key {
-        JsonKey::Name => get_json_data(JsonKey::Key),
-        JsonKey::Key => get_json_data(JsonKey::Name),
+    let Some(user) = key.keyring_user() else {
+        return write_file_data(key, val);
+    };
+
+    match set_keyring_password(user, val) {
+        // Don't leave an old plain text key behind
+        Ok(()) => write_file_data(key, ""),
+        Err(err) => {
+            warn!("Unable to use the OS keyring, storing {} in {FILE_NAME}: {err}", key.field());
+            write_file_data(key, val);
+        }
+    }
+}
+
+/// Returns the API key of a provider and the header to send it in.
+/// Providers without a configured header use their default one.
+///
+/// # Arguments
+///
+/// * `source` - The provider, one of [`Source::KEYED`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use get_flight_data::json_operations::get_api_key;
+/// use get_flight_data::logic::Source;
+///
+/// let api_key = get_api_key(Source::Avwx);
+/// println!("AVWX key is sent in {}", api_key.auth.header);
+/// ```
+pub fn get_api_key(source: Source) -> ApiKey {
+    let key = match source {
+        Source::Avwx => get_json_data(JsonKey::Key),
+        Source::DeepL => get_json_data(JsonKey::TranslationKey),
+        Source::Noaa
+        | Source::Vatsim
+        | Source::Ivao
+        | Source::PilotEdge
+        | Source::SimBrief
+        | Source::OpenMeteo
+        | Source::AviationWeather => String::new(),
+    };
+    ApiKey { key, auth: get_api_auth(source) }
+}
+
+/// Returns the header a provider's API key is sent in, its default one if none is configured.
+pub fn get_api_auth(source: Source) -> ApiAuth {
+    read_file()
+        .get(API_AUTH_FIELD)
+        .and_then(|auths| auths.get(source.name()))
+        .cloned()
+        .and_then(|auth| serde_json::from_value(auth).ok())
+        .unwrap_or_else(|| source.default_auth())
+}
+
+/// Stores the header a provider's API key is sent in.
+/// The header is no secret, so it is kept in `userdata.json`.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to write to the file `FILE_NAME` in the config directory.
+pub fn set_api_auth(source: Source, auth: &ApiAuth) {
+    let mut json = read_file();
+    if !json.is_object() {
+        json = default_json();
+    }
+    if !json[API_AUTH_FIELD].is_object() {
+        json[API_AUTH_FIELD] = Value::Object(Default::default());
+    }
+    json[API_AUTH_FIELD][source.name()] = serde_json::to_value(auth).expect("Headers should be serializable");
+
+    write_file(&json);
+}
+
+/// A named set of credentials, e.g. for one virtual airline.
+/// The API key of a profile is kept in the credential store of the OS, like the active one.
+#[derive(Serialize, Deserialize)]
+struct Profile {
+    name: String,
+    #[serde(rename = "simBrief_userName")]
+    username: String,
+    // Only used if the OS keyring is not usable
+    #[serde(default)]
+    api_token: String,
+}
+
+/// Returns the names of all stored profiles in the order they were created.
+pub fn get_profile_names() -> Vec<String> {
+    read_profiles().into_iter().map(|profile| profile.name).collect()
+}
+
+/// Returns the name of the active profile, or an empty string if no profile is active.
+pub fn get_active_profile() -> String {
+    read_file()
+        .get(ACTIVE_PROFILE_FIELD)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Stores the current credentials as a profile and makes it the active one.
+/// An existing profile with the same name is overwritten.
+///
+/// # Arguments
+///
+/// * `name` - The name of the profile.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to write to the file `FILE_NAME` in the config directory.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use get_flight_data::json_operations::{save_profile, switch_profile};
+///
+/// save_profile("My VA");
+/// switch_profile("My other VA");
+/// ```
+pub fn save_profile(name: &str) {
+    let username = get_json_data(JsonKey::Name);
+    let api_key = get_json_data(JsonKey::Key);
+    let api_token = match set_keyring_password(&profile_keyring_user(name), &api_key) {
+        Ok(()) => String::new(),
+        Err(err) => {
+            warn!("Unable to use the OS keyring, storing API-Key of profile {name} in {FILE_NAME}: {err}");
+            api_key
+        }
+    };
+
+    let mut profiles = read_profiles();
+    profiles.retain(|profile| profile.name != name);
+    profiles.push(Profile { name: name.to_string(), username, api_token });
+    write_profiles(&profiles, name);
+    info!("Saved profile {name}");
+}
+
+/// Makes a stored profile the active one by loading its credentials.
+///
+/// # Arguments
+///
+/// * `name` - The name of the profile.
+///
+/// # Returns
+///
+/// `false` if there is no profile with this name.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to write to the file `FILE_NAME` in the config directory.
+pub fn switch_profile(name: &str) -> bool {
+    let profiles = read_profiles();
+    let Some(profile) = profiles.iter().find(|profile| profile.name == name) else {
+        return false;
+    };
+
+    let api_key = if profile.api_token.is_empty() {
+        get_keyring_password(&profile_keyring_user(name)).unwrap_or_default()
+    } else {
+        profile.api_token.clone()
+    };
+
+    set_json_data(JsonKey::Name, &profile.username);
+    set_json_data(JsonKey::Key, &api_key);
+    write_profiles(&profiles, name);
+    info!("Switched to profile {name}");
+    true
+}
+
+/// Deletes a stored profile. The active credentials are kept.
+///
+/// # Arguments
+///
+/// * `name` - The name of the profile.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to write to the file `FILE_NAME` in the config directory.
+pub fn delete_profile(name: &str) {
+    let mut profiles = read_profiles();
+    profiles.retain(|profile| profile.name != name);
+
+    let active = get_active_profile();
+    let active = if active == name { "" } else { active.as_str() };
+    write_profiles(&profiles, active);
+
+    if let Ok(entry) = Entry::new(KEYRING_SERVICE, &profile_keyring_user(name)) {
+        let _ = entry.delete_credential();
+    }
+    info!("Deleted profile {name}");
+}
+
+fn read_profiles() -> Vec<Profile> {
+    read_file()
+        .get(PROFILES_FIELD)
+        .cloned()
+        .and_then(|profiles| serde_json::from_value(profiles).ok())
+        .unwrap_or_default()
+}
+
+fn write_profiles(profiles: &[Profile], active: &str) {
+    let mut json = read_file();
+    if !json.is_object() {
+        json = default_json();
+    }
+    json[PROFILES_FIELD] = serde_json::to_value(profiles).expect("Profiles should be serializable");
+    json[ACTIVE_PROFILE_FIELD] = Value::from(active);
+
+    write_file(&json);
+}
+
+/// Returns the recently briefed airports, the latest first.
+pub fn get_recent_airports() -> Vec<String> {
+    read_airports(RECENT_AIRPORTS_FIELD)
+}
+
+/// Remembers briefed airports as the latest ones.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to write to the file `FILE_NAME` in the config directory.
+pub fn add_recent_airports(icaos: &[&str]) {
+    let mut recent = get_recent_airports();
+    // The first one given ends up first
+    for icao in icaos.iter().rev() {
+        push_recent(&mut recent, icao);
+    }
+    write_airports(RECENT_AIRPORTS_FIELD, &recent);
+}
+
+/// Moves an airport to the front of a list of recent airports, dropping the oldest ones
+/// beyond [`MAX_RECENT_AIRPORTS`].
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::json_operations::push_recent;
+///
+/// let mut recent = vec![String::from("EDDF"), String::from("EGLL")];
+/// push_recent(&mut recent, "EGLL");
+/// assert_eq!(recent, ["EGLL", "EDDF"]);
+///
+/// push_recent(&mut recent, "LOWW");
+/// assert_eq!(recent, ["LOWW", "EGLL", "EDDF"]);
+/// ```
+pub fn push_recent(recent: &mut Vec<String>, icao: &str) {
+    recent.retain(|recent| recent != icao);
+    recent.insert(0, icao.to_string());
+    recent.truncate(MAX_RECENT_AIRPORTS);
+}
+
+/// Returns the airports starred by the user, in the order they were starred.
+pub fn get_favorite_airports() -> Vec<String> {
+    read_airports(FAVORITE_AIRPORTS_FIELD)
+}
+
+/// Stars or unstars an airport.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to write to the file `FILE_NAME` in the config directory.
+pub fn set_favorite_airport(icao: &str, favorite: bool) {
+    let mut favorites = get_favorite_airports();
+    favorites.retain(|favorite| favorite != icao);
+    if favorite {
+        favorites.push(icao.to_string());
+    }
+    write_airports(FAVORITE_AIRPORTS_FIELD, &favorites);
+}
+
+/// Returns the airports monitored besides departure and arrival, in the order they were added.
+pub fn get_extra_airports() -> Vec<String> {
+    read_airports(EXTRA_AIRPORTS_FIELD)
+}
+
+/// Stores the airports monitored besides departure and arrival.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to write to the file `FILE_NAME` in the config directory.
+pub fn set_extra_airports(icaos: &[String]) {
+    write_airports(EXTRA_AIRPORTS_FIELD, icaos);
+}
+
+fn read_airports(field: &str) -> Vec<String> {
+    read_file()
+        .get(field)
+        .cloned()
+        .and_then(|airports| serde_json::from_value(airports).ok())
+        .unwrap_or_default()
+}
+
+fn write_airports(field: &str, airports: &[String]) {
+    let mut json = read_file();
+    if !json.is_object() {
+        json = default_json();
+    }
+    json[field] = Value::from(airports);
+
+    write_file(&json);
+}
+
+fn profile_keyring_user(name: &str) -> String {
+    format!("{KEYRING_USER}@{name}")
+}
+
+fn get_keyring_password(user: &str) -> keyring::Result<String> {
+    Entry::new(KEYRING_SERVICE, user)?.get_password()
+}
+
+fn set_keyring_password(user: &str, password: &str) -> keyring::Result<()> {
+    Entry::new(KEYRING_SERVICE, user)?.set_password(password)
+}
+
+/// Reads an API key from the credential store, migrating a key found in the file first.
+/// Falls back to the file if the credential store is not usable.
+fn get_secret(key: JsonKey, user: &str) -> String {
+    let name = key.field();
+    let file_key = read_file_data(key);
+
+    let entry = match Entry::new(KEYRING_SERVICE, user) {
+        Ok(entry) => entry,
+        Err(err) => {
+            warn!("Unable to use the OS keyring, reading {name} from {FILE_NAME}: {err}");
+            return file_key;
+        }
     };
 
-    let contend = match key {
-        JsonKey::Name => {
-            format!(
-                "{{\n\
-                \t\"{NAME_FIELD}\": \"{val}\",\n\
-                \t\"{KEY_FIELD}\": \"{other_val}\"\n\
-                }}"
-            )
+    // A key in the file is either from an old version or was entered by hand, so it wins
+    if !file_key.is_empty() {
+        match entry.set_password(&file_key) {
+            Ok(()) => {
+                info!("Moved {name} from {FILE_NAME} to the OS keyring");
+                write_file_data(key, "");
+            }
+            Err(err) => warn!("Unable to move {name} to the OS keyring: {err}"),
+        }
+        return file_key;
+    }
+
+    match entry.get_password() {
+        Ok(key) => key,
+        Err(keyring::Error::NoEntry) => String::new(),
+        Err(err) => {
+            warn!("Unable to read {name} from the OS keyring: {err}");
+            String::new()
         }
-        JsonKey::Key => {
-            format!(
-                "{{\n\
-                \t\"{NAME_FIELD}\": \"{other_val}\",\n\
-                \t\"{KEY_FIELD}\": \"{val}\"\n\
-                }}"
-            )
+    }
+}
+
+/// Retrieves JSON data from a given key in a file. If the file does not exist or is not a valid JSON format,
+/// it creates a new JSON file with default values but valid keys.
+///
+/// # Arguments
+///
+/// * `key` - An enum value representing the key to retrieve from the JSON data.
+///
+/// # Errors
+///
+/// This function returns the default value of the key under the following conditions:
+/// * If the file does not exist, it is created anew with default values but valid keys.
+/// * If the file exists but cannot be read or written.
+/// * If the file is not a valid JSON file, it is overwritten with a valid JSON structure containing default values.
+/// * If the file lacks the key, e.g. because it was written by an older version.
+fn read_file_data(key: JsonKey) -> String {
+    read_file()
+        .get(key.field())
+        .and_then(Value::as_str)
+        .unwrap_or(key.default_value())
+        .to_string()
+}
+
+/// Sets the JSON data in the file based on a given key and value, keeping all other fields.
+///
+/// # Arguments
+///
+/// * `key` - The key indicating which field to update.
+/// * `val` - The new value to set for the specified field.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to create or write to the file `FILE_NAME` in the config directory.
+fn write_file_data(key: JsonKey, val: &str) {
+    let mut json = read_file();
+    if !json.is_object() {
+        json = default_json();
+    }
+    json[key.field()] = Value::from(val);
+
+    write_file(&json);
+}
+
+/// Reads the whole user data file, creating it with the defaults if it is missing.
+/// An invalid file is left as it is and the defaults are returned.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to read the file or to create a missing one.
+fn read_file() -> Value {
+    let contend = match fs::read_to_string(file_path()) {
+        Ok(contend) => contend,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            info!("No {FILE_NAME} yet, creating it");
+            let default_json = default_json();
+            write_file(&default_json);
+            return default_json;
         }
+        Err(err) => panic!("Error opening {FILE_NAME}\nError: {err}"),
     };
 
-    let mut file = File::create(&FILE_PATH).expect("Unable to create file");
-    write!(file, "{contend}").expect("Unable to write to file");
-}
\ No newline at end of file
+    serde_json::from_str(&contend).unwrap_or_else(|err| {
+        // The user may still repair it, it is only replaced once a setting is saved
+        warn!("Error reading from file {FILE_NAME}, using the defaults: {err}");
+        default_json()
+    })
+}
+
+/// Overwrites the user data file.
+///
+/// The data is written to a temporary file first and moved over the old one,
+/// so reading the file never sees it half written.
+///
+/// # Panics
+///
+/// This function will panic if it is unable to write or replace the file.
+fn write_file(json: &Value) {
+    let contend = serde_json::to_string_pretty(json).expect("User data should be serializable");
+    let path = file_path();
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, contend).expect("Unable to write to file");
+    fs::rename(&temporary, &path).expect("Unable to replace file");
+}
+
+/// A user data object with the default value for every key.
+fn default_json() -> Value {
+    let fields = JsonKey::ALL
+        .iter()
+        .map(|key| (key.field().to_string(), Value::from(key.default_value())))
+        .collect();
+    Value::Object(fields)
+}
+
+/// Moves the user data file aside, so the defaults are used from now on.
+/// API keys in the credential store are kept, the SimBrief username and profiles only in the backup.
+///
+/// # Returns
+///
+/// The path of the backup, e.g. `userdata.json.bak`.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be renamed.
+pub fn reset_user_data() -> io::Result<PathBuf> {
+    let path = file_path();
+    let backup = path.with_file_name(format!("{FILE_NAME}.bak"));
+    fs::rename(&path, &backup)?;
+    info!("Moved {FILE_NAME} to {}", backup.display());
+    Ok(backup)
+}
+
+/// Returns the directory the user data file is stored in.
+///
+/// # Errors
+///
+/// Returns an error if the directory doesn't exist and can't be created.
+pub fn get_file_dir() -> io::Result<PathBuf> {
+    let dir = PathBuf::from(get_config_dir());
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Returns the path of the user data file, creating the config directory if needed.
+fn file_path() -> PathBuf {
+    get_file_dir()
+        .expect("Unable to create config directory")
+        .join(FILE_NAME)
+}
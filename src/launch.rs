@@ -0,0 +1,106 @@
+use reqwest::Url;
+
+use crate::logic::normalize_icao;
+
+/// The URI scheme of deep links, e.g. `gfd://brief?dep=EDDF&arr=LOWW`.
+pub const URI_SCHEME: &str = "gfd";
+
+pub const USAGE: &str = "\
+Usage: get_flight_data [OPTIONS] [gfd://brief?dep=ICAO&arr=ICAO&profile=NAME]
+
+Options:
+  --profile <NAME>        Switch to the stored credential profile NAME
+  --dep <ICAO>            Use ICAO as departure instead of the SimBrief flight plan
+  --arr <ICAO>            Use ICAO as arrival instead of the SimBrief flight plan
+  --register-uri-scheme   Open gfd:// links with this executable and exit
+  --help                  Print this help and exit";
+
+/// How the app was asked to start, from command line flags or a deep link.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct LaunchOptions {
+    pub profile: Option<String>,
+    pub departure: Option<String>,
+    pub arrival: Option<String>,
+    pub register_uri_scheme: bool,
+    pub help: bool,
+}
+
+impl LaunchOptions {
+    /// Returns both airports if the flight was given at launch,
+    /// in which case the SimBrief flight plan is not needed.
+    pub fn airports(&self) -> Option<(String, String)> {
+        Some((self.departure.clone()?, self.arrival.clone()?))
+    }
+}
+
+/// Parses the command line arguments, without the program name.
+///
+/// A `gfd://brief?...` deep link can be given instead of or in addition to the flags,
+/// this is how the OS starts the app when such a link is clicked.
+///
+/// # Errors
+///
+/// Returns a message for the user if an argument is unknown, a value is missing
+/// or an ICAO code is invalid.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::launch::parse_args;
+///
+/// let args = ["gfd://brief?dep=eddf&arr=LOWW&profile=My%20VA"].map(String::from);
+/// let options = parse_args(args).unwrap();
+/// assert_eq!(options.departure.as_deref(), Some("EDDF"));
+/// assert_eq!(options.arrival.as_deref(), Some("LOWW"));
+/// assert_eq!(options.profile.as_deref(), Some("My VA"));
+///
+/// let args = ["--dep", "EDDF", "--arr", "LOWW"].map(String::from);
+/// assert!(parse_args(args).unwrap().airports().is_some());
+/// ```
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<LaunchOptions, String> {
+    let mut options = LaunchOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--profile" => options.profile = Some(next_value(&mut args, &arg)?),
+            "--dep" => options.departure = Some(parse_icao(&next_value(&mut args, &arg)?)?),
+            "--arr" => options.arrival = Some(parse_icao(&next_value(&mut args, &arg)?)?),
+            "--register-uri-scheme" => options.register_uri_scheme = true,
+            "--help" | "-h" => options.help = true,
+            _ if arg.starts_with(&format!("{URI_SCHEME}:")) => parse_deep_link(&arg, &mut options)?,
+            _ => return Err(format!("Unknown argument \"{arg}\"")),
+        }
+    }
+
+    Ok(options)
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next()
+        .filter(|value| !value.starts_with("--"))
+        .ok_or_else(|| format!("Missing value for {flag}"))
+}
+
+fn parse_icao(value: &str) -> Result<String, String> {
+    normalize_icao(value).ok_or_else(|| format!("\"{value}\" is not a valid ICAO code"))
+}
+
+/// Applies the query of a `gfd://brief?...` link to the options.
+fn parse_deep_link(link: &str, options: &mut LaunchOptions) -> Result<(), String> {
+    let url = Url::parse(link).map_err(|err| format!("Invalid link \"{link}\": {err}"))?;
+    if url.host_str() != Some("brief") {
+        return Err(format!("Unsupported link \"{link}\", expected {URI_SCHEME}://brief?..."));
+    }
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "dep" => options.departure = Some(parse_icao(&value)?),
+            "arr" => options.arrival = Some(parse_icao(&value)?),
+            "profile" => options.profile = Some(value.into_owned()),
+            _ => return Err(format!("Unknown link parameter \"{key}\"")),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! Fetches the data needed to brief a flight in the simulator:
+//! the latest SimBrief flight plan, METARs from AVWX,
+//! and ATIS and online ATC from VATSIM.
+//!
+//! The egui front-end lives in the binary, this library has no GUI dependencies
+//! and can be used with `default-features = false`.
+//! To embed the briefing in another tool, start with [`engine::BriefingEngine`].
+
+pub mod airport;
+pub mod avwx;
+pub mod briefing;
+pub mod cache;
+pub mod crash;
+pub mod dir_manager;
+pub mod downloads;
+pub mod engine;
+pub mod error;
+pub mod hazards;
+pub mod history;
+pub mod i18n;
+pub mod icing;
+pub mod ivao;
+pub mod json_operations;
+pub mod launch;
+pub mod logging;
+pub mod logic;
+pub mod pilotedge;
+pub mod platform;
+pub mod presence;
+pub mod quota;
+pub mod report;
+pub mod retry;
+pub mod runways;
+pub mod safe_mode;
+pub mod schedule;
+pub mod session;
+pub mod sim;
+pub mod simconnect;
+pub mod simbrief;
+pub mod statistics;
+pub mod sun;
+pub mod taf;
+pub mod timer;
+pub mod translation;
+pub mod units;
+pub mod update;
+pub mod vatsim;
+pub mod winds;
+pub mod worker;
+pub mod xplane;
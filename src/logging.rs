@@ -0,0 +1,288 @@
+use std::collections::VecDeque;
+use std::fmt::{self as std_fmt, Display, Formatter};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+
+use crate::dir_manager::get_log_dir;
+use crate::logic::LOGFILE_NAME;
+
+/// Rotated log files are named like `gfd.2024-06-21_093000.log`, after the time they were rotated.
+pub const LOG_FILE_PREFIX: &str = "gfd";
+pub const LOG_FILE_SUFFIX: &str = "log";
+/// Environment variable with the console log levels, e.g. `debug` or `info,get_flight_data::vatsim=debug`.
+pub const LOG_ENV: &str = "GFD_LOG";
+/// Lines kept in memory for the log viewer of the app, the oldest are dropped first.
+pub const BUFFER_LINES: usize = 1000;
+
+static BUFFER: Mutex<VecDeque<LogLine>> = Mutex::new(VecDeque::new());
+
+/// A log event as kept in memory.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LogLine {
+    pub time: DateTime<Utc>,
+    pub level: Level,
+    /// The module the event was logged in, e.g. `get_flight_data::vatsim`
+    pub target: String,
+    pub message: String,
+}
+
+impl Display for LogLine {
+    /// Formats the line like in the log file, but with the time only.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use get_flight_data::logging::LogLine;
+    /// use tracing::Level;
+    ///
+    /// let line = LogLine {
+    ///     time: Utc.with_ymd_and_hms(2024, 6, 21, 9, 30, 0).unwrap(),
+    ///     level: Level::WARN,
+    ///     target: "get_flight_data::vatsim".to_string(),
+    ///     message: "No ATIS for EDDF".to_string(),
+    /// };
+    /// assert_eq!(line.to_string(), "09:30:00Z  WARN get_flight_data::vatsim: No ATIS for EDDF");
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std_fmt::Result {
+        write!(f, "{}Z {:>5} {}: {}", self.time.format("%H:%M:%S"), self.level, self.target, self.message)
+    }
+}
+
+/// When the log file is rotated and how many old ones are kept.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LogConfig {
+    /// Size in bytes after which the log is rotated, it is also rotated at midnight UTC
+    pub max_size: u64,
+    /// Rotated files kept next to the current one, older ones are deleted
+    pub kept_files: usize,
+}
+
+impl LogConfig {
+    pub const DEFAULT: LogConfig = LogConfig { max_size: 10 * 1024 * 1024, kept_files: 7 };
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig::DEFAULT
+    }
+}
+
+/// Sends the log events of the app to the console and to `gfd.log` in the log directory.
+///
+/// The file gets everything down to debug from this crate and warnings from its dependencies,
+/// the console only info and above unless set otherwise in [`LOG_ENV`].
+///
+/// # Arguments
+///
+/// * `config` - When to rotate the log file and how many old ones to keep.
+///
+/// # Returns
+///
+/// A guard that writes the remaining events to the file when dropped, keep it until the app exits.
+///
+/// # Errors
+///
+/// Returns an error if the log file can't be opened or a subscriber is already installed.
+pub fn init(config: LogConfig) -> io::Result<WorkerGuard> {
+    let file = RotatingFile::open(PathBuf::from(get_log_dir()), config)?;
+    let (writer, guard) = tracing_appender::non_blocking(file);
+
+    let console_filter = std::env::var(LOG_ENV)
+        .ok()
+        .and_then(|levels| levels.parse::<Targets>().ok())
+        .unwrap_or_else(|| Targets::new().with_default(LevelFilter::INFO));
+    let file_filter = Targets::new()
+        .with_target(env!("CARGO_CRATE_NAME"), LevelFilter::DEBUG)
+        .with_default(LevelFilter::WARN);
+
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_filter(console_filter))
+        .with(fmt::layer().with_writer(writer).with_ansi(false).with_filter(file_filter.clone()))
+        .with(BufferLayer.with_filter(file_filter))
+        .try_init()
+        .map_err(io::Error::other)?;
+    Ok(guard)
+}
+
+/// Returns the lines logged since the start, the last [`BUFFER_LINES`] at most.
+///
+/// # Arguments
+///
+/// * `level` - The least severe level to include, e.g. `Level::WARN` for warnings and errors.
+pub fn recent_lines(level: Level) -> Vec<LogLine> {
+    let Ok(buffer) = BUFFER.lock() else {
+        return Vec::new();
+    };
+    // More verbose levels compare greater
+    buffer.iter().filter(|line| line.level <= level).cloned().collect()
+}
+
+/// Returns the last lines logged at any level, for crash reports.
+///
+/// Unlike [`recent_lines`] this never waits for the buffer, so it can't deadlock
+/// when called while panicking in the middle of logging. Nothing is returned then.
+pub fn last_lines(count: usize) -> Vec<LogLine> {
+    let Ok(buffer) = BUFFER.try_lock() else {
+        return Vec::new();
+    };
+    buffer.iter().skip(buffer.len().saturating_sub(count)).cloned().collect()
+}
+
+/// Keeps the events in [`BUFFER`] for the log viewer.
+struct BufferLayer;
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor(String::new());
+        event.record(&mut visitor);
+        let line = LogLine {
+            time: Utc::now(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: visitor.0,
+        };
+
+        let Ok(mut buffer) = BUFFER.lock() else {
+            return;
+        };
+        if buffer.len() >= BUFFER_LINES {
+            buffer.pop_front();
+        }
+        buffer.push_back(line);
+    }
+}
+
+/// Collects the message of an event, followed by any other fields like the file layer shows them.
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std_fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{value:?}"));
+        } else {
+            self.0.push_str(&format!(" {}={value:?}", field.name()));
+        }
+    }
+}
+
+/// Lists the rotated log files in the log directory, oldest first, without the current `gfd.log`.
+pub fn log_files() -> Vec<PathBuf> {
+    rotated_files(Path::new(&get_log_dir()))
+}
+
+/// Checks whether a file name is one of the rotated log files.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::logging::is_log_file;
+///
+/// assert!(is_log_file("gfd.2024-06-21_093000.log"));
+/// assert!(!is_log_file("gfd.log"));
+/// assert!(!is_log_file("gfd_err.log"));
+/// assert!(!is_log_file("gfd_report_20240621_120000.zip"));
+/// ```
+pub fn is_log_file(name: &str) -> bool {
+    name != LOGFILE_NAME
+        && name.starts_with(&format!("{LOG_FILE_PREFIX}."))
+        && name.ends_with(&format!(".{LOG_FILE_SUFFIX}"))
+}
+
+fn rotated_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(is_log_file))
+        .collect();
+    // The times in the names sort chronologically
+    files.sort();
+    files
+}
+
+/// `gfd.log`, moved aside when it gets too big or a new day starts.
+struct RotatingFile {
+    dir: PathBuf,
+    config: LogConfig,
+    file: File,
+    // Size of the current file and the UTC day it was started on
+    size: u64,
+    day: NaiveDate,
+}
+
+impl RotatingFile {
+    fn open(dir: PathBuf, config: LogConfig) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(LOGFILE_NAME);
+        let (size, day) = match fs::metadata(&path) {
+            Ok(metadata) => {
+                let modified: DateTime<Utc> = metadata.modified()?.into();
+                (metadata.len(), modified.date_naive())
+            }
+            Err(_) => (0, Utc::now().date_naive()),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        let mut rotating = RotatingFile { dir, config, file, size, day };
+        // The log of the last start may be from another day or already too big
+        if rotating.due(0) {
+            rotating.rotate()?;
+        }
+        Ok(rotating)
+    }
+
+    fn due(&self, incoming: usize) -> bool {
+        let too_big = self.size > 0 && self.size + incoming as u64 > self.config.max_size;
+        too_big || self.day != Utc::now().date_naive()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let name = format!("{LOG_FILE_PREFIX}.{}.{LOG_FILE_SUFFIX}", Utc::now().format("%Y-%m-%d_%H%M%S"));
+        let path = self.dir.join(LOGFILE_NAME);
+        fs::rename(&path, self.dir.join(name))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.size = 0;
+        self.day = Utc::now().date_naive();
+
+        let files = rotated_files(&self.dir);
+        let surplus = files.len().saturating_sub(self.config.kept_files);
+        for old in &files[..surplus] {
+            // Not logged, the subscriber is writing right now
+            let _ = fs::remove_file(old);
+        }
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.due(buf.len()) {
+            // Losing the rotation is better than losing the line
+            if let Err(err) = self.rotate() {
+                eprintln!("Failed to rotate {LOGFILE_NAME}: {err}");
+            }
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
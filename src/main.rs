@@ -2,35 +2,140 @@
 // hide console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::{fs, panic, process, thread};
-use std::sync::{Arc, mpsc, Mutex};
-use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod tray;
+
+use std::{panic, process};
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, mpsc, Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, TimeDelta, Timelike, Utc};
 use eframe::egui;
-use logger_utc as logger;
+use tokio::runtime::Runtime;
+use tracing::{debug, error, info, warn, Level};
+
+use get_flight_data::{
+    airport, briefing, cache, crash, downloads, i18n, icing, ivao, json_operations, launch, logging, logic,
+    pilotedge, platform, presence, quota, report, retry, runways, safe_mode, sim, statistics, sun, taf, units,
+    update, vatsim, winds,
+};
+use get_flight_data::airport::AirportInfo;
+use get_flight_data::briefing::BriefingFormat;
+use get_flight_data::dir_manager::{default_download_dir, get_log_dir, prepare_dirs};
+use get_flight_data::downloads::Download;
+use get_flight_data::error::GfdError;
+use get_flight_data::history::{MetarHistory, Quantity};
+use get_flight_data::i18n::{tr, tr_args, Language};
+use get_flight_data::icing::IcingRisk;
+use get_flight_data::json_operations::JsonKey;
+use get_flight_data::logging::LogConfig;
+use get_flight_data::logic::{
+    attribution, normalize_icao, AirportData, ApiAuth, ApiKey, Endpoints, FlightPlan, HttpClient, LoadItem,
+    LoadState, Network, OfpFile, Position, ProfilePoint, Providers, ProxySettings, Source, ERROR_FILE_NAME,
+    LOGFILE_NAME,
+};
+use get_flight_data::presence::Activity;
+use get_flight_data::retry::RetryPolicy;
+use get_flight_data::runways::Wind;
+use get_flight_data::schedule::{DailyPeriod, RefreshPhase, RefreshPlan};
+use get_flight_data::session::UiState;
+use get_flight_data::sim::{AircraftState, FlightPhase, SimEvent, Simulator};
+use get_flight_data::statistics::Statistics;
+use get_flight_data::sun::Daylight;
+use get_flight_data::taf::Likelihood;
+use get_flight_data::timer::{format_duration, Event, FlightTimer};
+use get_flight_data::units::Conversion;
+use get_flight_data::update::Release;
+use get_flight_data::vatsim::{Coverage, StationChange};
+use get_flight_data::winds::WindAloft;
+use get_flight_data::worker::{Command, Worker, WorkerEvent};
 
-use json_operations::JsonKey;
-use crate::dir_manager::get_log_dir;
-use crate::logic::{log, LOGFILE_NAME};
+const AVWX_KEY_URL: &str = "https://account.avwx.rest/getting-started";
+// A start that runs this long without crashing is no longer counted as failed
+const STARTUP_GRACE: Duration = Duration::from_secs(30);
+const MEGABYTE: u64 = 1024 * 1024;
+// Range of the UI scale setting, e.g. for a 4K monitor across the room
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 3.0;
+// A density altitude this far above the field noticeably lengthens the takeoff roll
+const HIGH_DENSITY_ALTITUDE_FT: f64 = 1000.0;
+// Inner size of the window in the compact overlay mode
+const COMPACT_SIZE: [f32; 2] = [420.0, 110.0];
+const DEFAULT_SIZE: [f32; 2] = [750.0, 725.0];
 
-mod logic;
-mod json_operations;
-mod dir_manager;
+/// The lifecycle of the app, each request runs in exactly one state.
+/// Starting a request cancels the running one of the same kind in the worker,
+/// and only the result of the request the state waits for is applied.
+enum AppState {
+    /// Waiting for the next automatic refresh
+    Idle,
+    /// Loading the SimBrief flight plan, the weather is fetched afterwards
+    FetchingFp {
+        /// The id of the request in the worker
+        id: u64,
+    },
+    /// Loading METAR, ATIS and ATC of both airports
+    FetchingWx {
+        id: u64,
+        /// Print the briefing once the data arrived, set for a new flight plan
+        print: bool,
+    },
+    /// The last request failed, the message is shown until the next one starts
+    Error(String),
+    /// Automatic refreshes are suppressed by the user
+    Paused,
+}
 
-const ERROR_FILE_NAME: &'static str = "gfd_err.log";
+/// The airport whose weather is shown below the tab bar.
+#[derive(Clone, PartialEq, Eq)]
+enum AirportTab {
+    Departure,
+    Arrival,
+    /// The alternate or an airport added by the user, by ICAO code
+    Other(String),
+}
 
 struct DataCarrier {
+    // Async runtime all network tasks are spawned on
+    runtime: Runtime,
+    // Runs the refreshes and flight plan reloads, started with the window
+    worker: Option<Worker>,
+    // What the app is doing right now
+    state: AppState,
+    // The parts of the running request and whether each arrived, shown while loading
+    loading: Vec<(LoadItem, LoadState)>,
     // Time since last request
     last_update: Instant,
-    // Shared data
-    data: Arc<Mutex<Option<(String, String)>>>,
-    // Flag for current loading status
-    loading: Arc<AtomicBool>,
+    // Last received data, when it arrived, and whether the last refresh failed to reach the APIs
+    data: Option<(AirportData, AirportData)>,
+    data_time: Option<DateTime<Utc>>,
+    offline: bool,
+    // Last received SimBrief flight plan, `None` if the airports were given at launch
+    flight_plan: Option<FlightPlan>,
+    // Forecast winds at cruise level of the flight plan, and the receiver while they are loaded
+    winds: Result<Vec<WindAloft>, String>,
+    winds_update: Option<mpsc::Receiver<Result<Vec<WindAloft>, GfdError>>>,
+    // Station data per airport, fetched once, and the receiver while it is loaded
+    airport_info: HashMap<String, Result<AirportInfo, String>>,
+    airport_info_update: Option<mpsc::Receiver<(String, Result<AirportInfo, GfdError>)>>,
+    // Receiver of a running OFP file download, and the result of the last one
+    download_update: Option<mpsc::Receiver<Result<Download, GfdError>>>,
+    download_status: Result<String, String>,
+    // Block and flight time of the current flight, recorded by hand
+    flight_timer: FlightTimer,
     // Credentials to store on button press
     username: Arc<Mutex<String>>,
     api_key: Arc<Mutex<String>>,
+    // Receiver of a running credential test, and the results of the last one per service
+    credential_test_update: Option<mpsc::Receiver<Vec<CredentialResult>>>,
+    credential_test: Vec<CredentialResult>,
+    // Internal error shown above everything until dismissed, the app keeps running
+    error_banner: Option<String>,
     // Timer to check if we saved credential in last 5 sec
     save_credential_time: Instant,
     // The time of last request
@@ -39,317 +144,3692 @@ struct DataCarrier {
     // Departure and arrival
     departure: String,
     arrival: String,
-    // Flag if we are loading a flight plan through button click
-    loading_flight_plan: bool,
-    flight_plan_update: Option<mpsc::Receiver<(String, String)>>,
-    // Flag if user changed SimBrief username
-    username_changed: bool,
-    // Flag to check if user wants to pause calls
-    stop_updating: bool,
-    // Flag if the user is manually updating and thus overriding the checkbox for exactly one time
-    manual_update: bool,
+    // Airports typed in by the user instead of taking them from SimBrief, and why they were rejected
+    departure_entry: String,
+    arrival_entry: String,
+    airport_entry_error: Option<String>,
+    // Airports offered for quick selection, starred ones and the recently briefed ones
+    favorite_airports: Vec<String>,
+    recent_airports: Vec<String>,
+    // Airports monitored besides departure and arrival, added by the user,
+    // their data by ICAO code or why it couldn't be fetched, and the id of the running request in the worker
+    extra_airports: Vec<String>,
+    extra_data: HashMap<String, Result<AirportData, String>>,
+    extra_request: Option<u64>,
+    // The airport shown, the ICAO code typed in to monitor another one and why it was rejected
+    airport_tab: AirportTab,
+    extra_entry: String,
+    extra_entry_error: Option<String>,
+    // Whether the arrival was shown first, to switch to it once when the flight phase changes
+    arrival_first: bool,
+    // Stations that logged on or off between the last two refreshes
+    atc_changes: Vec<StationChange>,
+    // Last seen ATIS information letter per airport
+    atis_letters: HashMap<String, char>,
+    // Airports whose ATIS letter changed in the last refresh, with the previous letter
+    new_atis: HashMap<String, char>,
+    // Receiver for a running check whether the arrival ATIS came online
+    atis_watch_update: Option<mpsc::Receiver<(String, Result<bool, GfdError>)>>,
+    // Time of the last ATIS check
+    last_atis_watch: Instant,
+    // Message shown when a watched ATIS came online, until dismissed
+    atis_notice: Option<String>,
+    // The user's choice to suppress automatic updates, `state` is `Paused` while nothing else happens
+    paused: bool,
+    // Daily period like `23:00-07:00` in local time to pause automatic updates, empty for none,
+    // whether they are paused because of it, and whether the user resumed them early
+    quiet_hours: String,
+    quiet_paused: bool,
+    quiet_override: bool,
+    // Footer for exports, edited in the settings
+    disclaimer: String,
+    // Whether to show desktop notifications for important events
+    notifications: bool,
+    // Whether the flight is shown as Discord Rich Presence, the Discord application it is published as,
+    // and the thread publishing it while enabled
+    discord_presence: bool,
+    discord_app_id: String,
+    presence: Option<mpsc::Sender<Option<Activity>>>,
+    // The simulator the aircraft is read from, `None` to not read it, the events it sends,
+    // the last state of the aircraft and why there is none
+    simulator: Option<Simulator>,
+    sim_update: Option<mpsc::Receiver<SimEvent>>,
+    aircraft: Option<AircraftState>,
+    sim_status: String,
+    // The arrival whose weather was refreshed for the approach, so it happens once per flight
+    approach_refreshed: Option<String>,
+    // The airport nearest to the aircraft being looked up
+    nearest_update: Option<mpsc::Receiver<Result<String, GfdError>>>,
+    // Whether to look for a newer release on startup, the running check and the release found
+    check_for_updates: bool,
+    update_check: Option<mpsc::Receiver<Option<Release>>>,
+    new_release: Option<Release>,
+    // DeepL API key for translating non-English ATIS, empty to not translate
+    translation_key: String,
+    // The headers API keys are sent in, per provider
+    api_auths: Vec<(Source, ApiAuth)>,
+    // The online network ATIS, ATC and traffic are shown for
+    network: Network,
+    // Folder OFP files are saved to, empty for the downloads folder,
+    // and the simulator folder flight plan files are copied to, empty to not copy
+    download_dir: String,
+    sim_flight_plan_dir: String,
+    // Folder briefings are exported to, empty for the download folder, and their format
+    briefing_dir: String,
+    briefing_format: BriefingFormat,
+    // Where the last briefing was exported to, or why it failed
+    export_status: Result<String, String>,
+    // Crosswind in knots above which a runway is shown in red
+    crosswind_limit: f64,
+    // Minutes after which the age of a METAR is shown in yellow and in red
+    metar_age_warning: i64,
+    metar_age_alert: i64,
+    // Zoom factor of the whole window as last saved, also changed with Ctrl + and Ctrl -
+    ui_scale: f32,
+    // Seconds a response is reused for the same request
+    cache_seconds: u64,
+    // Tries per request on timeouts and server errors
+    retry_attempts: u32,
+    // When the log file is rotated, applied on the next start
+    log_config: LogConfig,
+    // How often the weather is refreshed in each part of the flight
+    refresh_plan: RefreshPlan,
+    // Least severe level shown in the Logs section
+    log_level: Level,
+    // API endpoints, empty for the default, and why they can't be used
+    endpoints: Endpoints,
+    endpoints_error: Option<String>,
+    // Proxy all requests go through, empty for the environment's, and why it can't be used
+    proxy: ProxySettings,
+    proxy_error: Option<String>,
+    // Values of the unit converters in the Tools section, in both units
+    conversions: Vec<(Conversion, f64, f64)>,
+    // Runway heading and wind entered in the wind calculator of the Tools section
+    calculator_heading: f64,
+    calculator_wind: Wind,
+    // Whether to print the briefing whenever a new flight plan is loaded
+    auto_print: bool,
+    // The last METARs per airport, saved to disk if `keep_history` is set
+    metar_history: MetarHistory,
+    keep_history: bool,
+    // Stored credential profiles and the name of the active one, empty if none
+    profiles: Vec<String>,
+    active_profile: String,
+    // Name entered for saving the current credentials as a profile
+    new_profile_name: String,
+    // Description in the "Report a problem" dialog, `None` while it is closed
+    problem_report: Option<String>,
+    problem_report_error: Option<String>,
+    // Whether the last run crashed and its report wasn't offered yet
+    crash_report_pending: bool,
+    // Notice shown while in safe mode after repeated failed starts, `None` when started normally
+    safe_mode: Option<String>,
+    // When the app started, the start counts as successful after `STARTUP_GRACE`
+    started: Instant,
+    startup_confirmed: bool,
+    // Expanded sections, scroll position and window size, saved on exit,
+    // and the scroll position to restore on the first frame
+    session: UiState,
+    restore_scroll: Option<f32>,
+    // Icon in the system tray, `None` if it couldn't be created
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    tray: Option<tray::Tray>,
+}
+
+/// The name of a service and whether it accepted the entered credentials, with a message either way.
+type CredentialResult = (&'static str, Result<String, String>);
+
+/// Reads the refresh intervals, the default for missing or invalid ones.
+fn refresh_plan() -> RefreshPlan {
+    let interval = |key, default: Duration| match json_operations::get_json_data(key).parse::<u64>() {
+        Ok(minutes) => Duration::from_secs(minutes.max(1) * 60),
+        Err(_) => default,
+    };
+    let defaults = RefreshPlan::DEFAULT;
+    RefreshPlan {
+        ground: interval(JsonKey::RefreshGroundMinutes, defaults.ground),
+        cruise: interval(JsonKey::RefreshCruiseMinutes, defaults.cruise),
+        arrival: interval(JsonKey::RefreshArrivalMinutes, defaults.arrival),
+        arrival_window: json_operations::get_json_data(JsonKey::ArrivalWindowMinutes)
+            .parse()
+            .map_or(defaults.arrival_window, TimeDelta::minutes),
+        arrival_range_nm: json_operations::get_json_data(JsonKey::ArrivalRangeNm)
+            .parse()
+            .unwrap_or(defaults.arrival_range_nm),
+    }
+}
+
+/// Reads the log rotation settings, the default for missing or invalid ones.
+fn log_config() -> LogConfig {
+    let megabytes = json_operations::get_json_data(JsonKey::LogMaxMegabytes).parse().ok();
+    LogConfig {
+        max_size: megabytes.map_or(LogConfig::DEFAULT.max_size, |megabytes: u64| megabytes.max(1) * MEGABYTE),
+        kept_files: json_operations::get_json_data(JsonKey::LogFilesKept)
+            .parse()
+            .unwrap_or(LogConfig::DEFAULT.kept_files),
+    }
 }
 
 fn main() {
 
     // Set panic behavior
-    // Note: Expect does nothing in this block, so we can use unwrap
+    // Note: A failure in here can't be reported anywhere, so errors are ignored
     panic::set_hook(Box::new(|panic_payload| {
         fn err_log(to_log: &str) {
-            let log_dir = get_log_dir();
-            let file_name = &format!("{log_dir}{ERROR_FILE_NAME}");
-            logger::log_to_file(to_log, file_name).unwrap();
+            let file_name = format!("{}{ERROR_FILE_NAME}", get_log_dir());
+            if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(file_name) {
+                let _ = writeln!(file, "[{}] - {to_log}", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+            }
         }
 
-        if let Some(err_msg) = panic_payload.payload().downcast_ref::<&str>() {
-            err_log(&format!("A panic occurred: {err_msg}"));
-        } else if let Some(err_msg) = panic_payload.payload().downcast_ref::<String>() {
-            err_log(&format!("A panic occurred: {err_msg}"));
-        } else {
-            err_log("A panic occurred");
-        }
+        let payload = panic_payload.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown");
+        let location = panic_payload.location().map(|location| location.to_string());
+        // Captured regardless of RUST_BACKTRACE, users never set it
+        let backtrace = Backtrace::force_capture().to_string();
+        let log_lines = logging::last_lines(crash::REPORT_LOG_LINES);
 
-        if let Some(err_location) = panic_payload.location() {
-            err_log(&format!("Panic happened at {err_location}"));
-        } else {
-            err_log("The panic location cannot be determined");
-        }
+        err_log(&crash::crash_report(message, location.as_deref(), &backtrace, &log_lines));
+        // Offer the report on the next start
+        let _ = crash::mark_pending();
     }));
     
-    // Ensure log dir exists
-    fs::create_dir_all(get_log_dir()).unwrap();
+    // Ensure config and log dir exist and move files of older versions there
+    if let Err(err) = prepare_dirs() {
+        eprintln!("Failed to create the config and log directories: {err}");
+        process::exit(1);
+    }
+
+    // Flushes the log file when dropped at the end of main
+    let _log_guard = logging::init(log_config())
+        .map_err(|err| eprintln!("Failed to set up logging: {err}"))
+        .ok();
+
+    i18n::set_language(Language::from_code(&json_operations::get_json_data(JsonKey::Language)));
+
+    // Flags or a gfd:// link the app was started with
+    let (launch_options, launch_error) = match launch::parse_args(std::env::args().skip(1)) {
+        Ok(options) => (options, None),
+        Err(err) => {
+            warn!("Ignoring launch arguments: {err}");
+            eprintln!("{err}\n\n{}", launch::USAGE);
+            (launch::LaunchOptions::default(), Some(tr_args("launch-arguments-ignored", &[("error", &err)])))
+        }
+    };
 
-    // Remove old log file
-    {
-        let log_file = LOGFILE_NAME;
-        let lop_dir = get_log_dir();
-        let old_file_name = format!("{lop_dir}{log_file}");
-        let _ = fs::remove_file(old_file_name);
+    if launch_options.help {
+        println!("{}", launch::USAGE);
+        return;
     }
-    
+
+    if launch_options.register_uri_scheme {
+        match platform::register_uri_scheme() {
+            Ok(()) => println!("{}:// links now open get_flight_data", launch::URI_SCHEME),
+            Err(err) => {
+                warn!("Failed to register {}://: {err}", launch::URI_SCHEME);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Integrations and stored settings are skipped after repeated failed starts, one of them may be the cause
+    let failed_starts = safe_mode::record_startup();
+    let safe = failed_starts >= safe_mode::CRASH_LIMIT;
+    if safe {
+        warn!("Starting in safe mode after {failed_starts} failed starts");
+    }
+
+    if let Some(profile) = launch_options.profile.as_ref().filter(|_| !safe) {
+        if !json_operations::switch_profile(profile) {
+            warn!("Unknown profile \"{profile}\" requested at launch");
+        }
+    }
+
+    let runtime = Runtime::new().unwrap_or_else(|err| {
+        error!("Failed to start async runtime: {err}");
+        process::exit(1);
+    });
+
     // Initially call Simbrief to get the flight plan
 
-    let contend = DataCarrier {
-        // Initially data will be loaded because we simulate click of reload fp button
+    let mut contend = DataCarrier {
+        runtime,
+        worker: None,
+        // The flight plan is loaded as soon as the window exists
+        state: AppState::Idle,
+        loading: Vec::new(),
         last_update: Instant::now(),
-        data: Arc::new(Mutex::new(None)),
-        loading: Arc::new(AtomicBool::new(false)),
+        data: None,
+        data_time: None,
+        offline: false,
+        flight_plan: None,
         username: Arc::new(Mutex::new(String::new())),
         api_key: Arc::new(Mutex::new(String::new())),
+        credential_test_update: None,
+        credential_test: Vec::new(),
+        error_banner: None,
         save_credential_time: Instant::now() - Duration::from_secs(6), // Subtract 6 seconds
         // so later check >= 5 is false at the beginning
         local_time: Local::now(),
         utc_time: Utc::now(),
         departure: String::new(),
         arrival: String::new(),
-        loading_flight_plan: false,
-        flight_plan_update: None,
-        username_changed: true,
-        stop_updating: false,
-        manual_update: false,
+        departure_entry: String::new(),
+        arrival_entry: String::new(),
+        airport_entry_error: None,
+        favorite_airports: json_operations::get_favorite_airports(),
+        recent_airports: json_operations::get_recent_airports(),
+        extra_airports: json_operations::get_extra_airports(),
+        extra_data: HashMap::new(),
+        extra_request: None,
+        airport_tab: AirportTab::Departure,
+        extra_entry: String::new(),
+        extra_entry_error: None,
+        arrival_first: false,
+        atc_changes: Vec::new(),
+        atis_letters: HashMap::new(),
+        new_atis: HashMap::new(),
+        atis_watch_update: None,
+        winds: Ok(Vec::new()),
+        winds_update: None,
+        airport_info: HashMap::new(),
+        airport_info_update: None,
+        download_update: None,
+        download_status: Ok(String::new()),
+        flight_timer: FlightTimer::default(),
+        last_atis_watch: Instant::now(),
+        atis_notice: None,
+        paused: false,
+        quiet_hours: json_operations::get_json_data(JsonKey::QuietHours),
+        quiet_paused: false,
+        quiet_override: false,
+        disclaimer: json_operations::get_json_data(JsonKey::Disclaimer),
+        notifications: json_operations::get_json_data(JsonKey::Notifications) == "true",
+        discord_presence: json_operations::get_json_data(JsonKey::DiscordPresence) == "true",
+        discord_app_id: json_operations::get_json_data(JsonKey::DiscordAppId),
+        presence: None,
+        simulator: Simulator::from_name(&json_operations::get_json_data(JsonKey::Simulator)),
+        sim_update: None,
+        aircraft: None,
+        sim_status: String::new(),
+        approach_refreshed: None,
+        nearest_update: None,
+        check_for_updates: json_operations::get_json_data(JsonKey::CheckForUpdates) == "true",
+        update_check: None,
+        new_release: None,
+        translation_key: json_operations::get_json_data(JsonKey::TranslationKey),
+        api_auths: Source::KEYED
+            .iter()
+            .map(|&source| (source, json_operations::get_api_auth(source)))
+            .collect(),
+        network: Network::from_name(&json_operations::get_json_data(JsonKey::Network)),
+        download_dir: json_operations::get_json_data(JsonKey::DownloadDir),
+        sim_flight_plan_dir: json_operations::get_json_data(JsonKey::SimFlightPlanDir),
+        briefing_dir: json_operations::get_json_data(JsonKey::BriefingDir),
+        briefing_format: BriefingFormat::from_extension(
+            &json_operations::get_json_data(JsonKey::BriefingFormat)
+        ),
+        export_status: Ok(String::new()),
+        crosswind_limit: json_operations::get_json_data(JsonKey::CrosswindLimit).parse().unwrap_or(20.0),
+        metar_age_warning: json_operations::get_json_data(JsonKey::MetarAgeWarningMinutes)
+            .parse()
+            .unwrap_or(75),
+        metar_age_alert: json_operations::get_json_data(JsonKey::MetarAgeAlertMinutes)
+            .parse()
+            .unwrap_or(120),
+        ui_scale: json_operations::get_json_data(JsonKey::UiScale)
+            .parse::<f32>()
+            .map_or(1.0, |scale| scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)),
+        cache_seconds: json_operations::get_json_data(JsonKey::CacheSeconds)
+            .parse()
+            .unwrap_or(cache::DEFAULT_TTL),
+        retry_attempts: json_operations::get_json_data(JsonKey::RetryAttempts)
+            .parse()
+            .unwrap_or(RetryPolicy::DEFAULT.attempts),
+        log_config: log_config(),
+        refresh_plan: refresh_plan(),
+        log_level: Level::INFO,
+        proxy: ProxySettings {
+            url: json_operations::get_json_data(JsonKey::Proxy),
+            username: json_operations::get_json_data(JsonKey::ProxyUser),
+            password: json_operations::get_json_data(JsonKey::ProxyPassword),
+        },
+        proxy_error: None,
+        endpoints: Endpoints::from_user_data(),
+        endpoints_error: None,
+        conversions: Conversion::ALL
+            .iter()
+            .map(|&conversion| (conversion, 1.0, conversion.forward(1.0)))
+            .collect(),
+        calculator_heading: 360.0,
+        calculator_wind: Wind { direction: Some(360.0), speed: 0.0, gust: None },
+        auto_print: json_operations::get_json_data(JsonKey::AutoPrint) == "true",
+        metar_history: MetarHistory::default(),
+        keep_history: json_operations::get_json_data(JsonKey::KeepHistory) == "true",
+        profiles: json_operations::get_profile_names(),
+        active_profile: json_operations::get_active_profile(),
+        new_profile_name: String::new(),
+        problem_report: None,
+        problem_report_error: None,
+        crash_report_pending: crash::take_pending(),
+        safe_mode: None,
+        started: Instant::now(),
+        startup_confirmed: false,
+        // A broken layout shouldn't survive safe mode
+        session: if safe { UiState::default() } else { UiState::load() },
+        restore_scroll: None,
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        tray: None,
     };
 
+    if safe {
+        contend.safe_mode = Some(tr_args("safe-mode", &[("count", &failed_starts)]));
+        contend.notifications = false;
+        contend.discord_presence = false;
+        contend.simulator = None;
+        contend.translation_key.clear();
+        contend.auto_print = false;
+        contend.keep_history = false;
+        contend.paused = true;
+        contend.state = AppState::Paused;
+    }
+
+    // A broken proxy or endpoint shouldn't survive safe mode either, the defaults stay until the next start
+    if !safe {
+        cache::set_ttl(Duration::from_secs(contend.cache_seconds));
+        retry::set_policy(RetryPolicy { attempts: contend.retry_attempts, ..RetryPolicy::DEFAULT });
+        contend.apply_proxy();
+        contend.apply_endpoints();
+    }
+    contend.apply_presence();
+    contend.apply_simulator();
+    contend.restore_scroll = Some(contend.session.scroll_offset);
+
+    if contend.keep_history {
+        contend.metar_history = MetarHistory::load();
+    }
+
+    let airports = launch_options.airports();
+
+    let session = &contend.session;
+    let size = if session.compact { COMPACT_SIZE } else { session.window_size.unwrap_or(DEFAULT_SIZE) };
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(size) // [x, y]
+        .with_maximized(session.maximized && !session.compact)
+        .with_decorations(!session.compact)
+        .with_window_level(window_level(session.always_on_top));
+    if let Some(position) = contend.session.window_position {
+        viewport = viewport.with_position(position);
+    }
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size(
-            [750.0, 725.0]), // [x, y]
+        viewport,
         ..Default::default()
     };
 
     eframe::run_native(
         "Get Flight Data",
         options,
-        Box::new(|_| {
+        Box::new(move |cc| {
+            cc.egui_ctx.set_zoom_factor(contend.ui_scale);
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
+            if !safe {
+                contend.tray = tray::Tray::new(&cc.egui_ctx)
+                    .map_err(|err| warn!("Failed to create tray icon: {err}"))
+                    .ok();
+            }
+            if contend.check_for_updates && !safe {
+                contend.check_for_update(&cc.egui_ctx);
+            }
+
+            match (launch_error, airports) {
+                // Wait for the user to decide how to continue
+                _ if safe => (),
+                // Show why the arguments were ignored instead of loading anything
+                (Some(err), _) => contend.state = AppState::Error(err),
+                // A flight given at launch replaces the SimBrief flight plan
+                (None, Some((departure, arrival))) => {
+                    contend.departure = departure;
+                    contend.arrival = arrival;
+                    contend.fetch_weather(&cc.egui_ctx);
+                }
+                (None, None) => contend.fetch_flight_plan(&cc.egui_ctx),
+            }
             Box::<DataCarrier>::new(contend)
         }),
     ).unwrap_or_else(|err| {
-        log(&format!("Failed to run Egui frame: {err}"));
+        error!("Failed to run Egui frame: {err}");
         process::exit(1);
     });
 
+    // Closing the window is a successful start, however short it was
+    safe_mode::startup_succeeded();
     println!("Shutting down")
 }
 
 impl eframe::App for DataCarrier {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.apply_quiet_hours(ctx);
+        // Apply finished requests and start the automatic refresh
+        self.poll(ctx);
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        self.handle_tray(ctx);
+        self.show_problem_report(ctx);
+        self.show_crash_notice(ctx);
+        self.confirm_startup(ctx);
+        self.handle_shortcuts(ctx);
+        self.record_window(ctx);
+        // Save the zoom, whether it was set in the settings or with the keyboard
+        let zoom = ctx.zoom_factor();
+        if zoom != self.ui_scale {
+            self.ui_scale = zoom;
+            json_operations::set_json_data(JsonKey::UiScale, &format!("{zoom:.2}"));
+        }
+
+        if self.session.compact {
+            egui::CentralPanel::default().show(ctx, |ui| self.show_compact(ui, ctx));
+            return;
+        }
         egui::CentralPanel::default().show(ctx, |ui| {
-            let five_mins = Duration::from_secs(5 * 60);
+            let mut scroll_area = egui::ScrollArea::vertical().auto_shrink(false);
+            // Only on the first frame, afterwards it's up to the user
+            if let Some(offset) = self.restore_scroll.take() {
+                scroll_area = scroll_area.vertical_scroll_offset(offset);
+            }
+            let output = scroll_area.show(ui, |ui| self.show_main(ui, ctx));
+            self.session.scroll_offset = output.state.offset.y;
+        });
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Some(ref worker) = self.worker {
+            worker.shutdown();
+        }
+        if let Err(err) = self.session.save() {
+            warn!("Failed to save UI state: {err}");
+        }
+    }
+}
+
+impl DataCarrier {
+    /// Shows everything below the panel, inside the scroll area.
+    fn show_main(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        self.show_safe_mode(ui, ctx);
+        self.show_error_banner(ui);
+        self.show_update_banner(ui);
+
+        ui.horizontal(|ui| {
+            // Give the user a way to manually reload
+            if ui.button(tr("reload-data")).on_hover_text(tr_args("shortcut", &[("key", &"R")])).clicked() {
+                self.fetch_weather(ctx);
+            }
+
+            // Keeps refreshing while hidden, e.g. behind a full-screen simulator
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
+            if self.tray.is_some() && ui.button(tr("hide-to-tray")).clicked() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+            }
+
+            if matches!(self.state, AppState::FetchingFp { .. }) {
+                // Only show while updating
+                ui.label(tr("loading-flight-plan"));
+                ui.spinner();
+            } else if ui.button(tr("reload-flight-plan"))
+                .on_hover_text(tr_args("shortcut", &[("key", &"F")]))
+                .clicked() {
+                self.fetch_flight_plan(ctx);
+            }
+
+            // Checkbox for users to stop automatic updates
+            // In cruise you usually don't need those constant calls
+            let text = tr("suppress-updates");
+            let shortcut = tr_args("shortcut", &[("key", &"P")]);
+            if ui.checkbox(&mut self.paused, text).on_hover_text(shortcut).changed() {
+                self.set_paused(self.paused);
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                show_clock(ui);
+                if ui.button(tr("compact-view")).clicked() {
+                    self.set_compact(ctx, true);
+                }
+                self.show_pin(ui, ctx);
+            });
+        });
+
+        self.show_airport_entry(ui, ctx);
+        if let Some((departure_val, arrival_val)) = self.data.as_ref() {
+            show_distance(ui, departure_val, arrival_val, self.flight_plan.as_ref());
+        }
 
+        if self.quiet_paused {
             ui.horizontal(|ui| {
-                // Give the user a way to manually reload
-                if ui.button("Reload data").clicked() {
-                    self.last_update = Instant::now() - five_mins;
-                    self.manual_update = true;
+                let quiet_hours = self.quiet_hours.trim();
+                ui.label(tr_args("quiet-hours-active", &[("hours", &quiet_hours)]));
+                if ui.small_button(tr("resume-now")).clicked() {
+                    self.quiet_override = true;
                 }
+            });
+        }
 
-                if self.loading_flight_plan {
-                    // Only show while updating
-                    ui.label("Loading Flight Plan...");
-                    ui.spinner();
+        if let Some(simulator) = self.simulator {
+            self.show_aircraft(ui, ctx, simulator);
+        }
 
-                    if let Some(ref flight_plan_update) = self.flight_plan_update {
-                        match flight_plan_update.try_recv() {
-                            Ok((departure, arrival)) => {
-                                // Update received, apply it
-                                self.departure = departure;
-                                self.arrival = arrival;
-                                // Force an update, regardless if paused
-                                self.last_update = Instant::now() - five_mins;
-                                self.manual_update = true;
-
-                                // Stop loading and clear the Receiver
-                                self.loading_flight_plan = false;
-                                self.flight_plan_update = None;
-                            }
-                            // If no update received yet, nothing to do
-                            Err(_) => (),
-                        }
-                    }
+        // Watch for the arrival ATIS while there is none,
+        // the datafeed is cheap to check and doesn't cost AVWX quota
+        let atis_watch_interval = Duration::from_secs(60);
+        if let Some((_, arrival_val)) = self.data.as_ref() {
+            let waiting = matches!(self.state, AppState::Idle | AppState::Error(_)) && !self.paused;
+            if !arrival_val.atis_online && waiting && self.atis_watch_update.is_none() {
+                if self.last_atis_watch.elapsed() >= atis_watch_interval {
+                    self.last_atis_watch = Instant::now();
+
+                    let (tx, rx) = mpsc::channel();
+                    self.atis_watch_update = Some(rx);
+
+                    let icao = arrival_val.icao.clone();
+                    let network = arrival_val.network;
+                    let ctx = ctx.clone();
+                    self.runtime.spawn(async move {
+                        let online = match network {
+                            Network::Vatsim => vatsim::atis_online(&icao).await,
+                            Network::Ivao => ivao::atis_online(&icao).await,
+                            Network::PilotEdge => pilotedge::atis_online(&icao).await,
+                        };
+                        let _ = tx.send((icao, online));
+                        ctx.request_repaint();
+                    });
                 } else {
-                    // Reload flight plan if button clicked or SimBrief username is changed
-                    if ui.button("Reload Flight Plan").clicked() || self.username_changed {
-                        // Begin loading
-                        self.loading_flight_plan = true;
-                        self.username_changed = false;
-
-                        // Reset the Receiver
-                        let (tx, rx) = mpsc::channel();
-                        self.flight_plan_update = Some(rx);
-
-                        // Spawn a new thread to perform the update
-                        thread::spawn(move || {
-                            let (departure, arrival) = logic::update_fp();
-
-                            // Send the update back to the main thread
-                            tx.send((departure, arrival)).unwrap();
-                        });
+                    // Wake up for the next check, even without user input
+                    ctx.request_repaint_after(atis_watch_interval - self.last_atis_watch.elapsed());
+                }
+            }
+        }
+
+        // Receive the result of a running ATIS check
+        if let Some(ref atis_watch_update) = self.atis_watch_update {
+            match atis_watch_update.try_recv() {
+                Ok((icao, result)) => {
+                    self.atis_watch_update = None;
+                    let still_arrival = self.data
+                        .as_ref()
+                        .is_some_and(|(_, arrival_val)| arrival_val.icao == icao);
+
+                    match result {
+                        Ok(true) if still_arrival => {
+                            info!("{icao} ATIS is now online");
+                            let notice = tr_args("atis-online", &[("icao", &icao)]);
+                            self.notify(&notice, &tr("fetching-new-atis"));
+                            self.atis_notice = Some(notice);
+                            // Fetch the new ATIS right away
+                            self.fetch_weather(ctx);
+                            // The app is usually behind the simulator, make the user look
+                            ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+                                egui::UserAttentionType::Informational));
+                        }
+                        Ok(_) => (),
+                        Err(err) => warn!("Failed to check ATIS of {icao}: {err}"),
                     }
                 }
+                Err(mpsc::TryRecvError::Empty) => (),
+                Err(mpsc::TryRecvError::Disconnected) => self.atis_watch_update = None,
+            }
+        }
 
-                // Checkbox for users to stop automatic updates
-                // In cruise you usually don't need those constant calls
-                let text = "Suppress automatic updates";
-                ui.checkbox(&mut self.stop_updating, text);
+        // Check loading status
+        if matches!(self.state, AppState::FetchingWx { .. }) {
+            ui.add_space(25.0);
+
+            ui.horizontal(|ui| {
+                ui.label(tr("loading-data"));
+                ui.spinner();
             });
+            show_loading(ui, &self.loading);
+        }
 
-            // Was the last update > 5 mins ago?
-            if (!self.stop_updating || self.manual_update)
-                && self.last_update.elapsed() >= five_mins {
+        if let Some(ref notice) = self.atis_notice {
+            ui.add_space(25.0);
+            let mut dismissed = false;
+            ui.horizontal(|ui| {
+                ui.colored_label(egui::Color32::GREEN, notice);
+                dismissed = ui.small_button(tr("dismiss")).clicked();
+            });
+            if dismissed {
+                self.atis_notice = None;
+            }
+        }
 
-                // Set times
-                self.local_time = Local::now();
-                self.utc_time = Utc::now();
-                // Reset activation conditions
-                self.last_update = Instant::now();
-                self.manual_update = false;
+        if let (true, Some(time), Some(_)) = (self.offline, self.data_time, &self.data) {
+            ui.add_space(25.0);
+            show_offline_banner(ui, time);
+            // Keep the age current
+            ctx.request_repaint_after(Duration::from_secs(60));
+        }
 
-                // Clone for use in new Thread
-                let data_to_update = self.data.clone();
-                let loading_status = self.loading.clone();
+        if let AppState::Error(ref err) = self.state {
+            ui.add_space(25.0);
+            ui.horizontal(|ui| {
+                ui.colored_label(egui::Color32::RED, err);
+                if ui.small_button(tr("open-log-folder")).clicked() {
+                    log_open_error(platform::open_log_folder(), "log folder");
+                }
+            });
+        }
 
-                loading_status.store(true, Ordering::Relaxed); // Set loading status
+        ui.add_space(25.0);
+        self.show_flight_timer(ui);
+        if self.flight_timer.is_running() {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
 
-                // Clone the fields to use in new thread
-                let departure = self.departure.clone();
-                let arrival = self.arrival.clone();
+        let mut requested_download = None;
+        if let Some(ref flight_plan) = self.flight_plan {
+            ui.add_space(25.0);
+            let title = format!("{} {}", tr("flight-plan"), attribution(&[Source::SimBrief]));
+            let response = egui::CollapsingHeader::new(title)
+                .default_open(self.session.is_open("flight_plan"))
+                .show(ui, |ui| {
+                    show_flight_plan(ui, flight_plan);
+                    requested_download = show_downloads(
+                        ui,
+                        &flight_plan.files,
+                        self.download_update.is_some(),
+                        &self.download_status,
+                    );
+                });
+            self.session.set_open("flight_plan", is_expanded(&response));
+            let title = format!("{} {}", tr("enroute"), attribution(&[Source::OpenMeteo]));
+            let response = egui::CollapsingHeader::new(title)
+                .default_open(self.session.is_open("enroute"))
+                .show(ui, |ui| {
+                    if self.winds_update.is_some() {
+                        ui.spinner();
+                    } else {
+                        show_winds(ui, flight_plan, &self.winds);
+                    }
+                });
+            self.session.set_open("enroute", is_expanded(&response));
+            if flight_plan.profile.len() > 1 {
+                let winds = self.winds.as_deref().unwrap_or_default();
+                let title = format!("{} {}", tr("vertical-profile"), attribution(&[Source::SimBrief]));
+                let response = egui::CollapsingHeader::new(title)
+                    .default_open(self.session.is_open("profile"))
+                    .show(ui, |ui| show_profile(ui, flight_plan, winds));
+                self.session.set_open("profile", is_expanded(&response));
+            }
+        }
+        if let Some(file) = requested_download {
+            self.download(ctx, file);
+        }
 
-                thread::spawn(move || {
-                    let new_data = logic::update_data(&departure, &arrival);
+        let markers = map_markers(self.flight_plan.as_ref(), self.data.as_ref(), self.aircraft.as_ref());
+        if markers.len() > 1 {
+            ui.add_space(25.0);
+            let route = self.flight_plan.as_ref().map(|flight_plan| flight_plan.profile.as_slice());
+            let response = egui::CollapsingHeader::new(tr("route-map"))
+                .default_open(self.session.is_open("map"))
+                .show(ui, |ui| show_route_map(ui, &markers, route.unwrap_or_default()));
+            self.session.set_open("map", is_expanded(&response));
+        }
 
-                    // Update shared data
-                    match data_to_update.lock() {
-                        Ok(mut data) => {
-                            *data = Some(new_data);
-                        }
-                        Err(err) => {
-                            let msg = &format!("Mutex was poisoned. \
-                            Failed to fetch data from the `data` Mutex guard: {err}");
-                            log(msg);
-                            process::exit(1);
-                        }
-                    }
+        // If data is available, display it
+        if let Some((departure_val, arrival_val)) = self.data.as_ref() {
+            if !matches!(self.state, AppState::FetchingWx { .. }) {
+                ui.add_space(25.0);
 
-                    // Now, when loading is done, set flag to false
-                    loading_status.store(false, Ordering::Relaxed);
+                let phase = self.refresh_phase();
+                let phase_text = tr(match phase {
+                    RefreshPhase::Ground => "refresh-phase-ground",
+                    RefreshPhase::Cruise => "refresh-phase-cruise",
+                    RefreshPhase::Arrival => "refresh-phase-arrival",
                 });
+                ui.label(tr_args("refresh-interval", &[
+                    ("minutes", &(self.refresh_plan.interval(phase).as_secs() / 60)),
+                    ("phase", &phase_text),
+                    ("local", &self.local_time.format("%H:%M")),
+                    ("utc", &self.utc_time.format("%H:%M")),
+                ]));
+                if let Some(remaining) = self.next_refresh() {
+                    let seconds = remaining.as_secs();
+                    let time = format!("{}:{:02}", seconds / 60, seconds % 60);
+                    ui.label(tr_args("next-update", &[("time", &time)]));
+                    // Count down without user input
+                    ctx.request_repaint_after(Duration::from_secs(1));
+                }
+                show_quota(ui);
             }
+            let flight_plan = self.flight_plan.as_ref();
+            show_sun_times(ui, departure_val, "ETD", flight_plan.and_then(|flight_plan| flight_plan.etd));
+            show_sun_times(ui, arrival_val, "ETA", flight_plan.and_then(|flight_plan| flight_plan.eta));
+            ui.add_space(25.0);
 
-            // Check loading status
-            {
-                if self.loading.load(Ordering::Relaxed) {
-                    ui.add_space(25.0);
-
-                    ui.horizontal(|ui| {
-                        ui.label("Loading data...");
-                        ui.spinner();
-                    });
+            if !self.atc_changes.is_empty() {
+                for change in &self.atc_changes {
+                    let (key, callsign) = match change {
+                        StationChange::LoggedOn(callsign) => ("station-logged-on", callsign),
+                        StationChange::LoggedOff(callsign) => ("station-logged-off", callsign),
+                    };
+                    ui.colored_label(egui::Color32::LIGHT_BLUE, tr_args(key, &[("callsign", callsign)]));
                 }
+                ui.add_space(25.0);
             }
 
-            // Access shared data
-            {
-                match self.data.lock() {
-                    Ok(data) => {
-                        // If data is available, display it
-                        if let Some((departure_val, arrival_val)) = data.as_ref() {
-                            if !self.loading.load(Ordering::Relaxed) {
-                                ui.add_space(25.0);
+        }
+        if self.data.is_some() {
+            self.show_airport_tabs(ui, ctx);
+        }
+
+        ui.add_space(25.0);
+
+        let response = egui::CollapsingHeader::new(tr("weather-statistics"))
+            .default_open(self.session.is_open("statistics"))
+            .show(ui, |ui| show_statistics(ui, &self.metar_history, self.keep_history));
+        self.session.set_open("statistics", is_expanded(&response));
+
+        let response = egui::CollapsingHeader::new(tr("tools"))
+            .default_open(self.session.is_open("tools"))
+            .show(ui, |ui| {
+                show_converters(ui, &mut self.conversions);
+                ui.add_space(10.0);
+                show_wind_calculator(
+                    ui,
+                    &mut self.calculator_heading,
+                    &mut self.calculator_wind,
+                    self.crosswind_limit,
+                );
+            });
+        self.session.set_open("tools", is_expanded(&response));
+
+        // Add a way to store credentials
+        let response = egui::CollapsingHeader::new(tr("set-credentials"))
+            .default_open(self.session.is_open("credentials"))
+            .show(ui, |ui| {
+                // A panic while editing must not end the app, start over with empty fields instead
+                for (field, name, key) in [
+                    (&mut self.username, "username", "field-username"),
+                    (&mut self.api_key, "API key", "field-api-key"),
+                ] {
+                    if field.is_poisoned() {
+                        error!("Mutex of the entered {name} was poisoned, clearing it");
+                        *field = Arc::new(Mutex::new(String::new()));
+                        self.error_banner = Some(tr_args("credentials-cleared", &[("field", &tr(key))]));
+                    }
+                }
+
+                // Lock clones, so the guards don't keep `self` borrowed
+                let username = Arc::clone(&self.username);
+                let api_key = Arc::clone(&self.api_key);
+                let mut username = username.lock().unwrap_or_else(PoisonError::into_inner);
+                let mut api_key = api_key.lock().unwrap_or_else(PoisonError::into_inner);
 
-                                ui.label(format!("Data will be refreshed every five minutes, \
-                                        last request time was at: {}lcl ({}z)",
-                                                 self.local_time.format("%H:%M"),
-                                                 self.utc_time.format("%H:%M")));
+                // Switch between the credentials of e.g. different virtual airlines
+                ui.horizontal(|ui| {
+                    ui.label(tr("profile"));
+                    let mut selected = self.active_profile.clone();
+                    let selected_text = if selected.is_empty() { tr("none") } else { selected.clone() };
+                    egui::ComboBox::from_id_source("profile")
+                        .selected_text(selected_text.to_string())
+                        .show_ui(ui, |ui| {
+                            for name in &self.profiles {
+                                ui.selectable_value(&mut selected, name.clone(), name);
                             }
-                            ui.add_space(25.0);
+                        });
+
+                    if selected != self.active_profile && json_operations::switch_profile(&selected) {
+                        self.active_profile = selected;
+                        // Load the flight plan and weather with the new credentials
+                        self.fetch_flight_plan(ctx);
+                    }
+
+                    if !self.active_profile.is_empty() && ui.button(tr("delete-profile")).clicked() {
+                        json_operations::delete_profile(&self.active_profile);
+                        self.profiles = json_operations::get_profile_names();
+                        self.active_profile.clear();
+                    }
+                });
 
-                            ui.heading("Departure");
-                            ui.label(format!("{}", departure_val));
+                ui.horizontal(|ui| {
+                    ui.label(tr("save-as-profile"));
+                    ui.text_edit_singleline(&mut self.new_profile_name);
+                    let name = self.new_profile_name.trim().to_string();
+                    if ui.button(tr("save-profile")).clicked() && !name.is_empty() {
+                        json_operations::save_profile(&name);
+                        self.profiles = json_operations::get_profile_names();
+                        self.active_profile = name;
+                        self.new_profile_name.clear();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("username"));
+                    ui.text_edit_singleline(&mut *username);
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("api-key"));
+                    ui.text_edit_singleline(&mut *api_key);
+                });
+
+                if ui.button(tr("save")).clicked() {
+                    // Set data if not empty
+                    if !username.trim().is_empty() || !api_key.trim().is_empty() {
+                        let mut username_changed = false;
+                        // Set username if not empty and different
+                        if !username.trim().is_empty() &&
+                            username.trim() != json_operations::get_json_data(JsonKey::Name) {
+                            json_operations::set_json_data(JsonKey::Name, username.trim());
+                            info!("Replacing username");
+                            username_changed = true;
+                        }
+                        // Set API-Key if not empty and different
+                        if !api_key.trim().is_empty() &&
+                            api_key.trim() != json_operations::get_json_data(JsonKey::Key) {
+                            json_operations::set_json_data(JsonKey::Key, api_key.trim());
+                            info!("Replacing API-Key");
+                        }
 
-                            ui.add_space(25.0);
+                        // Keep the active profile in sync with its credentials
+                        if !self.active_profile.is_empty() {
+                            json_operations::save_profile(&self.active_profile);
+                        }
 
-                            ui.heading("Arrival");
-                            ui.label(format!("{}", arrival_val));
+                        // Display changed data message
+                        self.save_credential_time = Instant::now();
+                        // Reload on change of data, the flight plan if the SimBrief username changed
+                        if username_changed {
+                            self.fetch_flight_plan(ctx);
+                        } else if !self.paused {
+                            self.fetch_weather(ctx);
                         }
                     }
-                    Err(err) => {
-                        let msg = &format!("Mutex was poisoned. \
-                            Failed to fetch data from the `data` Mutex guard: {err}");
-                        log(msg);
-                        process::exit(1);
+                    // Clear both fields, even if no contend
+                    username.clear();
+                    api_key.clear();
+                }
+
+                ui.horizontal(|ui| {
+                    let blank = username.trim().is_empty() && api_key.trim().is_empty();
+                    if self.credential_test_update.is_some() {
+                        ui.label(tr("testing"));
+                        ui.spinner();
+                    } else if ui.add_enabled(!blank, egui::Button::new(tr("test-credentials"))).clicked() {
+                        self.test_credentials(ctx, username.trim(), api_key.trim());
                     }
+                    ui.weak(tr("test-credentials-hint"));
+                });
+                for (service, result) in &self.credential_test {
+                    let (color, message) = match result {
+                        Ok(message) => (egui::Color32::GREEN, message),
+                        Err(message) => (egui::Color32::RED, message),
+                    };
+                    ui.colored_label(color, format!("{service}: {message}"));
                 }
-            }
 
-            ui.add_space(25.0);
+                // If a credential was saved in the last five seconds
+                if self.save_credential_time.elapsed() <= Duration::from_secs(5) {
+                    // Display success message
+                    // Note: the program would panic if not successful,
+                    // so we can assume it worked
+                    ui.colored_label(egui::Color32::GREEN, tr("credentials-saved"));
+                }
 
-            // Add a way to store credentials
-            egui::CollapsingHeader::new("Set Credentials")
-                .show(ui, |ui| {
-                    let mut username = match self.username.lock() {
-                        Ok(name) => name,
-                        Err(err) => {
-                            let msg = &format!("Mutex was poisoned. \
-                            Failed to fetch data from the `data` Mutex guard: {err}");
-                            log(msg);
-                            process::exit(1);
+                ui.horizontal(|ui| {
+                    if ui.button(tr("get-avwx-key")).clicked() {
+                        log_open_error(platform::open_url(AVWX_KEY_URL), AVWX_KEY_URL);
+                    }
+                    if ui.button(tr("open-config-folder")).clicked() {
+                        log_open_error(platform::open_config_folder(), "config folder");
+                    }
+                    if ui.button(tr("open-log-folder")).clicked() {
+                        log_open_error(platform::open_log_folder(), "log folder");
+                    }
+                    if ui.button(tr("report-problem")).clicked() {
+                        self.problem_report.get_or_insert_with(String::new);
+                    }
+                });
+            });
+        self.session.set_open("credentials", is_expanded(&response));
+
+        let response = egui::CollapsingHeader::new(tr("settings"))
+            .default_open(self.session.is_open("settings"))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("export-disclaimer"));
+                    let response = ui.text_edit_singleline(&mut self.disclaimer);
+                    if response.lost_focus() {
+                        json_operations::set_json_data(JsonKey::Disclaimer, self.disclaimer.trim());
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("network"));
+                    let previous = self.network;
+                    egui::ComboBox::from_id_source("network")
+                        .selected_text(self.network.name())
+                        .show_ui(ui, |ui| {
+                            for network in Network::ALL {
+                                ui.selectable_value(&mut self.network, network, network.name());
+                            }
+                        });
+                    if self.network != previous {
+                        json_operations::set_json_data(JsonKey::Network, self.network.name());
+                        if !self.paused && !self.departure.is_empty() {
+                            self.fetch_weather(ctx);
                         }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("simulator"));
+                    let previous = self.simulator;
+                    let name = |simulator: Option<Simulator>| {
+                        simulator.map_or_else(|| tr("off"), |simulator| simulator.name().to_string())
                     };
-                    let mut api_key = match self.api_key.lock() {
-                        Ok(key) => key,
-                        Err(err) => {
-                            let msg = &format!("Mutex was poisoned. \
-                            Failed to fetch data from the `data` Mutex guard: {err}");
-                            log(msg);
-                            process::exit(1);
+                    egui::ComboBox::from_id_source("simulator")
+                        .selected_text(name(self.simulator))
+                        .show_ui(ui, |ui| {
+                            for simulator in std::iter::once(None).chain(Simulator::ALL.map(Some)) {
+                                ui.selectable_value(&mut self.simulator, simulator, name(simulator));
+                            }
+                        });
+                    if self.simulator != previous {
+                        let name = self.simulator.map(|simulator| simulator.name()).unwrap_or_default();
+                        json_operations::set_json_data(JsonKey::Simulator, name);
+                        self.apply_simulator();
+                    }
+                    ui.weak(tr("simulator-hint"));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("deepl-key"));
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.translation_key).password(true));
+                    if response.lost_focus() {
+                        json_operations::set_json_data(JsonKey::TranslationKey, self.translation_key.trim());
+                    }
+                });
+
+                ui.label(tr("api-headers"));
+                egui::Grid::new("api_auths").striped(true).show(ui, |ui| {
+                    ui.strong(tr("provider"));
+                    ui.strong(tr("header"));
+                    ui.strong(tr("prefix"));
+                    ui.end_row();
+
+                    for (source, auth) in &mut self.api_auths {
+                        ui.label(source.name());
+                        let header = ui.text_edit_singleline(&mut auth.header);
+                        let prefix = ui.text_edit_singleline(&mut auth.prefix);
+                        let reset = ui.button(tr("reset")).clicked();
+                        let edited = header.lost_focus() || prefix.lost_focus();
+
+                        // A request without a header name would fail, so fall back to the default
+                        if reset || (edited && auth.header.trim().is_empty()) {
+                            *auth = source.default_auth();
                         }
-                    };
+                        if reset || edited {
+                            json_operations::set_api_auth(*source, auth);
+                        }
+                        ui.end_row();
+                    }
+                });
 
-                    ui.horizontal(|ui| {
-                        ui.label("Username:");
-                        ui.text_edit_singleline(&mut *username);
-                    });
+                ui.horizontal(|ui| {
+                    ui.label(tr("quiet-hours"));
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.quiet_hours)
+                        .hint_text(tr("quiet-hours-example"))
+                        .desired_width(100.0));
+                    if response.lost_focus() {
+                        json_operations::set_json_data(JsonKey::QuietHours, self.quiet_hours.trim());
+                    }
+                    let quiet_hours = self.quiet_hours.trim();
+                    if !quiet_hours.is_empty() && DailyPeriod::parse(quiet_hours).is_none() {
+                        ui.colored_label(egui::Color32::RED, tr("quiet-hours-invalid"));
+                    } else {
+                        ui.weak(tr("quiet-hours-hint"));
+                    }
+                });
 
+                egui::Grid::new("folders").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("download-folder"));
+                    let hint = default_download_dir().display().to_string();
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.download_dir)
+                        .hint_text(hint));
+                    if response.lost_focus() {
+                        json_operations::set_json_data(JsonKey::DownloadDir, self.download_dir.trim());
+                    }
+                    ui.end_row();
+
+                    ui.label(tr("copy-flight-plan-to"));
+                    let hint = tr("copy-flight-plan-hint");
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.sim_flight_plan_dir)
+                        .hint_text(hint));
+                    if response.lost_focus() {
+                        let folder = self.sim_flight_plan_dir.trim();
+                        json_operations::set_json_data(JsonKey::SimFlightPlanDir, folder);
+                    }
+                    ui.end_row();
+
+                    ui.label(tr("export-briefings-to"));
                     ui.horizontal(|ui| {
-                        ui.label("API Key:     ");
-                        ui.text_edit_singleline(&mut *api_key);
+                        let response = ui.add(egui::TextEdit::singleline(&mut self.briefing_dir)
+                            .hint_text(tr("download-folder-hint")));
+                        if response.lost_focus() {
+                            json_operations::set_json_data(JsonKey::BriefingDir, self.briefing_dir.trim());
+                        }
+                        for format in BriefingFormat::ALL {
+                            let label = format!("{} (.{})", format.name(), format.extension());
+                            if ui.radio_value(&mut self.briefing_format, format, label).changed() {
+                                json_operations::set_json_data(JsonKey::BriefingFormat, format.extension());
+                            }
+                        }
                     });
+                    ui.end_row();
+                });
 
-                    if ui.button("Save").clicked() {
-                        // Set data if not empty
-                        if !username.trim().is_empty() || !api_key.trim().is_empty() {
-                            // Set username if not empty and different
-                            if !username.trim().is_empty() &&
-                                username.trim() != json_operations::get_json_data(JsonKey::Name) {
-                                json_operations::set_json_data(JsonKey::Name, username.trim());
-                                log("Replacing username");
-                                // Reload Flight Plan from SimBrief with new username
-                                self.username_changed = true;
-                            }
-                            // Set API-Key if not empty and different
-                            if !api_key.trim().is_empty() &&
-                                api_key.trim() != json_operations::get_json_data(JsonKey::Key) {
-                                json_operations::set_json_data(JsonKey::Key, api_key.trim());
-                                log("Replacing API-Key");
+                ui.horizontal(|ui| {
+                    ui.label(tr("crosswind-limit"));
+                    let response = ui.add(egui::DragValue::new(&mut self.crosswind_limit)
+                        .clamp_range(0.0..=60.0)
+                        .suffix(" kt"));
+                    if response.drag_released() || response.lost_focus() {
+                        let limit = self.crosswind_limit.to_string();
+                        json_operations::set_json_data(JsonKey::CrosswindLimit, &limit);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("language"));
+                    let mut language = i18n::language();
+                    egui::ComboBox::from_id_source("language")
+                        .selected_text(language.name())
+                        .show_ui(ui, |ui| {
+                            for option in Language::ALL {
+                                ui.selectable_value(&mut language, option, option.name());
                             }
+                        });
+                    if language != i18n::language() {
+                        i18n::set_language(language);
+                        json_operations::set_json_data(JsonKey::Language, language.code());
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("ui-scale"));
+                    // Applied once released, zooming while dragging would move the slider away
+                    let mut scale = ctx.zoom_factor();
+                    let response = ui.add(egui::Slider::new(&mut scale, MIN_UI_SCALE..=MAX_UI_SCALE)
+                        .step_by(0.1)
+                        .custom_formatter(|scale, _| format!("{:.0} %", scale * 100.0))
+                        .custom_parser(|text| text.trim_end_matches([' ', '%']).parse::<f64>().ok()
+                            .map(|percent| percent / 100.0)));
+                    if response.drag_released() || (response.changed() && !response.dragged()) {
+                        ctx.set_zoom_factor(scale);
+                    }
+                    ui.weak(tr("ui-scale-hint"));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("metar-age-warning"));
+                    let warning = ui.add(egui::DragValue::new(&mut self.metar_age_warning)
+                        .clamp_range(10..=600)
+                        .suffix(" min"));
+                    ui.label(tr("metar-age-alert"));
+                    let alert = ui.add(egui::DragValue::new(&mut self.metar_age_alert)
+                        .clamp_range(10..=600)
+                        .suffix(" min"));
+                    let saved = |response: &egui::Response| response.drag_released() || response.lost_focus();
+                    if saved(&warning) || saved(&alert) {
+                        let warning = self.metar_age_warning.to_string();
+                        json_operations::set_json_data(JsonKey::MetarAgeWarningMinutes, &warning);
+                        let alert = self.metar_age_alert.to_string();
+                        json_operations::set_json_data(JsonKey::MetarAgeAlertMinutes, &alert);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("cache-duration"));
+                    let response = ui.add(egui::DragValue::new(&mut self.cache_seconds)
+                        .clamp_range(0..=600)
+                        .suffix(" s"));
+                    if response.changed() {
+                        cache::set_ttl(Duration::from_secs(self.cache_seconds));
+                    }
+                    if response.drag_released() || response.lost_focus() {
+                        let seconds = self.cache_seconds.to_string();
+                        json_operations::set_json_data(JsonKey::CacheSeconds, &seconds);
+                    }
+                    ui.weak(tr("cache-duration-hint"));
+                });
+
+                ui.label(tr("proxy"));
+                egui::Grid::new("proxy").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("proxy-url"));
+                    let url = ui.add(egui::TextEdit::singleline(&mut self.proxy.url)
+                        .hint_text(tr("proxy-url-hint")));
+                    ui.end_row();
+
+                    ui.label(tr("username"));
+                    let username = ui.text_edit_singleline(&mut self.proxy.username);
+                    ui.end_row();
+
+                    ui.label(tr("password"));
+                    let password = ui.add(egui::TextEdit::singleline(&mut self.proxy.password)
+                        .password(true));
+                    ui.end_row();
+
+                    if url.lost_focus() || username.lost_focus() || password.lost_focus() {
+                        json_operations::set_json_data(JsonKey::Proxy, self.proxy.url.trim());
+                        json_operations::set_json_data(JsonKey::ProxyUser, self.proxy.username.trim());
+                        json_operations::set_json_data(JsonKey::ProxyPassword, &self.proxy.password);
+                        self.apply_proxy();
+                    }
+                });
+                if let Some(ref err) = self.proxy_error {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
 
-                            // Display changed data message
-                            self.save_credential_time = Instant::now();
-                            // Reload on change of data
-                            self.last_update = Instant::now() - five_mins;
+                ui.label(tr("api-endpoints"));
+                let defaults = Endpoints::default();
+                let rows = [
+                    ("AVWX", JsonKey::AvwxUrl, &mut self.endpoints.avwx, defaults.avwx),
+                    ("SimBrief", JsonKey::SimBriefUrl, &mut self.endpoints.simbrief, defaults.simbrief),
+                    ("VATSIM ATIS", JsonKey::VatsimAtisUrl, &mut self.endpoints.vatsim_atis,
+                     defaults.vatsim_atis),
+                    ("VATSIM datafeed", JsonKey::VatsimDatafeedUrl, &mut self.endpoints.vatsim_datafeed,
+                     defaults.vatsim_datafeed),
+                ];
+                let mut changed = false;
+                egui::Grid::new("endpoints").show(ui, |ui| {
+                    for (name, key, url, default) in rows {
+                        ui.label(format!("{name}:"));
+                        let response = ui.add(egui::TextEdit::singleline(url).hint_text(&default));
+                        let reset = ui.button(tr("reset")).clicked();
+                        if reset {
+                            url.clear();
                         }
-                        // Clear both fields, even if no contend
-                        username.clear();
-                        api_key.clear();
+                        if reset || response.lost_focus() {
+                            // The default is stored as empty, so it follows future changes of the default
+                            let stored = if url.trim() == default { "" } else { url.trim() };
+                            json_operations::set_json_data(key, stored);
+                            changed = true;
+                        }
+                        ui.end_row();
+                    }
+                });
+                if changed {
+                    self.apply_endpoints();
+                }
+                if let Some(ref err) = self.endpoints_error {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+
+                ui.horizontal_wrapped(|ui| {
+                    let plan = &mut self.refresh_plan;
+                    let mut minutes = [plan.ground, plan.cruise, plan.arrival]
+                        .map(|interval| interval.as_secs() / 60);
+                    let mut window = plan.arrival_window.num_minutes();
+                    let mut saved = false;
+                    let mut save = |response: egui::Response| {
+                        saved |= response.drag_released() || response.lost_focus();
+                    };
+
+                    ui.label(tr("refresh-every"));
+                    save(ui.add(egui::DragValue::new(&mut minutes[0]).clamp_range(1..=120).suffix(" min")));
+                    ui.label(tr("refresh-before-departure"));
+                    save(ui.add(egui::DragValue::new(&mut minutes[1]).clamp_range(1..=240).suffix(" min")));
+                    ui.label(tr("refresh-in-cruise"));
+                    save(ui.add(egui::DragValue::new(&mut minutes[2]).clamp_range(1..=120).suffix(" min")));
+                    ui.label(tr("refresh-within"));
+                    save(ui.add(egui::DragValue::new(&mut window).clamp_range(0..=240).suffix(" min")));
+                    ui.label(tr("refresh-of-eta"));
+                    save(ui.add(egui::DragValue::new(&mut plan.arrival_range_nm)
+                        .clamp_range(0.0..=500.0)
+                        .suffix(" NM")));
+                    ui.label(tr("refresh-of-arrival"));
+
+                    [plan.ground, plan.cruise, plan.arrival] =
+                        minutes.map(|minutes| Duration::from_secs(minutes * 60));
+                    plan.arrival_window = TimeDelta::minutes(window);
+                    if saved {
+                        let keys = [
+                            JsonKey::RefreshGroundMinutes,
+                            JsonKey::RefreshCruiseMinutes,
+                            JsonKey::RefreshArrivalMinutes,
+                        ];
+                        for (key, minutes) in keys.into_iter().zip(minutes) {
+                            json_operations::set_json_data(key, &minutes.to_string());
+                        }
+                        json_operations::set_json_data(JsonKey::ArrivalWindowMinutes, &window.to_string());
+                        let range = plan.arrival_range_nm.to_string();
+                        json_operations::set_json_data(JsonKey::ArrivalRangeNm, &range);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("retry-attempts"));
+                    let response = ui.add(egui::DragValue::new(&mut self.retry_attempts).clamp_range(1..=10));
+                    if response.changed() {
+                        let attempts = self.retry_attempts;
+                        retry::set_policy(RetryPolicy { attempts, ..RetryPolicy::DEFAULT });
+                    }
+                    if response.drag_released() || response.lost_focus() {
+                        let attempts = self.retry_attempts.to_string();
+                        json_operations::set_json_data(JsonKey::RetryAttempts, &attempts);
                     }
+                    ui.weak(tr("retry-attempts-hint"));
+                });
 
-                    // If a credential was saved in the last five seconds
-                    if self.save_credential_time.elapsed() <= Duration::from_secs(5) {
-                        // Display success message
-                        // Note: the program would panic if not successful,
-                        // so we can assume it worked
-                        ui.colored_label(egui::Color32::GREEN,
-                                         "Success! Data has been saved.");
+                ui.horizontal(|ui| {
+                    ui.label(tr("log-files-keep"));
+                    let files = ui.add(egui::DragValue::new(&mut self.log_config.kept_files).clamp_range(1..=100));
+                    ui.label(tr("log-files-rotate"));
+                    let mut megabytes = self.log_config.max_size / MEGABYTE;
+                    let size = ui.add(egui::DragValue::new(&mut megabytes).clamp_range(1..=500).suffix(" MB"));
+                    self.log_config.max_size = megabytes * MEGABYTE;
+                    if files.drag_released() || files.lost_focus() || size.drag_released() || size.lost_focus() {
+                        let kept_files = self.log_config.kept_files.to_string();
+                        json_operations::set_json_data(JsonKey::LogFilesKept, &kept_files);
+                        json_operations::set_json_data(JsonKey::LogMaxMegabytes, &megabytes.to_string());
                     }
+                    ui.weak(tr("applies-next-start"));
                 });
+
+                let text = tr("auto-print");
+                if ui.checkbox(&mut self.auto_print, text).changed() {
+                    json_operations::set_json_data(JsonKey::AutoPrint, &self.auto_print.to_string());
+                }
+
+                let text = tr("keep-history");
+                if ui.checkbox(&mut self.keep_history, text).changed() {
+                    json_operations::set_json_data(JsonKey::KeepHistory, &self.keep_history.to_string());
+                    self.save_history();
+                }
+
+                let text = tr("desktop-notifications");
+                if ui.checkbox(&mut self.notifications, text).changed() {
+                    json_operations::set_json_data(JsonKey::Notifications, &self.notifications.to_string());
+                }
+
+                let text = tr("check-for-updates");
+                if ui.checkbox(&mut self.check_for_updates, text).changed() {
+                    let enabled = self.check_for_updates.to_string();
+                    json_operations::set_json_data(JsonKey::CheckForUpdates, &enabled);
+                }
+
+                ui.horizontal(|ui| {
+                    let text = tr("discord-presence");
+                    if ui.checkbox(&mut self.discord_presence, text).changed() {
+                        let enabled = self.discord_presence.to_string();
+                        json_operations::set_json_data(JsonKey::DiscordPresence, &enabled);
+                        self.apply_presence();
+                    }
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.discord_app_id)
+                        .hint_text(tr("discord-app-id-hint"))
+                        .desired_width(180.0));
+                    if response.lost_focus() {
+                        json_operations::set_json_data(JsonKey::DiscordAppId, self.discord_app_id.trim());
+                        self.apply_presence();
+                    }
+                });
+            });
+        self.session.set_open("settings", is_expanded(&response));
+
+        let response = egui::CollapsingHeader::new(tr("logs"))
+            .default_open(self.session.is_open("logs"))
+            .show(ui, |ui| show_logs(ui, &mut self.log_level));
+        self.session.set_open("logs", is_expanded(&response));
+
+        ui.add_space(25.0);
+        ui.separator();
+
+        // Attribution required by some data providers
+        let sources: Vec<&str> = Source::ALL
+            .iter()
+            .map(|source| match source {
+                Source::Vatsim => self.network.name(),
+                _ => source.name(),
+            })
+            .collect();
+        let provided_by = tr_args("data-provided-by", &[("sources", &sources.join(", "))]);
+        ui.weak(format!("{provided_by} {}", self.disclaimer.trim()));
+    }
+
+    /// Sends all requests through the configured proxy, or the environment's if none is set.
+    fn apply_proxy(&mut self) {
+        let proxy = (!self.proxy.url.trim().is_empty()).then(|| self.proxy.clone());
+        self.proxy_error = logic::set_proxy(proxy).err().map(|err| {
+            warn!("Invalid proxy {}: {err}", logic::without_userinfo(&self.proxy.url));
+            tr_args("invalid-proxy", &[("error", &err)])
+        });
+    }
+
+    /// Sends the requests to the configured endpoints, the defaults are used for empty ones.
+    fn apply_endpoints(&mut self) {
+        let defaults = Endpoints::default();
+        let or_default = |url: &str, default: String| match url.trim() {
+            "" => default,
+            url => url.to_string(),
+        };
+        let endpoints = Endpoints {
+            avwx: or_default(&self.endpoints.avwx, defaults.avwx),
+            simbrief: or_default(&self.endpoints.simbrief, defaults.simbrief),
+            vatsim_atis: or_default(&self.endpoints.vatsim_atis, defaults.vatsim_atis),
+            vatsim_datafeed: or_default(&self.endpoints.vatsim_datafeed, defaults.vatsim_datafeed),
+        };
+        self.endpoints_error = logic::set_endpoints(endpoints).err().map(|err| {
+            warn!("Invalid API endpoint: {err}");
+            err.to_string()
+        });
+    }
+
+    /// Handles the keyboard shortcuts of the main actions, easier to hit than the buttons while flying:
+    /// R reloads the data, F the flight plan and P toggles the automatic updates.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // The letters are meant for the text field being edited
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let pressed = |key| ctx.input_mut(|input| input.consume_key(egui::Modifiers::NONE, key));
+        if pressed(egui::Key::R) {
+            info!("Reloading data with the keyboard shortcut");
+            self.fetch_weather(ctx);
+        }
+        if pressed(egui::Key::F) && !matches!(self.state, AppState::FetchingFp { .. }) {
+            info!("Reloading the flight plan with the keyboard shortcut");
+            self.fetch_flight_plan(ctx);
+        }
+        if pressed(egui::Key::P) {
+            self.set_paused(!self.paused);
+        }
+    }
+
+    /// Suppresses or allows automatic updates, running requests and errors settle accordingly.
+    fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        match self.state {
+            AppState::Idle if paused => self.state = AppState::Paused,
+            AppState::Paused if !paused => self.state = AppState::Idle,
+            _ => (),
+        }
+    }
+
+    /// Pauses automatic updates during the quiet hours and resumes them afterwards,
+    /// unless the user resumed them early.
+    fn apply_quiet_hours(&mut self, ctx: &egui::Context) {
+        // Unpausing by hand overrides the quiet hours as well
+        if self.quiet_paused && !self.paused {
+            self.quiet_override = true;
+        }
+
+        let quiet = match DailyPeriod::parse(&self.quiet_hours) {
+            Some(period) => {
+                // Check again at the next full minute
+                let second = u64::from(Local::now().second());
+                ctx.request_repaint_after(Duration::from_secs(60 - second.min(59)));
+                period.contains(Local::now().time())
+            }
+            None => false,
+        };
+        if !quiet {
+            self.quiet_override = false;
+        }
+
+        match (quiet && !self.quiet_override, self.quiet_paused) {
+            (true, false) if !self.paused => {
+                info!("Quiet hours started, pausing automatic updates");
+                self.set_paused(true);
+                self.quiet_paused = true;
+            }
+            (false, true) => {
+                info!("Quiet hours ended or overridden, resuming automatic updates");
+                self.quiet_paused = false;
+                if self.paused {
+                    self.set_paused(false);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Updates the state of a part of the running request.
+    fn set_loading(&mut self, item: LoadItem, state: LoadState) {
+        if let Some((_, old)) = self.loading.iter_mut().find(|(loading, _)| *loading == item) {
+            *old = state;
+        }
+    }
+
+    /// The state to settle in when no request is running.
+    fn rest_state(&self) -> AppState {
+        if self.paused { AppState::Paused } else { AppState::Idle }
+    }
+
+    /// Cancels any running request and loads the SimBrief flight plan of the stored username.
+    fn fetch_flight_plan(&mut self, ctx: &egui::Context) {
+        // Read the username now, so the request can't pick up a later one
+        let username = json_operations::get_json_data(JsonKey::Name);
+
+        let id = self.worker(ctx).send(Command::ReloadFlightPlan { username });
+        self.state = AppState::FetchingFp { id };
+        self.loading = vec![(LoadItem::SimBrief, LoadState::Pending)];
+    }
+
+    /// Returns the worker, starting it on first use.
+    fn worker(&mut self, ctx: &egui::Context) -> &mut Worker {
+        self.worker.get_or_insert_with(|| {
+            let ctx = ctx.clone();
+            Worker::start(HttpClient, self.runtime.handle().clone(), move || ctx.request_repaint())
+        })
+    }
+
+    /// Loads the winds aloft of a new flight plan, they are forecasts and not refreshed with the weather.
+    fn fetch_winds(&mut self, ctx: &egui::Context, flight_plan: &FlightPlan) {
+        let (tx, rx) = mpsc::channel();
+        self.winds_update = Some(rx);
+
+        let flight_plan = flight_plan.clone();
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let _ = tx.send(winds::fetch_enroute_winds(&flight_plan).await);
+            ctx.request_repaint();
+        });
+    }
+
+    /// Loads the station data of the current airports that weren't loaded yet, it doesn't change.
+    fn fetch_airport_info(&mut self, ctx: &egui::Context) {
+        if self.airport_info_update.is_some() {
+            return;
+        }
+        let Some((departure, arrival)) = &self.data else { return; };
+        let extras = self.extra_data.iter().filter(|(_, airport)| airport.is_ok()).map(|(icao, _)| icao);
+        let mut missing: Vec<String> = [&departure.icao, &arrival.icao]
+            .into_iter()
+            .chain(extras)
+            .filter(|icao| !self.airport_info.contains_key(*icao))
+            .cloned()
+            .collect();
+        missing.dedup();
+        if missing.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.airport_info_update = Some(rx);
+
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let token = json_operations::get_api_key(Source::Avwx);
+            for icao in missing {
+                let info = airport::fetch_airport_info(&icao, &token).await;
+                let _ = tx.send((icao, info));
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    /// Downloads a file of the OFP into the download folder, copying flight plans to the simulator.
+    fn download(&mut self, ctx: &egui::Context, file: OfpFile) {
+        let (tx, rx) = mpsc::channel();
+        self.download_update = Some(rx);
+
+        let folder = match self.download_dir.trim() {
+            "" => default_download_dir(),
+            folder => PathBuf::from(folder),
+        };
+        let sim_folder = Some(PathBuf::from(self.sim_flight_plan_dir.trim()))
+            .filter(|folder| !folder.as_os_str().is_empty());
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let _ = tx.send(downloads::download(&file, &folder, sim_folder.as_deref()).await);
+            ctx.request_repaint();
+        });
+    }
+
+    /// Checks entered credentials in the background, a field left empty is skipped.
+    fn test_credentials(&mut self, ctx: &egui::Context, username: &str, api_key: &str) {
+        let (tx, rx) = mpsc::channel();
+        self.credential_test_update = Some(rx);
+        self.credential_test.clear();
+
+        let username = username.to_string();
+        let token = (!api_key.is_empty()).then(|| ApiKey {
+            key: api_key.to_string(),
+            auth: json_operations::get_api_auth(Source::Avwx),
+        });
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let simbrief = async {
+                if username.is_empty() {
+                    return None;
+                }
+                let result = logic::fetch_ofp(&HttpClient, &username)
+                    .await
+                    .map(|fp| tr_args("test-simbrief-ok", &[
+                        ("origin", &fp.origin),
+                        ("destination", &fp.destination),
+                    ]));
+                Some(("SimBrief", result))
+            };
+            let avwx = async {
+                let result = logic::check_avwx_key(&token?).await;
+                Some(("AVWX", result.map(|()| tr("test-avwx-ok"))))
+            };
+            let (simbrief, avwx) = tokio::join!(simbrief, avwx);
+
+            let results = [simbrief, avwx]
+                .into_iter()
+                .flatten()
+                .map(|(service, result)| {
+                    (service, result.map_err(|err| {
+                        warn!("Testing the {service} credentials failed: {err}");
+                        err.to_string()
+                    }))
+                })
+                .collect();
+            let _ = tx.send(results);
+            ctx.request_repaint();
+        });
+    }
+
+    /// Cancels any running refresh and loads the weather and ATC of both airports.
+    /// While a flight plan loads, nothing is started, the weather of its airports follows anyway.
+    fn fetch_weather(&mut self, ctx: &egui::Context) {
+        if matches!(self.state, AppState::FetchingFp { .. }) {
+            debug!("Skipping the refresh, the flight plan is still loading");
+            return;
+        }
+        self.start_weather(ctx, false);
+    }
+
+    /// Like `fetch_weather`, optionally printing the briefing once the data arrived.
+    fn start_weather(&mut self, ctx: &egui::Context, print: bool) {
+        // Set times
+        self.local_time = Local::now();
+        self.utc_time = Utc::now();
+        self.last_update = Instant::now();
+        self.fetch_extras(ctx);
+
+        let command = Command::RefreshWeather {
+            departure: self.departure.clone(),
+            arrival: self.arrival.clone(),
+            providers: self.providers(),
+        };
+        // A flight plan that was just loaded stays in the list
+        if !matches!(self.state, AppState::FetchingFp { .. }) {
+            self.loading.clear();
+        }
+        self.loading.extend(LoadItem::WEATHER.map(|item| (item, LoadState::Pending)));
+
+        let id = self.worker(ctx).send(command);
+        self.state = AppState::FetchingWx { id, print };
+    }
+
+    /// The providers of a refresh, read on this thread, which is also the one saving the settings.
+    /// Without a DeepL key in the settings, e.g. in safe mode, nothing is translated.
+    fn providers(&self) -> Providers {
+        let mut providers = Providers::from_user_data();
+        if self.translation_key.trim().is_empty() {
+            providers.translation = None;
+        }
+        providers
+    }
+
+    /// The airports monitored besides departure and arrival: the alternate and those added by the user.
+    fn monitored_airports(&self) -> Vec<String> {
+        let alternate = self.flight_plan.as_ref().and_then(|flight_plan| flight_plan.alternate.clone());
+        let mut airports: Vec<String> = Vec::new();
+        for icao in alternate.into_iter().chain(self.extra_airports.iter().cloned()) {
+            if icao != self.departure && icao != self.arrival && !airports.contains(&icao) {
+                airports.push(icao);
+            }
+        }
+        airports
+    }
+
+    /// Fetches the data of the monitored airports, alongside the refresh of departure and arrival.
+    fn fetch_extras(&mut self, ctx: &egui::Context) {
+        let icaos = self.monitored_airports();
+        self.extra_data.retain(|icao, _| icaos.contains(icao));
+        if icaos.is_empty() {
+            self.extra_request = None;
+            return;
+        }
+
+        let command = Command::RefreshAirports { icaos, providers: self.providers() };
+        self.extra_request = Some(self.worker(ctx).send(command));
+    }
+
+    /// Advances the state: applies the result of a finished request
+    /// or starts the automatic refresh once it is due.
+    fn poll(&mut self, ctx: &egui::Context) {
+        if let Some(ref sim_update) = self.sim_update {
+            // Only the latest state matters
+            let mut stopped = false;
+            loop {
+                match sim_update.try_recv() {
+                    Ok(SimEvent::State(state)) => {
+                        self.aircraft = Some(state);
+                        self.sim_status.clear();
+                    }
+                    Ok(SimEvent::Disconnected(reason)) => {
+                        self.aircraft = None;
+                        self.sim_status = reason;
+                    }
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        stopped = true;
+                        break;
+                    }
+                }
+            }
+            if stopped {
+                self.sim_update = None;
+            } else {
+                // The simulator can't wake up the UI, check for new states
+                ctx.request_repaint_after(Duration::from_secs(1));
+            }
+            self.check_approach(ctx);
+        }
+
+        if let Some(ref update_check) = self.update_check {
+            match update_check.try_recv() {
+                Ok(release) => {
+                    self.update_check = None;
+                    self.new_release = release;
+                }
+                Err(mpsc::TryRecvError::Empty) => (),
+                Err(mpsc::TryRecvError::Disconnected) => self.update_check = None,
+            }
+        }
+
+        if let Some(ref nearest_update) = self.nearest_update {
+            match nearest_update.try_recv() {
+                Ok(result) => {
+                    self.nearest_update = None;
+                    match result {
+                        Ok(icao) => {
+                            info!("Using the nearest airport {icao} as departure");
+                            // Without a flight plan, brief the nearest airport alone
+                            if self.arrival.is_empty() {
+                                self.arrival = icao.clone();
+                            }
+                            self.departure = icao;
+                            self.fetch_weather(ctx);
+                        }
+                        Err(err) => {
+                            warn!("Failed to find the nearest airport: {err}");
+                            let message = tr_args("nearest-airport-failed", &[("error", &err)]);
+                            self.state = AppState::Error(message);
+                        }
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => (),
+                Err(mpsc::TryRecvError::Disconnected) => self.nearest_update = None,
+            }
+        }
+
+        // The winds are loaded next to the other requests, they don't change the state
+        if let Some(ref winds_update) = self.winds_update {
+            match winds_update.try_recv() {
+                Ok(winds) => {
+                    self.winds_update = None;
+                    self.winds = winds.map_err(|err| {
+                        warn!("Failed to load winds aloft: {err}");
+                        err.to_string()
+                    });
+                }
+                Err(mpsc::TryRecvError::Empty) => (),
+                Err(mpsc::TryRecvError::Disconnected) => self.winds_update = None,
+            }
+        }
+
+        if let Some(ref airport_info_update) = self.airport_info_update {
+            let mut finished = false;
+            loop {
+                match airport_info_update.try_recv() {
+                    Ok((icao, info)) => {
+                        let info = info.map_err(|err| {
+                            warn!("Failed to load station info of {icao}: {err}");
+                            err.to_string()
+                        });
+                        self.airport_info.insert(icao, info);
+                    }
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.airport_info_update = None;
+                        finished = true;
+                        break;
+                    }
+                }
+            }
+            // Airports that came up while loading
+            if finished {
+                self.fetch_airport_info(ctx);
+            }
+        }
+
+        if let Some(ref credential_test_update) = self.credential_test_update {
+            match credential_test_update.try_recv() {
+                Ok(results) => {
+                    self.credential_test_update = None;
+                    self.credential_test = results;
+                }
+                Err(mpsc::TryRecvError::Empty) => (),
+                Err(mpsc::TryRecvError::Disconnected) => self.credential_test_update = None,
+            }
+        }
+
+        if let Some(ref download_update) = self.download_update {
+            match download_update.try_recv() {
+                Ok(download) => {
+                    self.download_update = None;
+                    self.download_status = match download {
+                        Ok(Download { path, copied_to: Some(copy) }) => Ok(tr_args("saved-and-copied", &[
+                            ("path", &path.display()),
+                            ("copy", &copy.display()),
+                        ])),
+                        Ok(Download { path, copied_to: None }) =>
+                            Ok(tr_args("saved", &[("path", &path.display())])),
+                        Err(err) => {
+                            warn!("Failed to download OFP file: {err}");
+                            Err(err.to_string())
+                        }
+                    };
+                }
+                Err(mpsc::TryRecvError::Empty) => (),
+                Err(mpsc::TryRecvError::Disconnected) => self.download_update = None,
+            }
+        }
+
+        // Results of requests replaced by a newer one are outdated
+        let current = match self.state {
+            AppState::FetchingFp { id } | AppState::FetchingWx { id, .. } => Some(id),
+            AppState::Idle | AppState::Error(_) | AppState::Paused => None,
+        };
+        let mut finished = None;
+        let mut extras = None;
+        while let Some(event) = self.worker.as_ref().and_then(Worker::try_recv) {
+            match event {
+                // The monitored airports are refreshed alongside, apart from the state
+                WorkerEvent::Airports { id, icaos, result } if Some(id) == self.extra_request =>
+                    extras = Some((icaos, result)),
+                WorkerEvent::Lost { id } if Some(id) == self.extra_request => self.extra_request = None,
+                _ if Some(event.id()) != current => (),
+                WorkerEvent::Progress { item, state, .. } => self.set_loading(item, state),
+                event => finished = Some(event),
+            }
+        }
+
+        if let Some((icaos, result)) = extras {
+            self.extra_request = None;
+            let airports: Vec<_> = match result {
+                Ok(airports) => airports
+                    .into_iter()
+                    .map(|airport| airport.map_err(|err| err.to_string()))
+                    .collect(),
+                Err(err) => icaos.iter().map(|_| Err(err.to_string())).collect(),
+            };
+            for (icao, airport) in icaos.into_iter().zip(airports) {
+                if let Err(ref err) = airport {
+                    warn!("Failed to get data of {icao}: {err}");
+                }
+                self.extra_data.insert(icao, airport);
+            }
+            self.fetch_airport_info(ctx);
+        }
+
+        match finished {
+            Some(WorkerEvent::FlightPlan { result: Ok(flight_plan), .. }) => {
+                // Update received, apply it and load the weather, regardless if paused
+                self.set_loading(LoadItem::SimBrief, LoadState::Done);
+                self.departure = flight_plan.origin.clone();
+                self.arrival = flight_plan.destination.clone();
+                // Reloading the same flight plan shouldn't print it again
+                let print = self.auto_print && self.flight_plan.as_ref() != Some(&flight_plan);
+                self.fetch_winds(ctx, &flight_plan);
+                self.download_status = Ok(String::new());
+                self.flight_plan = Some(flight_plan);
+                self.start_weather(ctx, print);
+            }
+            Some(WorkerEvent::FlightPlan { result: Err(err), .. }) => {
+                warn!("Failed to load flight plan: {err}");
+                self.state = AppState::Error(tr_args("flight-plan-failed", &[("error", &err)]));
+            }
+            Some(WorkerEvent::Weather { result: Ok(new_data), .. }) => {
+                let print = matches!(self.state, AppState::FetchingWx { print: true, .. });
+                self.apply_data(new_data);
+                self.offline = false;
+                self.fetch_airport_info(ctx);
+                if print {
+                    self.print_briefing();
+                }
+                self.state = self.rest_state();
+            }
+            Some(WorkerEvent::Weather { result: Err(err), .. }) => {
+                // Keep showing the old data, it's better than nothing
+                warn!("Failed to update data: {err}");
+                self.offline = retry::is_transient(&err);
+                self.notify(&tr("refresh-failed"), &err.to_string());
+                self.state = AppState::Error(tr_args("update-failed", &[("error", &err)]));
+            }
+            // The request died without a result, stop waiting for it
+            Some(WorkerEvent::Lost { .. }) => self.state = self.rest_state(),
+            // Handled as they arrive, if no update received yet, nothing to do
+            Some(WorkerEvent::Progress { .. } | WorkerEvent::Airports { .. }) | None => (),
+        }
+
+        match &self.state {
+            // Nothing to refresh without a flight
+            AppState::Idle | AppState::Error(_) if !self.paused && !self.departure.is_empty() => {
+                let elapsed = self.last_update.elapsed();
+                let phase = self.refresh_phase();
+                let interval = self.refresh_plan.interval(phase);
+                let budget = quota::budget();
+                // One more METAR for each monitored airport
+                let calls = quota::CALLS_PER_REFRESH + self.monitored_airports().len() as u32;
+                if elapsed >= interval && !budget.allows(calls) {
+                    // Try again after the interval, the quota may be reset by then
+                    self.last_update = Instant::now();
+                    warn!("Skipped the automatic refresh, only {} AVWX calls left today", budget.remaining);
+                    let message = tr_args("refresh-skipped", &[("remaining", &budget.remaining)]);
+                    self.state = AppState::Error(message);
+                } else if elapsed >= interval {
+                    info!("Automatic refresh {}", phase.name());
+                    self.fetch_weather(ctx);
+                } else {
+                    // Wake up for the refresh, even without user input,
+                    // and check for a new phase with a shorter interval now and then
+                    ctx.request_repaint_after((interval - elapsed).min(Duration::from_secs(60)));
+                }
+            }
+            AppState::Idle | AppState::Error(_) | AppState::Paused
+            | AppState::FetchingFp { .. } | AppState::FetchingWx { .. } => (),
+        }
+    }
+
+    /// Carries out the actions picked in the tray menu.
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    fn handle_tray(&mut self, ctx: &egui::Context) {
+        let Some(tray) = &self.tray else { return; };
+        let actions: Vec<tray::TrayAction> = tray.actions().collect();
+
+        for action in actions {
+            match action {
+                tray::TrayAction::Show => {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                }
+                tray::TrayAction::Refresh => self.fetch_weather(ctx),
+                tray::TrayAction::Quit => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+            }
+        }
+    }
+
+    /// Shows the flight timer with a button for its next event, and the briefing export.
+    fn show_flight_timer(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("flight-timer"));
+            if let Some(event) = self.flight_timer.next_event() {
+                let label = tr(match event {
+                    Event::OffBlock => "timer-off-block",
+                    Event::Takeoff => "timer-takeoff",
+                    Event::Landing => "timer-landing",
+                    Event::OnBlock => "timer-on-block",
+                });
+                if ui.button(label).clicked() {
+                    info!("Flight timer: {}", event.name());
+                    self.flight_timer.record(Utc::now());
+                }
+            }
+            if self.flight_timer.off_block.is_some() && ui.small_button(tr("reset")).clicked() {
+                self.flight_timer = FlightTimer::default();
+            }
+
+            let now = Utc::now();
+            let time = |time: Option<chrono::TimeDelta>| time
+                .map(format_duration)
+                .unwrap_or_else(|| String::from("-:--"));
+            ui.label(tr_args("block-flight-time", &[
+                ("block", &time(self.flight_timer.block_time(now))),
+                ("flight", &time(self.flight_timer.flight_time(now))),
+            ]));
+
+            if self.data.is_some() && ui.small_button(tr("print-briefing")).clicked() {
+                self.print_briefing();
+            }
+            if self.data.is_some() && ui.small_button(tr("export-briefing")).clicked() {
+                self.export_briefing();
+            }
+            match &self.export_status {
+                Ok(message) if message.is_empty() => (),
+                Ok(message) => { ui.weak(message); }
+                Err(err) => {
+                    ui.colored_label(egui::Color32::RED, tr_args("export-failed", &[("error", err)]));
+                }
+            }
+        });
+    }
+
+    /// Counts the start as successful once the app ran for `STARTUP_GRACE`.
+    fn confirm_startup(&mut self, ctx: &egui::Context) {
+        if self.startup_confirmed {
+            return;
+        }
+        match STARTUP_GRACE.checked_sub(self.started.elapsed()) {
+            Some(remaining) => ctx.request_repaint_after(remaining),
+            None => {
+                safe_mode::startup_succeeded();
+                self.startup_confirmed = true;
+            }
+        }
+    }
+
+    /// Shows the internal error banner with a button to dismiss it.
+    fn show_error_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(ref message) = self.error_banner else {
+            return;
+        };
+        let mut dismissed = false;
+        egui::Frame::none()
+            .fill(egui::Color32::from_rgb(140, 30, 30))
+            .inner_margin(8.0)
+            .rounding(4.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(egui::RichText::new(message).strong().color(egui::Color32::WHITE));
+                    dismissed = ui.button(tr("dismiss")).clicked();
+                });
+            });
+        if dismissed {
+            self.error_banner = None;
+        }
+        ui.add_space(10.0);
+    }
+
+    /// Asks GitHub for a newer release in the background, shown by [`Self::show_update_banner`].
+    fn check_for_update(&mut self, ctx: &egui::Context) {
+        let (tx, rx) = mpsc::channel();
+        self.update_check = Some(rx);
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let release = update::check()
+                .await
+                .unwrap_or_else(|err| {
+                    // Not worth bothering the user, the next start tries again
+                    warn!("Failed to check for a new release: {err}");
+                    None
+                });
+            let _ = tx.send(release);
+            ctx.request_repaint();
+        });
+    }
+
+    /// Shows a banner with a link to a newer release, until dismissed.
+    fn show_update_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(ref release) = self.new_release else {
+            return;
+        };
+        let mut dismissed = false;
+        egui::Frame::none()
+            .fill(egui::Color32::from_rgb(30, 70, 130))
+            .inner_margin(8.0)
+            .rounding(4.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal_wrapped(|ui| {
+                    let text = tr_args("new-version", &[
+                        ("version", &release.version),
+                        ("current", &update::CURRENT_VERSION),
+                    ]);
+                    ui.label(egui::RichText::new(text).strong().color(egui::Color32::WHITE));
+                    if ui.button(tr("open-release")).clicked() {
+                        log_open_error(platform::open_url(&release.url), &release.url);
+                    }
+                    dismissed = ui.button(tr("dismiss")).clicked();
+                });
+            });
+        if dismissed {
+            self.new_release = None;
+        }
+        ui.add_space(10.0);
+    }
+
+    /// Explains safe mode and offers to reset the settings or to continue normally.
+    fn show_safe_mode(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let Some(ref notice) = self.safe_mode else { return; };
+
+        ui.colored_label(egui::Color32::GOLD, notice);
+        ui.horizontal(|ui| {
+            if ui.button(tr("reset-settings")).clicked() {
+                self.safe_mode = Some(match json_operations::reset_user_data() {
+                    Ok(backup) => tr_args("settings-reset", &[("backup", &backup.display())]),
+                    Err(err) => {
+                        warn!("Failed to reset settings: {err}");
+                        tr_args("settings-reset-failed", &[("error", &err)])
+                    }
+                });
+            }
+            if ui.button(tr("continue")).clicked() {
+                self.safe_mode = None;
+                self.paused = false;
+                self.fetch_flight_plan(ctx);
+            }
+            if ui.small_button(tr("open-config-folder")).clicked() {
+                log_open_error(platform::open_config_folder(), "config folder");
+            }
+        });
+        ui.add_space(25.0);
+    }
+
+    /// Prints the briefing of the current flight plan and data.
+    fn print_briefing(&self) {
+        let Some((departure, arrival)) = &self.data else { return; };
+        let text = briefing::briefing(
+            self.flight_plan.as_ref(),
+            departure,
+            arrival,
+            Some(&self.flight_timer),
+            &self.disclaimer,
+        );
+        if let Err(err) = platform::print_text(&text) {
+            warn!("Failed to print briefing: {err}");
+        }
+    }
+
+    /// Writes the briefing of the current flight plan and data to a file, e.g. to attach it to a PIREP.
+    fn export_briefing(&mut self) {
+        let Some((departure, arrival)) = &self.data else { return; };
+        let text = briefing::formatted_briefing(
+            self.briefing_format,
+            self.flight_plan.as_ref(),
+            departure,
+            arrival,
+            Some(&self.flight_timer),
+            &self.disclaimer,
+        );
+        let folder = match (self.briefing_dir.trim(), self.download_dir.trim()) {
+            ("", "") => default_download_dir(),
+            ("", folder) | (folder, _) => PathBuf::from(folder),
+        };
+        let file_name = briefing::file_name(&departure.icao, &arrival.icao, self.briefing_format, Utc::now());
+        self.export_status = match briefing::export(&text, &folder, &file_name) {
+            Ok(path) => {
+                info!("Exported briefing to {}", path.display());
+                Ok(tr_args("saved", &[("path", &path.display())]))
+            }
+            Err(err) => {
+                warn!("Failed to export briefing: {err}");
+                Err(err.to_string())
+            }
+        };
+    }
+
+    /// Shows newly received data, noting what changed since the last refresh.
+    fn apply_data(&mut self, new_data: (AirportData, AirportData)) {
+        self.atc_changes = atc_changes(self.data.as_ref(), &new_data);
+        for change in &self.atc_changes {
+            match change {
+                StationChange::LoggedOn(callsign) => info!("{callsign} logged on"),
+                StationChange::LoggedOff(callsign) => info!("{callsign} logged off"),
+            }
+        }
+        self.new_atis = new_atis(&mut self.atis_letters, &new_data);
+        for airport in [&new_data.0, &new_data.1] {
+            // An airport whose METAR failed has nothing to record
+            if !airport.observation.raw.is_empty() {
+                self.metar_history.record(&airport.icao, airport.observation.clone());
+            }
+        }
+        self.save_history();
+        for airport in [&new_data.0, &new_data.1] {
+            if let (Some(letter), Some(previous)) =
+                (airport.atis_letter, self.new_atis.get(&airport.icao)) {
+                self.notify(&tr_args("new-atis-at", &[("icao", &airport.icao)]),
+                            &tr_args("new-atis-information", &[("letter", &letter), ("previous", previous)]));
+            }
+        }
+        if let Some(rules) = worsened_flight_rules(self.data.as_ref(), &new_data) {
+            self.notify(&tr_args("flight-rules-worsened", &[("icao", &new_data.1.icao), ("rules", &rules)]),
+                        &tr("check-destination-weather"));
+        }
+        if new_convective_sigmet(self.data.as_ref(), &new_data) {
+            self.notify(&tr_args("thunderstorm-sigmet", &[("icao", &new_data.1.icao)]),
+                        &tr("check-destination-hazards"));
+        }
+        if self.recent_airports.first() != Some(&new_data.0.icao) {
+            json_operations::add_recent_airports(&[&new_data.0.icao, &new_data.1.icao]);
+            self.recent_airports = json_operations::get_recent_airports();
+        }
+        self.data = Some(new_data);
+        self.data_time = Some(Utc::now());
+        self.update_presence();
+    }
+
+    /// Shows a tab for each airport, departure, arrival and the monitored ones,
+    /// with a field to monitor another one, and the weather of the selected airport below.
+    fn show_airport_tabs(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        // Close to the destination its weather matters most, switch to it once
+        let phase = self.flight_phase();
+        let arrival_first = phase.is_some_and(|phase| phase.arrival_first());
+        if arrival_first != self.arrival_first {
+            if let Some(phase) = phase {
+                info!("Showing the {} first, the flight phase is {}",
+                      if arrival_first { "arrival" } else { "departure" }, phase.name());
+            }
+            self.arrival_first = arrival_first;
+            self.airport_tab = if arrival_first { AirportTab::Arrival } else { AirportTab::Departure };
+        }
+
+        let monitored = self.monitored_airports();
+        if let AirportTab::Other(ref icao) = self.airport_tab {
+            if !monitored.contains(icao) {
+                self.airport_tab = AirportTab::Departure;
+            }
+        }
+        let Some((departure_val, arrival_val)) = self.data.as_ref() else { return; };
+        let alternate = self.flight_plan.as_ref().and_then(|flight_plan| flight_plan.alternate.as_ref());
+
+        let (mut added, mut removed) = (false, None);
+        ui.horizontal_wrapped(|ui| {
+            let tab = &mut self.airport_tab;
+            let departure = format!("{} {}", tr("departure"), departure_val.icao);
+            ui.selectable_value(tab, AirportTab::Departure, departure);
+            ui.selectable_value(tab, AirportTab::Arrival, format!("{} {}", tr("arrival"), arrival_val.icao));
+            for icao in &monitored {
+                let label = match alternate {
+                    Some(alternate) if alternate == icao => format!("{} {icao}", tr("alternate")),
+                    _ => icao.clone(),
+                };
+                ui.selectable_value(tab, AirportTab::Other(icao.clone()), label);
+                let extra = self.extra_airports.contains(icao);
+                if extra && ui.small_button("x").on_hover_text(tr("stop-monitoring")).clicked() {
+                    removed = Some(icao.clone());
+                }
+            }
+
+            ui.separator();
+            let response = ui.add(egui::TextEdit::singleline(&mut self.extra_entry)
+                .hint_text("ICAO")
+                .char_limit(4)
+                .desired_width(50.0));
+            added = response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            added |= ui.small_button(tr("monitor")).on_hover_text(tr("monitor-hint")).clicked();
+        });
+        if let Some(ref err) = self.extra_entry_error {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+
+        ui.add_space(10.0);
+        match self.airport_tab {
+            AirportTab::Departure => self.show_airport(ui, &tr("departure"), departure_val, false),
+            AirportTab::Arrival => self.show_airport(ui, &tr("arrival"), arrival_val, true),
+            AirportTab::Other(ref icao) => match self.extra_data.get(icao) {
+                Some(Ok(airport)) => {
+                    let title = if alternate == Some(icao) { tr("alternate") } else { tr("monitored") };
+                    self.show_airport(ui, &title, airport, false);
+                }
+                Some(Err(err)) => {
+                    let text = tr_args("airport-data-failed", &[("icao", icao), ("error", err)]);
+                    ui.colored_label(egui::Color32::RED, text);
+                }
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.label(tr_args("loading-airport", &[("icao", icao)]));
+                        ui.spinner();
+                    });
+                }
+            },
+        }
+
+        if let Some(icao) = removed {
+            self.extra_airports.retain(|extra| *extra != icao);
+            json_operations::set_extra_airports(&self.extra_airports);
+            self.extra_data.remove(&icao);
+        }
+        if added {
+            let entry = self.extra_entry.trim().to_string();
+            match normalize_icao(&entry) {
+                Some(icao) => {
+                    self.extra_entry.clear();
+                    self.extra_entry_error = None;
+                    if !self.extra_airports.contains(&icao) {
+                        self.extra_airports.push(icao.clone());
+                        json_operations::set_extra_airports(&self.extra_airports);
+                    }
+                    self.airport_tab = AirportTab::Other(icao);
+                    self.fetch_extras(ctx);
+                }
+                None => self.extra_entry_error = Some(tr_args("invalid-icao", &[("entry", &entry)])),
+            }
+        }
+    }
+
+    /// Shows fields to brief airports typed in by the user, without a SimBrief flight plan,
+    /// and a button to swap the airports for the return leg. A field left empty keeps the current airport.
+    fn show_airport_entry(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let (mut submitted, mut swapped) = (false, false);
+        let mut starred = None;
+        ui.horizontal(|ui| {
+            for (label, entry, current) in [
+                ("departure-entry", &mut self.departure_entry, &self.departure),
+                ("arrival-entry", &mut self.arrival_entry, &self.arrival),
+            ] {
+                ui.label(tr(label));
+                let response = ui.add(egui::TextEdit::singleline(entry)
+                    .hint_text(current.as_str())
+                    .char_limit(4)
+                    .desired_width(50.0));
+                submitted |= response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+
+                ui.menu_button("...", |ui| {
+                    let recent = self.recent_airports
+                        .iter()
+                        .filter(|icao| !self.favorite_airports.contains(icao));
+                    for (heading, airports) in [
+                        (tr("favorites"), self.favorite_airports.iter().collect::<Vec<_>>()),
+                        (tr("recent"), recent.collect()),
+                    ] {
+                        if airports.is_empty() {
+                            continue;
+                        }
+                        ui.weak(heading);
+                        for icao in airports {
+                            if ui.button(icao).clicked() {
+                                entry.clone_from(icao);
+                                submitted = true;
+                                ui.close_menu();
+                            }
+                        }
+                        ui.separator();
+                    }
+                    if !current.is_empty() {
+                        let favorite = self.favorite_airports.contains(current);
+                        let key = if favorite { "remove-favorite" } else { "add-favorite" };
+                        let text = tr_args(key, &[("icao", current)]);
+                        if ui.button(text).clicked() {
+                            starred = Some((current.clone(), !favorite));
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+            submitted |= ui.button(tr("brief")).clicked();
+            let both = !self.departure.is_empty() && !self.arrival.is_empty();
+            swapped = both && ui.button(tr("swap")).clicked();
+            if let Some(ref err) = self.airport_entry_error {
+                ui.colored_label(egui::Color32::RED, err);
+            }
+        });
+        if let Some((icao, favorite)) = starred {
+            json_operations::set_favorite_airport(&icao, favorite);
+            self.favorite_airports = json_operations::get_favorite_airports();
+        }
+        if swapped {
+            // The return leg, the SimBrief flight plan is for the other direction
+            std::mem::swap(&mut self.departure, &mut self.arrival);
+            info!("Swapped airports, briefing {} - {}", self.departure, self.arrival);
+            self.flight_plan = None;
+            self.airport_entry_error = None;
+            self.fetch_weather(ctx);
+        }
+        if !submitted {
+            return;
+        }
+
+        let airport = |entry: &str, current: &str| match entry.trim() {
+            "" if current.is_empty() => Err(tr("enter-both-airports")),
+            "" => Ok(current.to_string()),
+            entry => normalize_icao(entry).ok_or_else(|| tr_args("invalid-icao", &[("entry", &entry)])),
+        };
+        let airports = airport(&self.departure_entry, &self.departure)
+            .and_then(|departure| Ok((departure, airport(&self.arrival_entry, &self.arrival)?)));
+        match airports {
+            Ok((departure, arrival)) => {
+                info!("Briefing {departure} - {arrival} entered by the user");
+                self.airport_entry_error = None;
+                self.departure_entry.clear();
+                self.arrival_entry.clear();
+                // The SimBrief flight plan is for other airports now
+                if (departure.as_str(), arrival.as_str()) != (self.departure.as_str(), self.arrival.as_str()) {
+                    self.flight_plan = None;
+                }
+                self.departure = departure;
+                self.arrival = arrival;
+                self.fetch_weather(ctx);
+            }
+            Err(err) => self.airport_entry_error = Some(err),
+        }
+    }
+
+    /// Shows the weather, ATIS and ATC of an airport, with its TAF for the arrival.
+    fn show_airport(&self, ui: &mut egui::Ui, title: &str, airport: &AirportData, with_taf: bool) {
+        // What to expect on arrival, next to the current flight rules
+        let forecast = match (with_taf, &airport.taf, self.flight_plan.as_ref().and_then(|plan| plan.eta)) {
+            (true, Some(taf), Some(eta)) => taf::forecast_at(taf, eta),
+            _ => None,
+        };
+        // The weather expected at the ETA may be wetter than now
+        let icing = [Some(&airport.weather), forecast.as_ref().map(|forecast| &forecast.weather)]
+            .into_iter()
+            .flatten()
+            .filter_map(|weather| icing::risk(airport.temperature, airport.dewpoint, weather))
+            .max();
+        let info = self.airport_info.get(&airport.icao);
+        show_airport_heading(ui, title, airport, info, forecast.as_ref(), icing);
+        show_airport_errors(ui, airport);
+        show_metar_age(ui, airport, self.metar_age_warning, self.metar_age_alert);
+        if let Some(trend) = qnh_trend(&self.metar_history, &airport.icao) {
+            ui.label(tr_args("qnh-trend", &[("trend", &trend)])).on_hover_text(tr("qnh-trend-hint"));
+        }
+        show_density_altitude(ui, airport, info);
+        show_new_atis(ui, airport, self.new_atis.get(&airport.icao));
+        show_coverage(ui, airport);
+        show_runways_in_use(ui, airport);
+        show_copy_buttons(ui, airport);
+        show_raw_text(ui, &airport.summary);
+        show_trends(ui, &self.metar_history, &airport.icao);
+        show_runway_winds(ui, airport, self.crosswind_limit);
+        if with_taf {
+            let eta = self.flight_plan.as_ref().and_then(|flight_plan| flight_plan.eta);
+            show_taf(ui, airport, eta);
+        }
+        show_hazards(ui, airport);
+        show_stations(ui, airport);
+        show_airport_info(ui, &airport.icao, info);
+    }
+
+    /// Switches between the full view and the compact overlay, a small window without title bar.
+    fn set_compact(&mut self, ctx: &egui::Context, compact: bool) {
+        self.session.compact = compact;
+        let size = if compact { COMPACT_SIZE } else { self.session.window_size.unwrap_or(DEFAULT_SIZE) };
+        ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(!compact));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size.into()));
+    }
+
+    /// Shows only the flight rules, wind, QNH and ATIS letter of both airports,
+    /// small enough to keep on top of the simulator.
+    fn show_compact(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        // Without a title bar, the window is moved by dragging it anywhere
+        let background = ui.interact(ui.max_rect(), ui.id().with("drag"), egui::Sense::drag());
+        if background.drag_started() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+        }
+
+        match self.data.as_ref() {
+            Some((departure_val, arrival_val)) => {
+                egui::Grid::new("compact").num_columns(5).show(ui, |ui| {
+                    for airport in [departure_val, arrival_val] {
+                        show_compact_airport(ui, airport, qnh_trend(&self.metar_history, &airport.icao));
+                        ui.end_row();
+                    }
+                });
+            }
+            None => { ui.weak(tr("no-data-yet")); }
+        }
+
+        ui.horizontal(|ui| {
+            if ui.small_button(tr("full-view")).clicked() {
+                self.set_compact(ctx, false);
+            }
+            self.show_pin(ui, ctx);
+            if matches!(self.state, AppState::FetchingWx { .. } | AppState::FetchingFp { .. }) {
+                ui.spinner();
+            } else if ui.small_button(tr("reload")).clicked() {
+                self.fetch_weather(ctx);
+            }
+        });
+    }
+
+    /// Shows the button keeping the window above all others.
+    fn show_pin(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let pin = ui.toggle_value(&mut self.session.always_on_top, tr("pin-on-top"))
+            .on_hover_text(tr("pin-on-top-hint"));
+        if pin.changed() {
+            let level = window_level(self.session.always_on_top);
+            ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+        }
+    }
+
+    /// Records the size and position of the window, to open it the same way next time.
+    fn record_window(&mut self, ctx: &egui::Context) {
+        // The geometry of the full view is restored when leaving the compact one
+        if self.session.compact {
+            return;
+        }
+        let (minimized, maximized, inner_rect, outer_rect) = ctx.input(|input| {
+            let viewport = input.viewport();
+            (viewport.minimized, viewport.maximized, viewport.inner_rect, viewport.outer_rect)
+        });
+        // A minimized window is moved off screen on Windows
+        if minimized == Some(true) {
+            return;
+        }
+        self.session.maximized = maximized == Some(true);
+        // Keep the size and position to return to from a maximized window
+        if self.session.maximized {
+            return;
+        }
+        if let Some(rect) = inner_rect {
+            self.session.window_size = Some([rect.width(), rect.height()]);
+        }
+        if let Some(rect) = outer_rect {
+            self.session.window_position = Some([rect.min.x, rect.min.y]);
+        }
+    }
+
+    /// The time until the next automatic refresh, `None` if there is none coming.
+    fn next_refresh(&self) -> Option<Duration> {
+        let waiting = matches!(self.state, AppState::Idle | AppState::Error(_));
+        if !waiting || self.paused || self.departure.is_empty() {
+            return None;
+        }
+        let interval = self.refresh_plan.interval(self.refresh_phase());
+        Some(interval.saturating_sub(self.last_update.elapsed()))
+    }
+
+    /// The part of the flight deciding how often the weather is refreshed.
+    fn refresh_phase(&self) -> RefreshPhase {
+        let flight_plan = self.flight_plan.as_ref();
+        let destination = self.data.as_ref().and_then(|(_, arrival)| arrival.position);
+        let aircraft = self.aircraft.map(|aircraft| {
+            (aircraft.on_ground, destination.map(|position| aircraft.position.distance_nm(position)))
+        });
+        self.refresh_plan.phase(
+            Utc::now(),
+            flight_plan.and_then(|flight_plan| flight_plan.etd),
+            flight_plan.and_then(|flight_plan| flight_plan.eta),
+            aircraft,
+        )
+    }
+
+    /// The part of the flight the aircraft in the simulator is in, `None` without a simulator.
+    fn flight_phase(&self) -> Option<FlightPhase> {
+        let destination = self.data.as_ref().and_then(|(_, arrival)| arrival.position);
+        self.aircraft.map(|aircraft| sim::phase(&aircraft, destination))
+    }
+
+    /// Starts or stops reading the aircraft from the simulator set in the settings.
+    fn apply_simulator(&mut self) {
+        // Dropping the old receiver ends its thread
+        self.sim_update = self.simulator.map(sim::start);
+        self.aircraft = None;
+        self.sim_status.clear();
+    }
+
+    /// Shows where the aircraft is, with a button to brief the nearest airport as departure.
+    fn show_aircraft(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, simulator: Simulator) {
+        ui.horizontal(|ui| {
+            let Some(aircraft) = self.aircraft else {
+                let status = match self.sim_status.as_str() {
+                    "" => tr("connecting"),
+                    status => status.to_string(),
+                };
+                ui.weak(format!("{}: {status}", simulator.name()));
+                return;
+            };
+
+            let destination = self.data.as_ref().and_then(|(_, arrival)| {
+                Some((arrival.icao.as_str(), arrival.position?))
+            });
+            let phase = self.flight_phase().map(|phase| tr(match phase {
+                FlightPhase::Departure => "flight-phase-departure",
+                FlightPhase::Enroute => "flight-phase-enroute",
+                FlightPhase::Approach => "flight-phase-approach",
+                FlightPhase::Arrived => "flight-phase-arrived",
+            })).unwrap_or_default();
+            let mut text = format!("{}: {phase}, {:.0} ft, {:.0} kt", simulator.name(), aircraft.altitude_ft,
+                                   aircraft.ground_speed_kt);
+            if let Some((icao, position)) = destination {
+                let distance = format!("{:.0}", aircraft.position.distance_nm(position));
+                text.push_str(&tr_args("distance-to", &[("distance", &distance), ("icao", &icao)]));
+            }
+            ui.label(text);
+
+            if self.nearest_update.is_some() {
+                ui.spinner();
+            } else if ui.small_button(tr("use-nearest-airport")).clicked() {
+                let (tx, rx) = mpsc::channel();
+                self.nearest_update = Some(rx);
+                let ctx = ctx.clone();
+                self.runtime.spawn(async move {
+                    let token = json_operations::get_api_key(Source::Avwx);
+                    let _ = tx.send(airport::nearest_airport(aircraft.position, &token).await);
+                    ctx.request_repaint();
+                });
+            }
+        });
+    }
+
+    /// Refreshes the weather once the aircraft nears the top of descent or the destination,
+    /// so the descent and approach are briefed with the latest METAR and ATIS
+    /// even when automatic updates are suppressed in cruise.
+    fn check_approach(&mut self, ctx: &egui::Context) {
+        let (Some(aircraft), Some((_, arrival))) = (self.aircraft, &self.data) else { return; };
+        let idle = matches!(self.state, AppState::Idle | AppState::Error(_) | AppState::Paused);
+        if !idle || self.approach_refreshed.as_ref() == Some(&arrival.icao) {
+            return;
+        }
+        let top_of_descent = self.flight_plan.as_ref().and_then(|flight_plan| flight_plan.top_of_descent);
+        let descending = top_of_descent.is_some_and(|position| sim::nearing_descent(&aircraft, position));
+        let approaching = arrival.position.is_some_and(|position| sim::approaching(&aircraft, position));
+        if descending || approaching {
+            info!("Nearing the descent into {}, refreshing the weather", arrival.icao);
+            self.approach_refreshed = Some(arrival.icao.clone());
+            self.fetch_weather(ctx);
+        }
+    }
+
+    /// Starts or stops publishing the Discord presence as set in the settings.
+    fn apply_presence(&mut self) {
+        let application_id = self.discord_app_id.trim();
+        // Dropping the old sender ends its thread and clears the presence
+        self.presence = (self.discord_presence && !application_id.is_empty())
+            .then(|| presence::start(application_id));
+        self.update_presence();
+    }
+
+    /// Shows the current flight in the Discord presence, if published.
+    fn update_presence(&self) {
+        if let (Some(presence), Some((departure, arrival))) = (&self.presence, &self.data) {
+            let _ = presence.send(Some(Activity::for_airports(departure, arrival)));
+        }
+    }
+
+    /// Saves the METAR history if it is kept between starts.
+    fn save_history(&self) {
+        if !self.keep_history {
+            return;
+        }
+        if let Err(err) = self.metar_history.save() {
+            warn!("Failed to save METAR history: {err}");
+        }
+    }
+
+    /// Shows a desktop notification if enabled.
+    /// Runs on the blocking pool, because some notification servers are slow to answer.
+    fn notify(&self, summary: &str, body: &str) {
+        if !self.notifications {
+            return;
+        }
+
+        let summary = summary.to_string();
+        let body = body.to_string();
+        self.runtime.spawn_blocking(move || {
+            if let Err(err) = platform::notify(&summary, &body) {
+                warn!("Failed to show notification: {err}");
+            }
+        });
+    }
+
+    /// Tells that the last run crashed and offers its report, once after the crash.
+    fn show_crash_notice(&mut self, ctx: &egui::Context) {
+        if !self.crash_report_pending {
+            return;
+        }
+
+        let mut open = true;
+        let mut closed = false;
+        egui::Window::new(tr("crash-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(tr_args("crash-text", &[("file", &ERROR_FILE_NAME)]));
+                ui.horizontal(|ui| {
+                    if ui.button(tr("open-crash-report")).clicked() {
+                        log_open_error(platform::open_crash_log(), ERROR_FILE_NAME);
+                    }
+                    if ui.button(tr("report-problem")).clicked() {
+                        self.problem_report.get_or_insert_with(String::new);
+                        closed = true;
+                    }
+                    closed |= ui.button(tr("close")).clicked();
+                });
+            });
+
+        if !open || closed {
+            self.crash_report_pending = false;
+        }
+    }
+
+    /// Shows the "Report a problem" dialog while it is open.
+    /// Creating the report opens a prefilled GitHub issue and the folder with the zip to attach.
+    fn show_problem_report(&mut self, ctx: &egui::Context) {
+        let Some(description) = self.problem_report.as_mut() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut created = false;
+        egui::Window::new(tr("report-problem"))
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(tr("report-question"));
+                ui.text_edit_multiline(description);
+                ui.weak(tr("report-contents"));
+
+                if ui.button(tr("create-report")).clicked() {
+                    match report::create_report(description) {
+                        Ok(path) => {
+                            let url = report::issue_url(description, &path);
+                            log_open_error(platform::open_url(&url), &url);
+                            log_open_error(platform::open_log_folder(), "log folder");
+                            created = true;
+                        }
+                        Err(err) => {
+                            warn!("Failed to create problem report: {err}");
+                            self.problem_report_error = Some(tr_args("report-failed", &[("error", &err)]));
+                        }
+                    }
+                }
+                if let Some(ref err) = self.problem_report_error {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+            });
+
+        if !open || created {
+            self.problem_report = None;
+            self.problem_report_error = None;
+        }
+    }
+}
+
+/// Returns the new flight rules of the arrival airport if they just dropped to IFR or LIFR.
+fn worsened_flight_rules<'a>(
+    old: Option<&(AirportData, AirportData)>,
+    new: &'a (AirportData, AirportData),
+) -> Option<&'a str> {
+    let instrument = |rules: &str| rules == "IFR" || rules == "LIFR";
+    let (_, old_arr) = old?;
+    let (_, new_arr) = new;
+
+    // Without the last METAR, the rules may have been instrument already
+    let worsened = old_arr.icao == new_arr.icao
+        && !old_arr.flight_rules.is_empty()
+        && !instrument(&old_arr.flight_rules)
+        && instrument(&new_arr.flight_rules);
+    worsened.then_some(new_arr.flight_rules.as_str())
+}
+
+/// Checks whether a convective SIGMET started to affect the arrival airport.
+fn new_convective_sigmet(old: Option<&(AirportData, AirportData)>, new: &(AirportData, AirportData)) -> bool {
+    let convective = |airport: &AirportData| airport.hazards.iter().any(|sigmet| sigmet.is_convective());
+    let (_, new_arr) = new;
+    let was_convective = old.is_some_and(|(_, old_arr)| old_arr.icao == new_arr.icao && convective(old_arr));
+
+    convective(new_arr) && !was_convective
+}
+
+/// Collects the ATC changes of both airports between two refreshes.
+/// Airports that changed in between, e.g. after loading a new flight plan, are skipped.
+fn atc_changes(
+    old: Option<&(AirportData, AirportData)>,
+    new: &(AirportData, AirportData),
+) -> Vec<StationChange> {
+    let Some((old_dep, old_arr)) = old else { return Vec::new(); };
+    let (new_dep, new_arr) = new;
+
+    let mut changes = Vec::new();
+    for (old, new) in [(old_dep, new_dep), (old_arr, new_arr)] {
+        if old.icao == new.icao {
+            changes.extend(vatsim::station_changes(&old.stations, &new.stations));
+        }
+    }
+    changes
+}
+
+/// Remembers the ATIS letters of both airports and returns the airports
+/// whose letter changed since they were last seen, mapped to the previous letter.
+fn new_atis(
+    letters: &mut HashMap<String, char>,
+    new: &(AirportData, AirportData),
+) -> HashMap<String, char> {
+    let (dep, arr) = new;
+
+    let mut changed = HashMap::new();
+    for airport in [dep, arr] {
+        let Some(letter) = airport.atis_letter else { continue; };
+        match letters.insert(airport.icao.clone(), letter) {
+            Some(previous) if previous != letter => {
+                info!("New ATIS information {letter} at {}, was {previous}", airport.icao);
+                changed.insert(airport.icao.clone(), previous);
+            }
+            _ => (),
+        }
+    }
+    changed
+}
+
+/// Flags a new ATIS information, so the user knows to re-brief.
+fn show_new_atis(ui: &mut egui::Ui, airport: &AirportData, previous: Option<&char>) {
+    if let (Some(letter), Some(previous)) = (airport.atis_letter, previous) {
+        ui.colored_label(
+            egui::Color32::GOLD,
+            tr_args("new-atis", &[("letter", &letter), ("previous", previous)]),
+        );
+    }
+}
+
+/// Lists the details of the SimBrief flight plan.
+fn show_flight_plan(ui: &mut egui::Ui, flight_plan: &FlightPlan) {
+    let time = |time: Option<DateTime<Utc>>| time
+        .map(|time| time.format("%H:%Mz").to_string())
+        .unwrap_or_else(|| String::from("-"));
+    let fuel = match flight_plan.block_fuel {
+        Some(fuel) => format!("{fuel} {}", flight_plan.fuel_units),
+        None => String::from("-"),
+    };
+
+    let rows = [
+        ("callsign", flight_plan.callsign.clone()),
+        ("from-to", format!("{} / {}", flight_plan.origin, flight_plan.destination)),
+        ("alternate", flight_plan.alternate.clone().unwrap_or_else(|| String::from("-"))),
+        ("cruise-level", flight_plan.cruise_level()),
+        ("cost-index", flight_plan.cost_index.clone()),
+        ("block-fuel", fuel),
+        ("etd-eta", format!("{} / {}", time(flight_plan.etd), time(flight_plan.eta))),
+    ];
+
+    egui::Grid::new("flight_plan").num_columns(2).show(ui, |ui| {
+        for (name, value) in rows {
+            ui.label(format!("{}:", tr(name)));
+            ui.label(value);
+            ui.end_row();
+        }
+    });
+
+    ui.label(tr("route"));
+    ui.monospace(&flight_plan.route);
+}
+
+/// Lists the files of the OFP with a button to download each.
+///
+/// # Returns
+///
+/// The file to download, if its button was clicked.
+fn show_downloads(
+    ui: &mut egui::Ui,
+    files: &[OfpFile],
+    downloading: bool,
+    status: &Result<String, String>,
+) -> Option<OfpFile> {
+    if files.is_empty() {
+        return None;
+    }
+
+    let mut requested = None;
+    ui.add_space(10.0);
+    ui.label(tr("downloads"));
+    ui.horizontal_wrapped(|ui| {
+        for file in files {
+            if ui.add_enabled(!downloading, egui::Button::new(&file.name).small()).clicked() {
+                requested = Some(file.clone());
+            }
+        }
+    });
+
+    if downloading {
+        ui.spinner();
+    }
+    match status {
+        Ok(message) if message.is_empty() => (),
+        Ok(message) => { ui.label(message); }
+        Err(err) => { ui.colored_label(egui::Color32::RED, tr_args("download-failed", &[("error", err)])); }
+    }
+    requested
+}
+
+/// Whether a section is expanded, or about to be once its animation finished.
+fn is_expanded<R>(response: &egui::CollapsingResponse<R>) -> bool {
+    response.openness > 0.5
+}
+
+/// Shows raw METAR, ATIS or TAF text monospaced, wrapped at the window width and selectable for copying.
+fn show_raw_text(ui: &mut egui::Ui, text: &str) {
+    // A `&str` buffer makes the text edit read-only
+    let mut text = text;
+    ui.add(egui::TextEdit::multiline(&mut text)
+        .font(egui::TextStyle::Monospace)
+        .desired_width(f32::INFINITY)
+        .desired_rows(1));
+}
+
+/// Shows buttons copying the raw METAR and the ATIS, e.g. for pilot client remarks or a chat.
+fn show_copy_buttons(ui: &mut egui::Ui, airport: &AirportData) {
+    ui.horizontal(|ui| {
+        if ui.small_button(tr("copy-metar")).clicked() {
+            ui.output_mut(|output| output.copied_text = airport.observation.raw.clone());
+        }
+        if airport.atis_online && ui.small_button(tr("copy-atis")).clicked() {
+            ui.output_mut(|output| output.copied_text = airport.atis_text.clone());
+        }
+    });
+}
+
+/// Shows how wind and visibility changed since the previous METAR,
+/// with the previous METARs in a collapsed list. The QNH has its own line, see [`qnh_trend`].
+fn show_trends(ui: &mut egui::Ui, history: &MetarHistory, icao: &str) {
+    let changes = history.trends(icao);
+    if changes.is_empty() {
+        return;
+    }
+
+    let changes: Vec<String> = changes
+        .iter()
+        .filter_map(|change| {
+            let name = match change.quantity {
+                Quantity::Qnh => return None,
+                Quantity::Wind => tr("quantity-wind"),
+                Quantity::Visibility => tr("quantity-visibility"),
+            };
+            Some(tr_args("trend", &[
+                ("name", &name),
+                ("current", &change.current),
+                ("arrow", &change.trend.arrow()),
+                ("previous", &change.previous),
+            ]))
+        })
+        .collect();
+    if !changes.is_empty() {
+        ui.label(tr_args("since-previous-metar", &[("changes", &changes.join(", "))]));
+    }
+
+    egui::CollapsingHeader::new(tr("previous-metars"))
+        .id_source(format!("metar_history_{icao}"))
+        .show(ui, |ui| {
+            for observation in history.observations(icao).rev().skip(1) {
+                ui.monospace(&observation.raw);
+            }
+        });
+}
+
+/// Lists the SIGMETs and AIRMETs affecting an airport, thunderstorms in red.
+fn show_hazards(ui: &mut egui::Ui, airport: &AirportData) {
+    if airport.hazards.is_empty() {
+        return;
+    }
+
+    ui.label(format!("{} {}:", tr("hazards"), attribution(&[Source::AviationWeather])));
+    for sigmet in &airport.hazards {
+        let heading = format!("{} {} {}", sigmet.kind, sigmet.hazard, sigmet.validity());
+        if sigmet.is_convective() {
+            ui.colored_label(egui::Color32::RED, heading);
+        } else {
+            ui.colored_label(egui::Color32::GOLD, heading);
+        }
+        show_raw_text(ui, &sigmet.raw);
+    }
+}
+
+/// Shows the forecast winds and temperatures at cruise level along the route.
+fn show_winds(ui: &mut egui::Ui, flight_plan: &FlightPlan, winds: &Result<Vec<WindAloft>, String>) {
+    let winds = match winds {
+        Ok(winds) if winds.is_empty() => {
+            ui.label(tr("no-positions"));
+            return;
+        }
+        Ok(winds) => winds,
+        Err(err) => {
+            ui.colored_label(egui::Color32::RED, tr_args("winds-failed", &[("error", err)]));
+            return;
+        }
+    };
+
+    ui.label(tr_args("winds-at", &[
+        ("level", &flight_plan.cruise_level()),
+        ("pressure", &winds[0].pressure_level),
+    ]));
+    egui::Grid::new("winds").num_columns(3).show(ui, |ui| {
+        for wind in winds {
+            ui.label(&wind.point);
+            ui.monospace(format!("{:03}°/{:>3} kt", wind.direction, wind.speed));
+            ui.monospace(format!("{:>3} °C", wind.temperature));
+            ui.end_row();
+        }
+    });
+
+    show_wind_deviations(ui, flight_plan, winds);
+}
+
+/// Compares the forecast winds with the planned ones, highlighting large differences
+/// and the arrival they lead to.
+fn show_wind_deviations(ui: &mut egui::Ui, flight_plan: &FlightPlan, winds: &[WindAloft]) {
+    // Differences in knots worth a look, and delays in minutes worth mentioning
+    const NOTABLE_DIFFERENCE: f64 = 20.0;
+    const NOTABLE_DELAY: i64 = 5;
+
+    let deviations = winds::compare_with_plan(flight_plan, winds);
+    if deviations.is_empty() {
+        return;
+    }
+
+    let describe = |difference: f64| {
+        let key = if difference < 0.0 { "more-headwind" } else { "more-tailwind" };
+        tr_args(key, &[("speed", &difference.abs().round())])
+    };
+    ui.add_space(10.0);
+    for deviation in &deviations {
+        let text = format!("{}: {}", deviation.point, describe(deviation.difference()));
+        if deviation.difference().abs() >= NOTABLE_DIFFERENCE {
+            ui.colored_label(egui::Color32::GOLD, text);
+        } else {
+            ui.label(text);
+        }
+    }
+
+    let distance = flight_plan.profile.last().map(|fix| fix.distance);
+    let (Some(eta), Some(enroute_time), Some(distance)) = (flight_plan.eta, flight_plan.enroute_time, distance)
+    else {
+        return;
+    };
+    let Some(revised) = winds::revised_eta(eta, enroute_time, distance, &deviations) else { return; };
+    let delay = (revised - eta).num_minutes();
+    if delay.abs() >= NOTABLE_DELAY {
+        let key = if delay > 0 { "later-arrival" } else { "earlier-arrival" };
+        let text = tr_args(key, &[("revised", &revised.format("%H:%Mz")), ("eta", &eta.format("%H:%Mz"))]);
+        ui.colored_label(egui::Color32::GOLD, text);
+    }
+}
+
+/// Plots the planned altitude over the distance flown, with top of climb and descent
+/// and the winds aloft at the fixes closest to where they were forecast.
+/// Scroll with Ctrl to zoom, drag to pan and double-click to reset.
+fn show_profile(ui: &mut egui::Ui, flight_plan: &FlightPlan, winds: &[WindAloft]) {
+    let profile = &flight_plan.profile;
+    let point = |fix: &ProfilePoint| [fix.distance, f64::from(fix.altitude)];
+
+    egui_plot::Plot::new("profile")
+        .height(200.0)
+        .allow_scroll(false)
+        .include_y(0.0)
+        .x_axis_label("NM")
+        .y_axis_label("ft")
+        .show(ui, |plot_ui| {
+            plot_ui.line(egui_plot::Line::new(profile.iter().map(point).collect::<egui_plot::PlotPoints>()));
+
+            for fix in profile.iter().filter(|fix| matches!(fix.ident.as_str(), "TOC" | "TOD")) {
+                plot_ui.points(egui_plot::Points::new(point(fix)).radius(4.0));
+                plot_ui.text(egui_plot::Text::new(point(fix).into(), &fix.ident)
+                    .anchor(egui::Align2::CENTER_TOP));
+            }
+
+            for wind in winds {
+                let closest = profile
+                    .iter()
+                    .filter_map(|fix| {
+                        let position = fix.position?;
+                        let distance = (position.latitude - wind.position.latitude).powi(2)
+                            + (position.longitude - wind.position.longitude).powi(2);
+                        Some((fix, distance))
+                    })
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b));
+                let Some((fix, _)) = closest else { continue; };
+
+                let label = format!("{:03}°/{} kt {} °C", wind.direction, wind.speed, wind.temperature);
+                plot_ui.text(egui_plot::Text::new(point(fix).into(), label)
+                    .color(egui::Color32::LIGHT_BLUE)
+                    .anchor(egui::Align2::CENTER_BOTTOM));
+            }
+        });
+}
+
+/// Shows the great circle distance between the airports
+/// and the time enroute at the planned cruise speed, if there is a flight plan.
+fn show_distance(
+    ui: &mut egui::Ui,
+    departure: &AirportData,
+    arrival: &AirportData,
+    flight_plan: Option<&FlightPlan>,
+) {
+    let (Some(from), Some(to)) = (departure.position, arrival.position) else { return; };
+    let distance = from.distance_nm(to);
+
+    let mut text = format!("{} → {}: {distance:.0} NM", departure.icao, arrival.icao);
+    if let Some(speed) = flight_plan.and_then(|flight_plan| flight_plan.cruise_speed) {
+        if let Some(time) = logic::enroute_time(distance, speed) {
+            text.push_str(&tr_args("enroute-time", &[("time", &format_duration(time)), ("speed", &speed)]));
+        }
+    }
+    ui.label(text);
+}
+
+/// A point shown on the route map.
+struct MapMarker {
+    label: String,
+    position: Position,
+    color: egui::Color32,
+}
+
+/// Collects the airports and the aircraft to show on the route map, those with a known position.
+///
+/// The airports are colored by their flight rules, the alternate gray as its weather is not fetched.
+fn map_markers(
+    flight_plan: Option<&FlightPlan>,
+    airports: Option<&(AirportData, AirportData)>,
+    aircraft: Option<&AircraftState>,
+) -> Vec<MapMarker> {
+    let airport = |airport: &AirportData| Some(MapMarker {
+        label: format!("{} {}", airport.icao, airport.flight_rules),
+        position: airport.position?,
+        color: flight_rules_color(&airport.flight_rules).unwrap_or(egui::Color32::GRAY),
+    });
+    let planned = |icao: &str, position: Option<Position>| Some(MapMarker {
+        label: icao.to_string(),
+        position: position?,
+        color: egui::Color32::GRAY,
+    });
+
+    let mut markers = Vec::new();
+    match (airports, flight_plan) {
+        (Some((departure, arrival)), _) => markers.extend([airport(departure), airport(arrival)]),
+        (None, Some(flight_plan)) => markers.extend([
+            planned(&flight_plan.origin, flight_plan.origin_position),
+            planned(&flight_plan.destination, flight_plan.destination_position),
+        ]),
+        (None, None) => (),
+    }
+    if let Some(flight_plan) = flight_plan {
+        if let Some(ref alternate) = flight_plan.alternate {
+            markers.push(planned(&format!("{alternate} (ALTN)"), flight_plan.alternate_position));
+        }
+    }
+    markers.push(aircraft.map(|aircraft| MapMarker {
+        label: tr("aircraft"),
+        position: aircraft.position,
+        color: egui::Color32::WHITE,
+    }));
+    markers.into_iter().flatten().collect()
+}
+
+/// Plots the airports and the planned route on a map, longitude to the right and latitude up.
+/// Scroll with Ctrl to zoom, drag to pan and double-click to reset.
+fn show_route_map(ui: &mut egui::Ui, markers: &[MapMarker], route: &[ProfilePoint]) {
+    // Shrink the longitude so distances look alike in all directions around the middle of the map
+    let latitudes = markers.iter().map(|marker| marker.position.latitude);
+    let middle = (latitudes.clone().fold(f64::MAX, f64::min) + latitudes.fold(f64::MIN, f64::max)) / 2.0;
+    let scale = middle.to_radians().cos().max(0.1);
+    let point = move |position: Position| [position.longitude * scale, position.latitude];
+
+    egui_plot::Plot::new("route_map")
+        .height(300.0)
+        .allow_scroll(false)
+        .data_aspect(1.0)
+        .show_axes(false)
+        .label_formatter(move |name, value| {
+            format!("{name}\n{:.2}°, {:.2}°", value.y, value.x / scale)
+        })
+        .show(ui, |plot_ui| {
+            let fixes: egui_plot::PlotPoints = route
+                .iter()
+                .filter_map(|fix| fix.position.map(point))
+                .collect();
+            plot_ui.line(egui_plot::Line::new(fixes).color(egui::Color32::LIGHT_BLUE).name(tr("route-line")));
+
+            for marker in markers {
+                plot_ui.points(egui_plot::Points::new(point(marker.position))
+                    .radius(6.0)
+                    .color(marker.color)
+                    .name(&marker.label));
+                plot_ui.text(egui_plot::Text::new(point(marker.position).into(), &marker.label)
+                    .anchor(egui::Align2::LEFT_BOTTOM));
+            }
+        });
+}
+
+/// Shows the TAF of an airport with the change groups valid at the ETA highlighted.
+fn show_taf(ui: &mut egui::Ui, airport: &AirportData, eta: Option<DateTime<Utc>>) {
+    let Some(ref taf) = airport.taf else { return; };
+
+    show_raw_text(ui, &format!("TAF {}: {}", attribution(&[Source::Avwx]), taf.raw));
+    if let Some(eta) = eta {
+        for risk in taf::risks_at(taf, eta) {
+            let rules = if risk.flight_rules.is_empty() { tr("taf-changes") } else { risk.flight_rules };
+            let args: [(&str, &dyn std::fmt::Display); 2] = [("group", &risk.raw), ("rules", &rules)];
+            let text = match risk.likelihood {
+                Likelihood::Probability(percent) =>
+                    tr_args("taf-risk-probability", &[args[0], args[1], ("percent", &percent)]),
+                Likelihood::Expected => tr_args("taf-risk-expected", &args),
+                Likelihood::Temporary => tr_args("taf-risk-temporary", &args),
+            };
+            ui.colored_label(egui::Color32::GOLD, text);
+        }
+    }
+}
+
+/// Shows the heading of an airport section with its local time, network traffic and a link to its charts.
+fn show_airport_heading(
+    ui: &mut egui::Ui,
+    heading: &str,
+    airport: &AirportData,
+    info: Option<&Result<AirportInfo, String>>,
+    forecast: Option<&taf::Forecast>,
+    icing: Option<IcingRisk>,
+) {
+    let icao = &airport.icao;
+    ui.horizontal(|ui| {
+        ui.heading(heading);
+        show_flight_rules(ui, &airport.flight_rules);
+        if let Some(forecast) = forecast {
+            let text = tr_args("forecast-at-eta", &[
+                ("time", &forecast.time.format("%H%M")),
+                ("conditions", &forecast.conditions()),
+            ]);
+            let color = flight_rules_color(&forecast.flight_rules)
+                .unwrap_or_else(|| ui.visuals().text_color());
+            ui.colored_label(color, text).on_hover_text(tr("forecast-at-eta-hint"));
+        }
+        if let Some(icing) = icing {
+            let (color, hint) = match icing {
+                IcingRisk::Possible => (egui::Color32::LIGHT_BLUE, "icing-possible"),
+                IcingRisk::Severe => (egui::Color32::RED, "icing-severe"),
+            };
+            ui.colored_label(color, egui::RichText::new("❄").strong()).on_hover_text(tr(hint));
+        }
+        if let Some(Ok(AirportInfo { time_zone: Some(time_zone), .. })) = info {
+            let local = time_zone.local(Utc::now());
+            ui.label(format!("{} {}", local.format("%H:%M"), time_zone.abbreviation))
+                .on_hover_text(format!("{} {}", time_zone.name, attribution(&[Source::OpenMeteo])));
+            // Keep the clock current
+            let seconds = u64::from(60 - local.second().min(59));
+            ui.ctx().request_repaint_after(Duration::from_secs(seconds));
+        }
+        if airport.network.has_datafeed() {
+            let traffic = tr_args("traffic", &[
+                ("inbound", &airport.traffic.inbound),
+                ("outbound", &airport.traffic.outbound),
+            ]);
+            ui.label(format!("{traffic} {}", attribution(&[airport.network.source()])));
+        }
+        if ui.small_button(tr("charts")).clicked() {
+            let url = format!("https://chartfox.org/{icao}");
+            log_open_error(platform::open_url(&url), &url);
+        }
+    });
+}
+
+/// Shows what couldn't be loaded of an airport, the rest is shown as usual.
+fn show_airport_errors(ui: &mut egui::Ui, airport: &AirportData) {
+    for error in &airport.errors {
+        ui.colored_label(egui::Color32::RED, tr_args("part-failed", &[("error", error)]));
+    }
+}
+
+/// Shows the parts of the running request, those that arrived with a check mark.
+fn show_loading(ui: &mut egui::Ui, loading: &[(LoadItem, LoadState)]) {
+    ui.horizontal_wrapped(|ui| {
+        for (item, state) in loading {
+            let name = tr(match item {
+                LoadItem::DepartureMetar => "load-departure-metar",
+                LoadItem::ArrivalMetar => "load-arrival-metar",
+                LoadItem::DepartureAtis => "load-departure-atis",
+                LoadItem::ArrivalAtis => "load-arrival-atis",
+                LoadItem::SimBrief => "load-simbrief",
+            });
+            match state {
+                LoadState::Pending => ui.weak(format!("{name} …")),
+                LoadState::Done => ui.colored_label(egui::Color32::GREEN, format!("{name} ✔")),
+                LoadState::Failed => ui.colored_label(egui::Color32::RED, format!("{name} ✖")),
+            };
+        }
+    });
+}
+
+/// Shows how long ago the METAR was observed, in yellow or red once it is older than expected,
+/// e.g. because an AUTO station stopped sending.
+fn show_metar_age(ui: &mut egui::Ui, airport: &AirportData, warning_minutes: i64, alert_minutes: i64) {
+    let now = Utc::now();
+    let observation = &airport.observation;
+    let Some(time) = observation.time.or_else(|| logic::observation_time(&observation.raw, now)) else {
+        return;
+    };
+
+    let minutes = (now - time).num_minutes().max(0);
+    let age = match minutes {
+        0..=59 => format!("{minutes} min"),
+        _ => format!("{} h {:02} min", minutes / 60, minutes % 60),
+    };
+    let text = tr_args("metar-age", &[("age", &age), ("time", &time.format("%H:%M"))]);
+    if minutes >= alert_minutes {
+        ui.colored_label(egui::Color32::RED, text);
+    } else if minutes >= warning_minutes {
+        ui.colored_label(egui::Color32::GOLD, text);
+    } else {
+        ui.label(text);
+    }
+    // Keep the age current
+    ui.ctx().request_repaint_after(Duration::from_secs(60));
+}
+
+/// Describes how the QNH of an airport changed since its previous METAR, e.g. `Q1013 ↓ -2 hPa`.
+fn qnh_trend(history: &MetarHistory, icao: &str) -> Option<String> {
+    let change = history.qnh_trend(icao)?;
+    Some(format!("{} {} {}",
+                 units::format_altimeter(change.current),
+                 change.trend.arrow(),
+                 units::format_altimeter_change(change.previous, change.current)))
+}
+
+/// Shows one airport as a row of the compact overlay.
+/// The QNH trend replaces the plain QNH where known.
+fn show_compact_airport(ui: &mut egui::Ui, airport: &AirportData, qnh_trend: Option<String>) {
+    ui.strong(&airport.icao);
+    show_flight_rules(ui, &airport.flight_rules);
+    ui.monospace(airport.wind.map(|wind| wind.describe()).unwrap_or_else(|| String::from("-")));
+    let qnh = qnh_trend.or_else(|| airport.observation.qnh.map(units::format_altimeter));
+    ui.monospace(qnh.unwrap_or_else(|| String::from("-")));
+    match airport.atis_letter {
+        Some(letter) => ui.strong(format!("ATIS {letter}")),
+        None => ui.weak(tr("no-atis")),
+    };
+}
+
+/// The level of the window, above all others if pinned.
+fn window_level(always_on_top: bool) -> egui::WindowLevel {
+    if always_on_top {
+        egui::WindowLevel::AlwaysOnTop
+    } else {
+        egui::WindowLevel::Normal
+    }
+}
+
+/// Shows the current time in UTC and local time, always current unlike the time of the last request.
+fn show_clock(ui: &mut egui::Ui) {
+    let (utc, local) = (Utc::now(), Local::now());
+    let text = format!("{}z  {} lcl", utc.format("%H:%M"), local.format("%H:%M"));
+    ui.label(egui::RichText::new(text).monospace())
+        .on_hover_text(utc.format("%A, %d %B %Y").to_string());
+    // Repaint at the next full minute, even without user input
+    let seconds = u64::from(60 - utc.second().min(59));
+    ui.ctx().request_repaint_after(Duration::from_secs(seconds));
+}
+
+/// Shows the runways in use above the ATIS, if the ATIS names them.
+fn show_runways_in_use(ui: &mut egui::Ui, airport: &AirportData) {
+    if let Some(ref runways) = airport.runways_in_use {
+        ui.label(egui::RichText::new(tr_args("runways-in-use", &[("runways", runways)])).strong());
+    }
+}
+
+/// Lists the head- and crosswind of each runway end, crosswinds above the limit in red.
+fn show_runway_winds(ui: &mut egui::Ui, airport: &AirportData, crosswind_limit: f64) {
+    let Some(ref wind) = airport.wind else { return; };
+    let components = runways::runway_winds(&airport.runways, wind);
+    if components.is_empty() {
+        return;
+    }
+
+    ui.add_space(10.0);
+    ui.label(format!("{} {}:", tr("runway-winds"), attribution(&[Source::Avwx])));
+    egui::Grid::new(format!("runway_winds_{}", airport.icao)).num_columns(3).show(ui, |ui| {
+        for component in components {
+            let text = |text: String, color: Option<egui::Color32>| match color {
+                Some(color) => egui::RichText::new(text).monospace().color(color),
+                None => egui::RichText::new(text).monospace(),
+            };
+            ui.label(text(component.ident, None));
+
+            let headwind = component.headwind.round();
+            let speed = |speed: f64| format!("{speed:>3}");
+            let (headwind, color) = if headwind < 0.0 {
+                (tr_args("runway-tailwind", &[("speed", &speed(-headwind))]), Some(egui::Color32::GOLD))
+            } else {
+                (tr_args("runway-headwind", &[("speed", &speed(headwind.abs()))]), None)
+            };
+            ui.label(text(headwind, color));
+
+            let side = if component.crosswind < 0.0 { "left" } else { "right" };
+            let crosswind = speed(component.crosswind.abs().round());
+            let crosswind = tr_args(&format!("runway-crosswind-{side}"), &[("speed", &crosswind)]);
+            let color = (component.crosswind.abs() > crosswind_limit).then_some(egui::Color32::RED);
+            ui.label(text(crosswind, color));
+            ui.end_row();
+        }
+    });
+}
+
+/// Shows a row per unit pair, editing either side converts it into the other.
+fn show_converters(ui: &mut egui::Ui, conversions: &mut [(Conversion, f64, f64)]) {
+    egui::Grid::new("converters").num_columns(2).show(ui, |ui| {
+        for (conversion, first, second) in conversions {
+            let (first_unit, second_unit) = conversion.units();
+            let first_changed = ui.add(egui::DragValue::new(first)
+                .max_decimals(2)
+                .suffix(format!(" {first_unit}"))).changed();
+            let second_changed = ui.add(egui::DragValue::new(second)
+                .max_decimals(2)
+                .suffix(format!(" {second_unit}"))).changed();
+            if first_changed {
+                *second = conversion.forward(*first);
+            } else if second_changed {
+                *first = conversion.backward(*second);
+            }
+            ui.end_row();
+        }
+    });
+}
+
+/// Shows the head- and crosswind on any runway heading for a wind entered by hand.
+fn show_wind_calculator(ui: &mut egui::Ui, heading: &mut f64, wind: &mut Wind, crosswind_limit: f64) {
+    let mut direction = wind.direction.unwrap_or_default();
+    let mut gust = wind.gust.unwrap_or_default();
+    ui.horizontal(|ui| {
+        ui.label(tr("runway-heading"));
+        ui.add(egui::DragValue::new(heading).clamp_range(1.0..=360.0).suffix("°"));
+        ui.label(tr("wind"));
+        ui.add(egui::DragValue::new(&mut direction).clamp_range(1.0..=360.0).suffix("°"));
+        ui.add(egui::DragValue::new(&mut wind.speed).clamp_range(0.0..=150.0).suffix(" kt"));
+        ui.label(tr("gusting"));
+        ui.add(egui::DragValue::new(&mut gust).clamp_range(0.0..=200.0).suffix(" kt"));
+    });
+    wind.direction = Some(direction);
+    // Gusts below the wind speed mean there are none
+    wind.gust = (gust > wind.speed).then_some(gust);
+
+    let (headwind, crosswind) = runways::wind_components(*heading, wind);
+    let headwind = match headwind.round() {
+        headwind if headwind < 0.0 => tr_args("tailwind", &[("speed", &-headwind)]),
+        headwind => tr_args("headwind", &[("speed", &headwind.abs())]),
+    };
+    let key = if crosswind < 0.0 { "crosswind-left" } else { "crosswind-right" };
+    let text = format!("{headwind}, {}", tr_args(key, &[("speed", &crosswind.abs().round())]));
+    if crosswind.abs() > crosswind_limit {
+        ui.colored_label(egui::Color32::RED, text);
+    } else {
+        ui.label(text);
+    }
+}
+
+/// Shows a banner that the APIs can't be reached and how old the data below is.
+fn show_offline_banner(ui: &mut egui::Ui, time: DateTime<Utc>) {
+    let minutes = (Utc::now() - time).num_minutes();
+    let text = tr_args("offline", &[("time", &time.format("%H:%M")), ("minutes", &minutes)]);
+    egui::Frame::none()
+        .fill(egui::Color32::from_rgb(120, 70, 0))
+        .inner_margin(8.0)
+        .rounding(4.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.label(egui::RichText::new(text).strong().color(egui::Color32::WHITE));
+        });
+}
+
+/// Shows the recent log lines down to a level, newest at the bottom.
+fn show_logs(ui: &mut egui::Ui, level: &mut Level) {
+    let lines = logging::recent_lines(*level);
+
+    ui.horizontal(|ui| {
+        ui.label(tr("log-level"));
+        egui::ComboBox::from_id_source("log_level")
+            .selected_text(level.as_str())
+            .show_ui(ui, |ui| {
+                for option in [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG] {
+                    ui.selectable_value(level, option, option.as_str());
+                }
+            });
+        if ui.button(tr("copy")).clicked() {
+            let text: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+            ui.output_mut(|output| output.copied_text = text.join("\n"));
+        }
+        if ui.button(tr("open-log-folder")).clicked() {
+            log_open_error(platform::open_log_folder(), "log folder");
+        }
+    });
+    let note = tr_args("log-note", &[("lines", &logging::BUFFER_LINES), ("file", &LOGFILE_NAME)]);
+    ui.weak(note);
+
+    if lines.is_empty() {
+        ui.label(tr("nothing-logged"));
+        return;
+    }
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    egui::ScrollArea::vertical()
+        .id_source("log_lines")
+        .max_height(250.0)
+        .stick_to_bottom(true)
+        .show_rows(ui, row_height, lines.len(), |ui, rows| {
+            for line in &lines[rows] {
+                let color = match line.level {
+                    Level::ERROR => ui.visuals().error_fg_color,
+                    Level::WARN => ui.visuals().warn_fg_color,
+                    _ => ui.visuals().text_color(),
+                };
+                ui.label(egui::RichText::new(line.to_string()).monospace().color(color));
+            }
+        });
+}
+
+/// Shows how many AVWX calls were made today and how many are left, in gold once they run low.
+fn show_quota(ui: &mut egui::Ui) {
+    let budget = quota::budget();
+    let text = tr_args("avwx-calls", &[
+        ("used", &budget.used),
+        ("limit", &budget.limit),
+        ("remaining", &budget.remaining),
+    ]);
+    // Less than an hour of refreshes
+    if budget.allows(12 * quota::CALLS_PER_REFRESH) {
+        ui.weak(text);
+    } else {
+        ui.colored_label(egui::Color32::GOLD, text);
+    }
+}
+
+/// Shows civil twilight, sunrise and sunset of an airport on the day of the scheduled time,
+/// and whether that time is in daylight.
+fn show_sun_times(ui: &mut egui::Ui, airport: &AirportData, event: &str, scheduled: Option<DateTime<Utc>>) {
+    let Some(position) = airport.position else { return; };
+    let time = scheduled.unwrap_or_else(Utc::now);
+    let times = sun::sun_times(position, time.date_naive());
+
+    let format = |time: Option<DateTime<Utc>>| time
+        .map(|time| time.format("%H:%Mz").to_string())
+        .unwrap_or_else(|| String::from("-"));
+    let mut text = tr_args("sun-times", &[
+        ("icao", &airport.icao),
+        ("dawn", &format(times.civil_dawn)),
+        ("sunrise", &format(times.sunrise)),
+        ("sunset", &format(times.sunset)),
+        ("dusk", &format(times.civil_dusk)),
+    ]);
+    if scheduled.is_some() {
+        let daylight = match times.daylight_at(time) {
+            Daylight::Day => "daylight-day",
+            Daylight::Twilight => "daylight-twilight",
+            Daylight::Night => "daylight-night",
+        };
+        text.push_str(&format!(" ({})", tr_args(daylight, &[("event", &event)])));
+    }
+    ui.label(text);
+}
+
+/// Shows flight rules in their usual colors, unknown ones in the default text color.
+fn show_flight_rules(ui: &mut egui::Ui, flight_rules: &str) {
+    let color = flight_rules_color(flight_rules).unwrap_or_else(|| ui.visuals().text_color());
+    ui.colored_label(color, egui::RichText::new(flight_rules).strong());
+}
+
+/// The usual color of flight rules, `None` for unknown ones.
+fn flight_rules_color(flight_rules: &str) -> Option<egui::Color32> {
+    match flight_rules {
+        "VFR" => Some(egui::Color32::GREEN),
+        "MVFR" => Some(egui::Color32::from_rgb(80, 140, 255)),
+        "IFR" => Some(egui::Color32::RED),
+        "LIFR" => Some(egui::Color32::from_rgb(255, 0, 255)),
+        _ => None,
+    }
+}
+
+/// Logs a failed platform action, there is nothing else we can do about it.
+fn log_open_error(result: std::io::Result<()>, target: &str) {
+    if let Err(err) = result {
+        warn!("Failed to open {target}: {err}");
+    }
+}
+
+/// Shows the top-down ATC coverage of an airport as a colored label.
+fn show_coverage(ui: &mut egui::Ui, airport: &AirportData) {
+    if !airport.network.has_datafeed() {
+        return;
+    }
+
+    let coverage = vatsim::coverage(&airport.stations);
+    let color = match coverage {
+        Coverage::Tower => egui::Color32::GREEN,
+        Coverage::ApproachOnly => egui::Color32::YELLOW,
+        Coverage::CenterOnly => egui::Color32::from_rgb(255, 165, 0),
+        Coverage::Unicom => egui::Color32::GRAY,
+    };
+    let description = tr(match coverage {
+        Coverage::Tower => "coverage-tower",
+        Coverage::ApproachOnly => "coverage-approach",
+        Coverage::CenterOnly => "coverage-center",
+        Coverage::Unicom => "coverage-unicom",
+    });
+    ui.colored_label(color, description);
+}
+
+/// Shows the density altitude of an airport, in gold if it is well above the field, e.g. at a high airport
+/// on a hot day. The elevation of the station info is used if the METAR lacks it.
+fn show_density_altitude(
+    ui: &mut egui::Ui,
+    airport: &AirportData,
+    info: Option<&Result<AirportInfo, String>>,
+) {
+    let elevation = airport.elevation_ft.or_else(|| match info {
+        Some(Ok(info)) => info.elevation_ft.map(f64::from),
+        _ => None,
+    });
+    let qnh = airport.observation.qnh;
+    let (Some(elevation), Some(qnh), Some(temperature)) = (elevation, qnh, airport.temperature) else {
+        return;
+    };
+
+    let density_altitude = units::density_altitude(elevation, qnh, temperature);
+    let text = tr_args("density-altitude", &[
+        ("altitude", &format!("{density_altitude:.0}")),
+        ("elevation", &format!("{elevation:.0}")),
+    ]);
+    let label = if density_altitude - elevation >= HIGH_DENSITY_ALTITUDE_FT {
+        ui.colored_label(egui::Color32::GOLD, text)
+    } else {
+        ui.label(text)
+    };
+    label.on_hover_text(tr("density-altitude-hint"));
+}
+
+/// Shows the elevation and runways of an airport in a collapsed section.
+fn show_airport_info(ui: &mut egui::Ui, icao: &str, info: Option<&Result<AirportInfo, String>>) {
+    ui.add_space(10.0);
+    egui::CollapsingHeader::new(format!("{} {}", tr("airport-info"), attribution(&[Source::Avwx])))
+        .id_source(format!("airport_info_{icao}"))
+        .show(ui, |ui| {
+            let info = match info {
+                None => {
+                    ui.spinner();
+                    return;
+                }
+                Some(Err(err)) => {
+                    ui.colored_label(egui::Color32::RED, tr_args("airport-info-failed", &[("error", err)]));
+                    return;
+                }
+                Some(Ok(info)) => info,
+            };
+
+            ui.label(format!("{} ({})", info.name, info.location));
+            let elevation = info.elevation_ft
+                .map_or_else(|| String::from("-"), |elevation| format!("{elevation} ft"));
+            ui.label(tr_args("elevation", &[("elevation", &elevation)]));
+
+            if info.runways.is_empty() {
+                return;
+            }
+            egui::Grid::new(format!("runways_{icao}")).num_columns(3).show(ui, |ui| {
+                for runway in &info.runways {
+                    ui.monospace(runway.idents.join("/"));
+                    let size = match (runway.length_ft, runway.width_ft) {
+                        (Some(length), Some(width)) => format!("{length} x {width} ft"),
+                        (Some(length), None) => format!("{length} ft"),
+                        _ => String::from("-"),
+                    };
+                    ui.monospace(size);
+                    ui.label(runway.surface.as_deref().unwrap_or_default());
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+/// Lists the share of each flight rules and the average wind of every airport in the METAR history.
+fn show_statistics(ui: &mut egui::Ui, history: &MetarHistory, kept: bool) {
+    const DAYS: i64 = 7;
+
+    if !kept {
+        ui.label(tr("statistics-session-only"));
+    }
+    let airports: Vec<(&str, Statistics)> = history
+        .airports()
+        .into_iter()
+        .filter_map(|icao| Some((icao, statistics::statistics(history, icao, DAYS)?)))
+        .collect();
+    if airports.is_empty() {
+        ui.label(tr("no-metars-recorded"));
+        return;
+    }
+
+    ui.label(tr_args("last-days", &[("days", &DAYS)]));
+    egui::Grid::new("statistics").num_columns(4).show(ui, |ui| {
+        for (icao, statistics) in airports {
+            ui.monospace(icao);
+            let flight_rules: Vec<String> = statistics.flight_rules
+                .iter()
+                .map(|(rules, share)| format!("{rules} {:.0}%", share * 100.0))
+                .collect();
+            ui.label(flight_rules.join(", "));
+            let wind = statistics.average_wind
+                .map_or_else(|| String::from("-"), |wind| format!("{wind:.0}"));
+            ui.label(tr_args("average-wind", &[("wind", &wind)]));
+            let count = tr_args("metar-count", &[("count", &statistics.observations)]);
+            let text = match statistics.since {
+                Some(since) => tr_args("metar-count-since", &[
+                    ("count", &count),
+                    ("since", &since.format("%Y-%m-%d %H:%Mz")),
+                ]),
+                None => count,
+            };
+            ui.weak(text);
+            ui.end_row();
+        }
+    });
+}
+
+/// Lists the online ATC stations of an airport with a button to copy each frequency.
+fn show_stations(ui: &mut egui::Ui, airport: &AirportData) {
+    let stations = &airport.stations;
+    if !airport.network.has_datafeed() {
+        return;
+    }
+    ui.add_space(10.0);
+
+    if stations.is_empty() {
+        ui.label(tr_args("no-atc", &[("network", &airport.network.name())]));
+        return;
+    }
+
+    ui.label(format!("{} {}:", tr("online-atc"), attribution(&[airport.network.source()])));
+    for station in stations {
+        ui.horizontal(|ui| {
+            ui.monospace(format!("{:<14}{}", station.callsign, station.frequency));
+            if ui.small_button(tr("copy")).clicked() {
+                ui.output_mut(|output| output.copied_text = station.frequency.clone());
+            }
+            if station.estimated {
+                ui.weak(tr("coverage-estimated"));
+            }
         });
     }
 }
@@ -0,0 +1,100 @@
+use tracing::{debug, info};
+
+use crate::error::Result;
+use crate::logic::{atis_letter, atis_text, parse_json, truncate, ApiClient, Atis, HttpClient};
+use crate::logic::MAX_LOGGED_RESPONSE;
+
+const ATIS_URI: &str = "https://www.pilotedge.net/atis";
+const NOT_AVAILABLE: &str = "No PilotEdge ATIS available";
+
+/// Checks whether an airport may be inside PilotEdge coverage.
+///
+/// PilotEdge only covers US airspace, so airports outside the US are not requested at all.
+/// Whether a US airport is actually covered is decided by the ATIS endpoint.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::pilotedge::in_coverage;
+///
+/// assert!(in_coverage("KLAX"));
+/// assert!(!in_coverage("EDDF"));
+/// ```
+pub fn in_coverage(icao: &str) -> bool {
+    let icao = icao.trim().to_uppercase();
+    icao.len() == 4 && icao.starts_with('K')
+}
+
+/// Fetches the PilotEdge ATIS of an airport.
+///
+/// Airports outside the coverage and airports without an ATIS
+/// get a "not available" text instead of an error.
+///
+/// # Arguments
+///
+/// * `client` - Sends the request, [`HttpClient`] for the real API.
+/// * `icao` - The ICAO code of the airport.
+///
+/// # Errors
+///
+/// Returns an error if the request fails.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::logic::HttpClient;
+///
+/// let atis = get_flight_data::pilotedge::fetch_atis(&HttpClient, "KSFO").await?;
+/// println!("{}", atis.text);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_atis(client: &impl ApiClient, icao: &str) -> Result<Atis> {
+    let not_available = || Atis { text: NOT_AVAILABLE.to_string(), letter: None, translation: None };
+
+    if !in_coverage(icao) {
+        info!("{icao} is outside PilotEdge coverage");
+        return Ok(not_available());
+    }
+
+    debug!("Calling PilotEdge ATIS for {icao}");
+    let response = client.get(&format!("{ATIS_URI}/{icao}.json")).await?;
+    debug!("Raw PilotEdge ATIS of {icao}: {}", truncate(&response, MAX_LOGGED_RESPONSE));
+
+    // Unknown airports are answered with an error page instead of JSON
+    let Ok(json) = parse_json(&response, "PilotEdge ATIS") else {
+        return Ok(not_available());
+    };
+
+    let text = atis_text(&json["text"]);
+    if text.trim().is_empty() {
+        return Ok(not_available());
+    }
+
+    let letter = json["letter"]
+        .as_str()
+        .and_then(|letter| letter.trim().chars().next())
+        .filter(char::is_ascii_alphabetic)
+        .map(|letter| letter.to_ascii_uppercase())
+        .or_else(|| atis_letter(&text));
+    info!("Information letter of PilotEdge ATIS of {icao}: {letter:?}");
+
+    Ok(Atis { text, letter, translation: None })
+}
+
+/// Checks whether an ATIS returned by [`fetch_atis`] is an actual ATIS.
+pub fn available(atis: &Atis) -> bool {
+    atis.text != NOT_AVAILABLE
+}
+
+/// Checks whether an airport has a PilotEdge ATIS.
+///
+/// # Errors
+///
+/// Returns an error if the request fails.
+pub async fn atis_online(icao: &str) -> Result<bool> {
+    let online = available(&fetch_atis(&HttpClient, icao).await?);
+    info!("PilotEdge ATIS of {icao} online: {online}");
+    Ok(online)
+}
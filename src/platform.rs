@@ -0,0 +1,195 @@
+use std::{env, fs, io};
+use std::path::Path;
+use std::process::Command;
+#[cfg(not(target_os = "macos"))]
+use std::env::current_exe;
+
+use tracing::info;
+
+use crate::dir_manager::get_log_dir;
+use crate::json_operations;
+use crate::launch::URI_SCHEME;
+use crate::logic::ERROR_FILE_NAME;
+
+const PRINT_FILE_NAME: &str = "gfd_briefing.txt";
+
+/// Opens the log directory in the file manager of the OS.
+///
+/// # Errors
+///
+/// Returns an error if the file manager can't be started.
+pub fn open_log_folder() -> io::Result<()> {
+    open(get_log_dir())
+}
+
+/// Opens the crash log in the default text editor.
+///
+/// # Errors
+///
+/// Returns an error if the editor can't be started.
+pub fn open_crash_log() -> io::Result<()> {
+    open(format!("{}{ERROR_FILE_NAME}", get_log_dir()))
+}
+
+/// Opens the directory containing `userdata.json` in the file manager of the OS.
+///
+/// # Errors
+///
+/// Returns an error if the directory can't be created or the file manager can't be started.
+pub fn open_config_folder() -> io::Result<()> {
+    open(json_operations::get_file_dir()?)
+}
+
+/// Opens a URL in the default browser.
+///
+/// # Errors
+///
+/// Returns an error if the browser can't be started.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// get_flight_data::platform::open_url("https://avwx.rest").unwrap();
+/// ```
+pub fn open_url(url: &str) -> io::Result<()> {
+    open(url)
+}
+
+/// Shows a desktop notification, so events are noticed while the app is behind the simulator.
+/// This may block until the notification server answered.
+///
+/// # Errors
+///
+/// Returns an error if the notification can't be shown, e.g. because there is no notification server.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// get_flight_data::platform::notify("New ATIS at EDDF", "Information K (was H), re-brief").unwrap();
+/// ```
+#[cfg(feature = "gui")]
+pub fn notify(summary: &str, body: &str) -> io::Result<()> {
+    info!("Notifying: {summary}: {body}");
+    notify_rust::Notification::new()
+        .appname("Get Flight Data")
+        .summary(summary)
+        .body(body)
+        .show()
+        .map(drop)
+        .map_err(io::Error::other)
+}
+
+/// Prints a text on the default printer, which may also be a PDF printer.
+/// The text is written to a file in the temporary directory first.
+///
+/// Windows prints with Notepad, macOS and Linux with `lp` from CUPS.
+/// The print command is not waited for.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be written or the print command can't be started.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// get_flight_data::platform::print_text("EDDF - LOWW").unwrap();
+/// ```
+pub fn print_text(text: &str) -> io::Result<()> {
+    let path = env::temp_dir().join(PRINT_FILE_NAME);
+    fs::write(&path, text)?;
+    info!("Printing {}", path.display());
+
+    #[cfg(target_os = "windows")]
+    Command::new("notepad").arg("/p").arg(&path).spawn()?;
+    #[cfg(not(target_os = "windows"))]
+    Command::new("lp").arg(&path).spawn()?;
+
+    Ok(())
+}
+
+/// Hands a path or URL to the OS, which opens it with the default application.
+/// The spawned process is not waited for.
+fn open(target: impl AsRef<Path>) -> io::Result<()> {
+    let target = target.as_ref();
+    info!("Opening {}", target.display());
+
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let program = "xdg-open";
+
+    Command::new(program).arg(target).spawn()?;
+    Ok(())
+}
+
+/// Registers this executable as the handler of `gfd://` links for the current user.
+///
+/// On Windows the scheme is added to the registry, on Linux a desktop entry is created
+/// and set as the default handler via `xdg-mime`.
+///
+/// # Errors
+///
+/// Returns an error if the registration fails.
+/// On macOS the scheme has to be declared in the `Info.plist` of the app bundle,
+/// so it can't be registered at runtime and an `Unsupported` error is returned.
+#[cfg(target_os = "windows")]
+pub fn register_uri_scheme() -> io::Result<()> {
+    let exe = current_exe()?;
+    let key = format!(r"HKCU\Software\Classes\{URI_SCHEME}");
+    let command = format!("\"{}\" \"%1\"", exe.display());
+
+    run(Command::new("reg").args(["add", &key, "/ve", "/d", "URL:Get Flight Data", "/f"]))?;
+    run(Command::new("reg").args(["add", &key, "/v", "URL Protocol", "/d", "", "/f"]))?;
+    run(Command::new("reg")
+        .args(["add", &format!(r"{key}\shell\open\command"), "/ve", "/d", &command, "/f"]))?;
+
+    info!("Registered {URI_SCHEME}:// for {}", exe.display());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn register_uri_scheme() -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{URI_SCHEME}:// has to be declared in the Info.plist of the app bundle on macOS"),
+    ))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn register_uri_scheme() -> io::Result<()> {
+    const DESKTOP_FILE: &str = "get_flight_data.desktop";
+
+    let exe = current_exe()?;
+    let applications = directories::BaseDirs::new()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No home directory"))?
+        .data_dir()
+        .join("applications");
+    fs::create_dir_all(&applications)?;
+
+    let entry = format!("[Desktop Entry]\n\
+        Type=Application\n\
+        Name=Get Flight Data\n\
+        Exec=\"{}\" %u\n\
+        MimeType=x-scheme-handler/{URI_SCHEME};\n\
+        NoDisplay=true\n", exe.display());
+    fs::write(applications.join(DESKTOP_FILE), entry)?;
+
+    run(Command::new("xdg-mime")
+        .args(["default", DESKTOP_FILE, &format!("x-scheme-handler/{URI_SCHEME}")]))?;
+
+    info!("Registered {URI_SCHEME}:// for {}", exe.display());
+    Ok(())
+}
+
+/// Runs a command to completion, turning a non-zero exit code into an error.
+#[cfg(not(target_os = "macos"))]
+fn run(command: &mut Command) -> io::Result<()> {
+    let status = command.status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{command:?} failed with {status}")))
+    }
+}
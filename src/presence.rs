@@ -0,0 +1,224 @@
+//! Discord Rich Presence, showing the flight and the weather at the destination in the Discord profile.
+//!
+//! Talks to the local Discord client over its IPC socket (a named pipe on Windows),
+//! so it works without a Discord library and without network access.
+//! Discord needs the ID of an application registered at <https://discord.com/developers/applications>,
+//! its name is shown above the activity.
+
+use std::io::{self, Read, Write};
+use std::sync::mpsc;
+use std::thread;
+
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use crate::logic::AirportData;
+
+// Opcodes of the IPC frames
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+// Discord tries the sockets `discord-ipc-0` to `discord-ipc-9`, one per running client
+const IPC_SOCKETS: u32 = 10;
+#[cfg(unix)]
+const IPC_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// What is shown in the Discord profile.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Activity {
+    /// The first line, e.g. `EDDF → EGLL`
+    pub details: String,
+    /// The second line, e.g. `ATIS info K, VFR`
+    pub state: String,
+}
+
+impl Activity {
+    /// Describes a flight with the ATIS letter and flight rules at the destination.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::presence::Activity;
+    ///
+    /// let activity = Activity::new("EDDF", "EGLL", Some('K'), "VFR");
+    /// assert_eq!(activity.details, "EDDF → EGLL");
+    /// assert_eq!(activity.state, "ATIS info K, VFR");
+    ///
+    /// let without_atis = Activity::new("EDDF", "EGLL", None, "IFR");
+    /// assert_eq!(without_atis.state, "IFR");
+    /// ```
+    pub fn new(departure: &str, arrival: &str, atis_letter: Option<char>, flight_rules: &str) -> Self {
+        let state = [
+            atis_letter.map(|letter| format!("ATIS info {letter}")),
+            Some(flight_rules.to_string()).filter(|rules| !rules.is_empty()),
+        ];
+        Activity {
+            details: format!("{departure} → {arrival}"),
+            state: state.into_iter().flatten().collect::<Vec<_>>().join(", "),
+        }
+    }
+
+    /// Describes a flight between two airports of a refresh.
+    pub fn for_airports(departure: &AirportData, arrival: &AirportData) -> Self {
+        Activity::new(&departure.icao, &arrival.icao, arrival.atis_letter, &arrival.flight_rules)
+    }
+}
+
+/// Starts a thread keeping the presence up to date with the activities sent to it.
+///
+/// `None` clears the presence. The presence is removed when the sender is dropped.
+/// If Discord is not running, the activity is dropped and the connection is tried again
+/// with the next one, so the presence appears with the first refresh after Discord was started.
+///
+/// # Arguments
+///
+/// * `application_id` - The ID of the Discord application the presence belongs to.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use get_flight_data::presence::{self, Activity};
+///
+/// let presence = presence::start("1234567890");
+/// presence.send(Some(Activity::new("EDDF", "EGLL", Some('K'), "VFR"))).unwrap();
+/// ```
+pub fn start(application_id: &str) -> mpsc::Sender<Option<Activity>> {
+    let (tx, rx) = mpsc::channel::<Option<Activity>>();
+    let application_id = application_id.trim().to_string();
+    thread::spawn(move || {
+        let mut connection: Option<Connection> = None;
+        for activity in rx {
+            if connection.is_none() {
+                connection = match Connection::open(&application_id) {
+                    Ok(connection) => Some(connection),
+                    Err(err) => {
+                        debug!("Discord is not available: {err}");
+                        continue;
+                    }
+                };
+            }
+            if let Some(open) = connection.as_mut() {
+                if let Err(err) = open.set_activity(activity.as_ref()) {
+                    // Discord was probably closed, reconnect with the next activity
+                    warn!("Failed to update the Discord presence: {err}");
+                    connection = None;
+                }
+            }
+        }
+        debug!("Discord presence stopped");
+    });
+    tx
+}
+
+/// Something to read and write IPC frames from, a socket or a named pipe.
+trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+/// An open connection to the Discord client.
+struct Connection {
+    stream: Box<dyn Stream>,
+    nonce: u64,
+}
+
+impl Connection {
+    fn open(application_id: &str) -> io::Result<Self> {
+        let mut connection = Connection { stream: connect()?, nonce: 0 };
+        connection.send(OP_HANDSHAKE, &json!({ "v": 1, "client_id": application_id }))?;
+        connection.receive()?;
+        info!("Connected to Discord");
+        Ok(connection)
+    }
+
+    fn set_activity(&mut self, activity: Option<&Activity>) -> io::Result<()> {
+        self.nonce += 1;
+        let activity = activity.map(|activity| json!({
+            "details": activity.details,
+            "state": activity.state,
+        }));
+        let command = json!({
+            "cmd": "SET_ACTIVITY",
+            "args": { "pid": std::process::id(), "activity": activity },
+            "nonce": self.nonce.to_string(),
+        });
+        self.send(OP_FRAME, &command)?;
+
+        let response = self.receive()?;
+        if response["evt"] == "ERROR" {
+            let message = response["data"]["message"].as_str().unwrap_or("unknown error");
+            return Err(io::Error::other(message.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Sends a frame, its opcode and length in little endian followed by the JSON.
+    fn send(&mut self, opcode: u32, payload: &Value) -> io::Result<()> {
+        let payload = payload.to_string();
+        let mut frame = Vec::with_capacity(8 + payload.len());
+        frame.extend_from_slice(&opcode.to_le_bytes());
+        frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        frame.extend_from_slice(payload.as_bytes());
+        self.stream.write_all(&frame)?;
+        self.stream.flush()
+    }
+
+    fn receive(&mut self) -> io::Result<Value> {
+        let mut header = [0; 8];
+        self.stream.read_exact(&mut header)?;
+        let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let mut payload = vec![0; length as usize];
+        self.stream.read_exact(&mut payload)?;
+        serde_json::from_slice(&payload).map_err(io::Error::other)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn connect() -> io::Result<Box<dyn Stream>> {
+    use std::fs::OpenOptions;
+
+    let mut last_error = io::Error::from(io::ErrorKind::NotFound);
+    for index in 0..IPC_SOCKETS {
+        match OpenOptions::new().read(true).write(true).open(format!(r"\\.\pipe\discord-ipc-{index}")) {
+            Ok(pipe) => return Ok(Box::new(pipe)),
+            Err(err) => last_error = err,
+        }
+    }
+    Err(last_error)
+}
+
+#[cfg(unix)]
+fn connect() -> io::Result<Box<dyn Stream>> {
+    use std::os::unix::net::UnixStream;
+    use std::path::PathBuf;
+
+    let temp_dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(std::env::var_os)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/tmp"));
+    // Flatpak and Snap installations of Discord keep their socket in a subdirectory
+    let dirs = [
+        temp_dir.clone(),
+        temp_dir.join("app/com.discordapp.Discord"),
+        temp_dir.join("snap.discord"),
+    ];
+
+    let mut last_error = io::Error::from(io::ErrorKind::NotFound);
+    for dir in &dirs {
+        for index in 0..IPC_SOCKETS {
+            match UnixStream::connect(dir.join(format!("discord-ipc-{index}"))) {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(IPC_TIMEOUT))?;
+                    stream.set_write_timeout(Some(IPC_TIMEOUT))?;
+                    return Ok(Box::new(stream));
+                }
+                Err(err) => last_error = err,
+            }
+        }
+    }
+    Err(last_error)
+}
+
+#[cfg(not(any(target_os = "windows", unix)))]
+fn connect() -> io::Result<Box<dyn Stream>> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
@@ -0,0 +1,143 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{NaiveDate, Utc};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::json_operations::get_file_dir;
+
+pub const USAGE_FILE_NAME: &str = "avwx_usage.json";
+/// Calls per UTC day on the free AVWX plan, used until AVWX reports a limit in its headers.
+pub const DAILY_LIMIT: u32 = 4000;
+/// AVWX calls of a refresh: the METARs of both airports and the arrival TAF.
+pub const CALLS_PER_REFRESH: u32 = 3;
+
+// Loaded from the usage file on first use
+static USAGE: Mutex<Option<Usage>> = Mutex::new(None);
+
+/// The AVWX calls made on one UTC day, saved between starts.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Usage {
+    pub day: NaiveDate,
+    pub calls: u32,
+    /// The limit and remaining calls from the `X-RateLimit-*` headers of the last response, if sent
+    pub reported_limit: Option<u32>,
+    pub reported_remaining: Option<u32>,
+}
+
+/// How many AVWX calls are left today.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Budget {
+    pub used: u32,
+    pub limit: u32,
+    pub remaining: u32,
+}
+
+impl Budget {
+    /// Checks whether the given number of calls still fits into the budget.
+    pub fn allows(&self, calls: u32) -> bool {
+        self.remaining >= calls
+    }
+}
+
+impl Usage {
+    fn new(day: NaiveDate) -> Self {
+        Usage { day, calls: 0, reported_limit: None, reported_remaining: None }
+    }
+
+    /// Counts a call, starting over on a new day.
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - The current UTC day.
+    /// * `limit` - The daily limit reported by AVWX, if any.
+    /// * `remaining` - The remaining calls reported by AVWX, if any.
+    pub fn record(&mut self, day: NaiveDate, limit: Option<u32>, remaining: Option<u32>) {
+        if self.day != day {
+            *self = Usage::new(day);
+        }
+        self.calls += 1;
+        self.reported_limit = limit.or(self.reported_limit);
+        self.reported_remaining = remaining;
+    }
+
+    /// The budget left on a day, preferring what AVWX reported over the own count.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::NaiveDate;
+    /// use get_flight_data::quota::{Usage, DAILY_LIMIT};
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+    /// let mut usage = Usage { day: today, calls: 0, reported_limit: None, reported_remaining: None };
+    /// usage.record(today, None, None);
+    /// usage.record(today, None, None);
+    /// assert_eq!(usage.budget(today).remaining, DAILY_LIMIT - 2);
+    ///
+    /// usage.record(today, Some(1000), Some(10));
+    /// let budget = usage.budget(today);
+    /// assert_eq!((budget.used, budget.limit, budget.remaining), (3, 1000, 10));
+    /// assert!(!budget.allows(11));
+    ///
+    /// // The count starts over at midnight UTC
+    /// let tomorrow = today.succ_opt().unwrap();
+    /// assert_eq!(usage.budget(tomorrow).remaining, 1000);
+    /// ```
+    pub fn budget(&self, day: NaiveDate) -> Budget {
+        let limit = self.reported_limit.unwrap_or(DAILY_LIMIT);
+        if self.day != day {
+            return Budget { used: 0, limit, remaining: limit };
+        }
+        let remaining = self.reported_remaining.unwrap_or(limit.saturating_sub(self.calls));
+        Budget { used: self.calls, limit, remaining }
+    }
+}
+
+/// Returns today's budget of AVWX calls.
+pub fn budget() -> Budget {
+    let today = Utc::now().date_naive();
+    match USAGE.lock() {
+        Ok(mut usage) => usage.get_or_insert_with(load).budget(today),
+        Err(_) => Usage::new(today).budget(today),
+    }
+}
+
+/// Counts an AVWX call and reads the rate limit headers of its response.
+pub(crate) fn record(headers: &HeaderMap) {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse().ok();
+    let (limit, remaining) = (header("x-ratelimit-limit"), header("x-ratelimit-remaining"));
+
+    let Ok(mut usage) = USAGE.lock() else {
+        return;
+    };
+    let usage = usage.get_or_insert_with(load);
+    usage.record(Utc::now().date_naive(), limit, remaining);
+    if let Err(err) = save(usage) {
+        warn!("Failed to save AVWX usage: {err}");
+    }
+}
+
+fn load() -> Usage {
+    let today = Utc::now().date_naive();
+    let Ok(content) = file_path().and_then(fs::read_to_string) else {
+        return Usage::new(today);
+    };
+    serde_json::from_str(&content).unwrap_or_else(|err| {
+        warn!("Ignoring invalid {USAGE_FILE_NAME}: {err}");
+        Usage::new(today)
+    })
+}
+
+fn save(usage: &Usage) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(usage).expect("Usage should be serializable");
+    fs::write(file_path()?, content)
+}
+
+fn file_path() -> io::Result<PathBuf> {
+    Ok(get_file_dir()?.join(USAGE_FILE_NAME))
+}
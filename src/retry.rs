@@ -0,0 +1,90 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::GfdError;
+
+static POLICY: Mutex<RetryPolicy> = Mutex::new(RetryPolicy::DEFAULT);
+
+/// How often and how patiently failed requests are repeated.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RetryPolicy {
+    /// Tries per request including the first, `1` to never retry
+    pub attempts: u32,
+    /// Wait before the first retry, doubled for every further one up to `max_delay`
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// Whether to wait a random part of the delay, so clients failing together don't retry together
+    pub jitter: bool,
+}
+
+impl RetryPolicy {
+    pub const DEFAULT: RetryPolicy = RetryPolicy {
+        attempts: 3,
+        initial_delay: Duration::from_millis(500),
+        max_delay: Duration::from_secs(8),
+        jitter: true,
+    };
+
+    /// The delay before a retry, without jitter.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of the retry, starting at 1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use get_flight_data::retry::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::DEFAULT;
+    /// assert_eq!(policy.backoff(1), Duration::from_millis(500));
+    /// assert_eq!(policy.backoff(3), Duration::from_secs(2));
+    /// assert_eq!(policy.backoff(10), Duration::from_secs(8));
+    /// ```
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// The delay before a retry, between half and all of the backoff if jitter is enabled.
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        if !self.jitter {
+            return backoff;
+        }
+        // Random enough to spread retries, without a dependency for it
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().subsec_nanos();
+        backoff.mul_f64(0.5 + f64::from(nanos % 1000) / 2000.0)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::DEFAULT
+    }
+}
+
+/// Sets the policy used for all further requests.
+pub fn set_policy(policy: RetryPolicy) {
+    if let Ok(mut current) = POLICY.lock() {
+        *current = policy;
+    }
+}
+
+pub(crate) fn policy() -> RetryPolicy {
+    POLICY.lock().map(|policy| *policy).unwrap_or_default()
+}
+
+/// Checks whether an error may go away by itself, i.e. a timeout, a failed connection or a 5xx status.
+/// Client errors and unexpected responses would only fail again.
+pub fn is_transient(err: &GfdError) -> bool {
+    match err {
+        GfdError::Request(err) => {
+            err.is_timeout()
+                || err.is_connect()
+                || err.status().is_some_and(|status| status.is_server_error())
+        }
+        _ => false,
+    }
+}
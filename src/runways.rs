@@ -0,0 +1,140 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::avwx::StationRunway;
+
+/// A runway of an airport as listed in the AVWX station data.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Runway {
+    /// The designators of both ends, e.g. `07L` and `25R`
+    pub idents: [String; 2],
+    /// The true headings of both ends in degrees, `None` if unknown
+    pub bearings: [Option<f64>; 2],
+    pub length_ft: Option<u32>,
+    pub width_ft: Option<u32>,
+    /// e.g. `asphalt` or `grass`
+    pub surface: Option<String>,
+}
+
+/// The reported surface wind.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Wind {
+    /// Direction the wind blows from, in degrees true, `None` if variable
+    pub direction: Option<f64>,
+    /// Speed and gusts in knots
+    pub speed: f64,
+    pub gust: Option<f64>,
+}
+
+impl Wind {
+    /// Formats the wind as in a METAR, in knots rounded to whole numbers, e.g. `240/12G25 kt`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::runways::Wind;
+    ///
+    /// let wind = Wind { direction: Some(240.0), speed: 12.4, gust: Some(25.0) };
+    /// assert_eq!(wind.describe(), "240/12G25 kt");
+    /// let variable = Wind { direction: None, speed: 3.0, gust: None };
+    /// assert_eq!(variable.describe(), "VRB/03 kt");
+    /// ```
+    pub fn describe(&self) -> String {
+        let direction = match self.direction {
+            Some(direction) => format!("{direction:03.0}"),
+            None => String::from("VRB"),
+        };
+        let gust = self.gust.map(|gust| format!("G{gust:02.0}")).unwrap_or_default();
+        format!("{direction}/{:02.0}{gust} kt", self.speed)
+    }
+}
+
+/// The wind components along and across one runway end.
+#[derive(Debug)]
+pub struct RunwayWind {
+    pub ident: String,
+    /// In knots, negative for a tailwind
+    pub headwind: f64,
+    /// In knots, positive from the right, including gusts
+    pub crosswind: f64,
+}
+
+/// Reads the runways from the `runways` array of AVWX station data.
+pub(crate) fn runways_from_json(station: &Value) -> Vec<Runway> {
+    Vec::<StationRunway>::deserialize(&station["runways"])
+        .map(|runways| runways.iter().map(StationRunway::runway).collect())
+        .unwrap_or_default()
+}
+
+/// Splits the wind into head- and crosswind components for every runway end with a known heading.
+///
+/// Crosswinds include the gusts. For a variable wind, all of it is taken as crosswind,
+/// as it may come from any side.
+///
+/// # Arguments
+///
+/// * `runways` - The runways of the airport.
+/// * `wind` - The reported surface wind.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::runways::{runway_winds, Runway, Wind};
+///
+/// let runway = Runway {
+///     idents: [String::from("07"), String::from("25")],
+///     bearings: [Some(70.0), Some(250.0)],
+///     length_ft: Some(13_123),
+///     width_ft: Some(197),
+///     surface: Some(String::from("asphalt")),
+/// };
+/// let wind = Wind { direction: Some(280.0), speed: 20.0, gust: None };
+///
+/// let components = runway_winds(&[runway], &wind);
+/// assert_eq!(components[1].ident, "25");
+/// assert_eq!(components[1].headwind.round(), 17.0);
+/// assert_eq!(components[1].crosswind.round(), 10.0);
+/// assert_eq!(components[0].headwind.round(), -17.0);
+/// ```
+pub fn runway_winds(runways: &[Runway], wind: &Wind) -> Vec<RunwayWind> {
+    runways
+        .iter()
+        .flat_map(|runway| runway.idents.iter().zip(runway.bearings))
+        .filter_map(|(ident, bearing)| {
+            let (headwind, crosswind) = wind_components(bearing?, wind);
+            Some(RunwayWind { ident: ident.clone(), headwind, crosswind })
+        })
+        .collect()
+}
+
+/// Splits the wind into head- and crosswind components for one heading, as [`runway_winds`] does.
+///
+/// # Arguments
+///
+/// * `bearing` - The runway heading in degrees, true or magnetic like the wind direction.
+/// * `wind` - The wind.
+///
+/// # Returns
+///
+/// The headwind and crosswind in knots, negative for a tailwind or a crosswind from the left.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::runways::{wind_components, Wind};
+///
+/// let wind = Wind { direction: Some(300.0), speed: 10.0, gust: Some(20.0) };
+/// let (headwind, crosswind) = wind_components(270.0, &wind);
+/// assert_eq!(headwind.round(), 9.0);
+/// assert_eq!(crosswind.round(), 10.0);
+/// ```
+pub fn wind_components(bearing: f64, wind: &Wind) -> (f64, f64) {
+    let strongest = wind.gust.unwrap_or(wind.speed).max(wind.speed);
+    match wind.direction {
+        Some(direction) => {
+            let angle = (direction - bearing).to_radians();
+            (wind.speed * angle.cos(), strongest * angle.sin())
+        }
+        None => (0.0, strongest),
+    }
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use tracing::{info, warn};
+
+use crate::json_operations::get_file_dir;
+
+const MARKER_FILE_NAME: &str = "startup_attempts";
+/// Failed starts in a row after which the app starts in safe mode.
+pub const CRASH_LIMIT: u32 = 3;
+
+/// Counts a start in the marker file, it is removed again by [`startup_succeeded`].
+///
+/// # Returns
+///
+/// The number of earlier starts in a row that never reached [`startup_succeeded`],
+/// i.e. crashed or were killed while starting.
+pub fn record_startup() -> u32 {
+    let Ok(path) = marker_path() else {
+        return 0;
+    };
+    let failed = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| content.trim().parse::<u32>().ok())
+        .unwrap_or(0);
+
+    if let Err(err) = fs::write(&path, (failed + 1).to_string()) {
+        warn!("Failed to write {MARKER_FILE_NAME}: {err}");
+    }
+    if failed > 0 {
+        warn!("{failed} failed starts in a row");
+    }
+    failed
+}
+
+/// Marks the current start as successful, resetting the count of failed starts.
+pub fn startup_succeeded() {
+    let Ok(path) = marker_path() else { return; };
+    match fs::remove_file(path) {
+        Ok(()) => info!("Startup succeeded"),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+        Err(err) => warn!("Failed to remove {MARKER_FILE_NAME}: {err}"),
+    }
+}
+
+fn marker_path() -> io::Result<PathBuf> {
+    Ok(get_file_dir()?.join(MARKER_FILE_NAME))
+}
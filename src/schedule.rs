@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveTime, TimeDelta, Utc};
+
+/// A daily period, e.g. quiet hours from 23:00 to 07:00, possibly spanning midnight.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DailyPeriod {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl DailyPeriod {
+    /// Parses a period written as `HH:MM-HH:MM`.
+    ///
+    /// # Returns
+    ///
+    /// The period, `None` if the text is empty or not a valid period.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::NaiveTime;
+    /// use get_flight_data::schedule::DailyPeriod;
+    ///
+    /// let quiet = DailyPeriod::parse("23:00-07:00").unwrap();
+    /// assert!(quiet.contains(NaiveTime::from_hms_opt(2, 30, 0).unwrap()));
+    /// assert!(!quiet.contains(NaiveTime::from_hms_opt(12, 0, 0).unwrap()));
+    /// assert_eq!(DailyPeriod::parse("23:00"), None);
+    /// ```
+    pub fn parse(text: &str) -> Option<Self> {
+        let (start, end) = text.split_once('-')?;
+        let time = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+        Some(DailyPeriod { start: time(start)?, end: time(end)? })
+    }
+
+    /// Whether a time of day is in the period, the start included and the end excluded.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            // Spans midnight
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// How often the weather is refreshed automatically in each part of the flight.
+///
+/// Before departure and near the arrival the weather is refreshed often, in cruise rarely,
+/// to save API calls while nothing depends on it.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RefreshPlan {
+    /// Interval before departure, and when nothing is known about the flight
+    pub ground: Duration,
+    pub cruise: Duration,
+    pub arrival: Duration,
+    /// Time before the ETA from which the arrival interval is used
+    pub arrival_window: TimeDelta,
+    /// Distance to the destination in nautical miles from which the arrival interval is used,
+    /// if the aircraft is read from the simulator
+    pub arrival_range_nm: f64,
+}
+
+impl RefreshPlan {
+    pub const DEFAULT: RefreshPlan = RefreshPlan {
+        ground: Duration::from_secs(5 * 60),
+        cruise: Duration::from_secs(30 * 60),
+        arrival: Duration::from_secs(5 * 60),
+        arrival_window: TimeDelta::minutes(45),
+        arrival_range_nm: 150.0,
+    };
+
+    /// The interval of a part of the flight.
+    pub fn interval(&self, phase: RefreshPhase) -> Duration {
+        match phase {
+            RefreshPhase::Ground => self.ground,
+            RefreshPhase::Cruise => self.cruise,
+            RefreshPhase::Arrival => self.arrival,
+        }
+    }
+
+    /// Tells the part of the flight, from the simulator if the aircraft is read from it,
+    /// otherwise from the times of the SimBrief flight plan.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    /// * `etd` - The planned time of departure, `None` without a flight plan.
+    /// * `eta` - The planned time of arrival, `None` without a flight plan.
+    /// * `aircraft` - Whether the aircraft is on the ground and its distance to the destination
+    ///   in nautical miles if known, `None` without a simulator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{TimeDelta, TimeZone, Utc};
+    /// use get_flight_data::schedule::{RefreshPhase, RefreshPlan};
+    ///
+    /// let plan = RefreshPlan::DEFAULT;
+    /// let etd = Utc.with_ymd_and_hms(2024, 6, 21, 9, 0, 0).unwrap();
+    /// let eta = etd + TimeDelta::hours(3);
+    /// let phase = |now| plan.phase(now, Some(etd), Some(eta), None);
+    ///
+    /// assert_eq!(phase(etd - TimeDelta::minutes(30)), RefreshPhase::Ground);
+    /// assert_eq!(phase(etd + TimeDelta::hours(1)), RefreshPhase::Cruise);
+    /// assert_eq!(phase(eta - TimeDelta::minutes(30)), RefreshPhase::Arrival);
+    /// assert_eq!(plan.phase(etd, None, None, None), RefreshPhase::Ground);
+    ///
+    /// // The simulator knows better than the schedule
+    /// let late = etd + TimeDelta::hours(1);
+    /// assert_eq!(plan.phase(late, Some(etd), Some(eta), Some((true, Some(800.0)))), RefreshPhase::Ground);
+    /// assert_eq!(plan.phase(late, Some(etd), Some(eta), Some((false, Some(120.0)))), RefreshPhase::Arrival);
+    /// ```
+    pub fn phase(
+        &self,
+        now: DateTime<Utc>,
+        etd: Option<DateTime<Utc>>,
+        eta: Option<DateTime<Utc>>,
+        aircraft: Option<(bool, Option<f64>)>,
+    ) -> RefreshPhase {
+        if let Some((on_ground, distance)) = aircraft {
+            let near = distance.is_some_and(|distance| distance <= self.arrival_range_nm);
+            return match (near, on_ground) {
+                (true, _) => RefreshPhase::Arrival,
+                (false, true) => RefreshPhase::Ground,
+                (false, false) => RefreshPhase::Cruise,
+            };
+        }
+
+        match (etd, eta) {
+            (_, Some(eta)) if now >= eta - self.arrival_window => RefreshPhase::Arrival,
+            (Some(etd), _) if now >= etd => RefreshPhase::Cruise,
+            _ => RefreshPhase::Ground,
+        }
+    }
+}
+
+impl Default for RefreshPlan {
+    fn default() -> Self {
+        RefreshPlan::DEFAULT
+    }
+}
+
+/// The parts of a flight with their own refresh interval.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RefreshPhase {
+    Ground,
+    Cruise,
+    Arrival,
+}
+
+impl RefreshPhase {
+    /// How the phase reads in the log, e.g. "in cruise".
+    pub fn name(&self) -> &'static str {
+        match self {
+            RefreshPhase::Ground => "before departure",
+            RefreshPhase::Cruise => "in cruise",
+            RefreshPhase::Arrival => "near the arrival",
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::json_operations::get_file_dir;
+
+pub const SESSION_FILE_NAME: &str = "session.json";
+
+/// How the window looked when the app was closed, restored on the next launch.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    /// Keys of the expanded sections, e.g. `flight_plan`
+    pub open_sections: BTreeSet<String>,
+    /// Vertical scroll position in points
+    pub scroll_offset: f32,
+    /// Inner size of the window in points, `None` for the default size
+    pub window_size: Option<[f32; 2]>,
+    /// Outer position of the window on the desktop in points, `None` to let the system place it
+    pub window_position: Option<[f32; 2]>,
+    /// Whether the window was maximized, size and position are those before it was
+    pub maximized: bool,
+    /// Whether the window is kept above other windows, e.g. a windowed simulator
+    pub always_on_top: bool,
+    /// Whether only the compact overlay with the key values of both airports is shown
+    pub compact: bool,
+}
+
+impl UiState {
+    /// Checks whether a section was expanded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::session::UiState;
+    ///
+    /// let mut state = UiState::default();
+    /// state.set_open("settings", true);
+    /// assert!(state.is_open("settings"));
+    /// assert!(!state.is_open("flight_plan"));
+    ///
+    /// state.set_open("settings", false);
+    /// assert!(!state.is_open("settings"));
+    /// ```
+    pub fn is_open(&self, section: &str) -> bool {
+        self.open_sections.contains(section)
+    }
+
+    /// Records whether a section is expanded.
+    pub fn set_open(&mut self, section: &str, open: bool) {
+        if open {
+            self.open_sections.insert(section.to_string());
+        } else {
+            self.open_sections.remove(section);
+        }
+    }
+
+    /// Reads the state saved by [`UiState::save`], the default one if there is none.
+    pub fn load() -> Self {
+        let Ok(content) = file_path().and_then(fs::read_to_string) else {
+            return UiState::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|err| {
+            warn!("Ignoring invalid {SESSION_FILE_NAME}: {err}");
+            UiState::default()
+        })
+    }
+
+    /// Saves the state next to `userdata.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn save(&self) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self).expect("UI state should be serializable");
+        fs::write(file_path()?, content)
+    }
+}
+
+fn file_path() -> io::Result<PathBuf> {
+    Ok(get_file_dir()?.join(SESSION_FILE_NAME))
+}
@@ -0,0 +1,191 @@
+//! The aircraft in the flight simulator, so the briefing can follow the flight.
+
+use std::sync::mpsc;
+
+use crate::logic::Position;
+use crate::{simconnect, xplane};
+
+/// Distance to the destination in nautical miles within which its weather is refreshed for the approach.
+pub const APPROACH_RANGE_NM: f64 = 100.0;
+/// Distance to the planned top of descent in nautical miles within which the weather is refreshed,
+/// so the descent is planned with the latest ATIS.
+pub const DESCENT_RANGE_NM: f64 = 20.0;
+// Distance to the destination in nautical miles within which an aircraft on the ground has arrived
+const ARRIVED_RANGE_NM: f64 = 10.0;
+
+/// The simulators the aircraft can be read from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Simulator {
+    /// Microsoft Flight Simulator, via SimConnect
+    Msfs,
+    /// X-Plane, via its UDP data output
+    XPlane,
+}
+
+impl Simulator {
+    pub const ALL: [Simulator; 2] = [Simulator::Msfs, Simulator::XPlane];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Simulator::Msfs => "MSFS",
+            Simulator::XPlane => "X-Plane",
+        }
+    }
+
+    /// Finds a simulator by its name, `None` for none or an unknown one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::sim::Simulator;
+    ///
+    /// assert_eq!(Simulator::from_name("MSFS"), Some(Simulator::Msfs));
+    /// assert_eq!(Simulator::from_name("X-Plane"), Some(Simulator::XPlane));
+    /// assert_eq!(Simulator::from_name(""), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        Simulator::ALL.into_iter().find(|simulator| simulator.name() == name.trim())
+    }
+}
+
+/// Where the aircraft is and what it does.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AircraftState {
+    pub position: Position,
+    /// Above mean sea level in feet
+    pub altitude_ft: f64,
+    pub ground_speed_kt: f64,
+    pub on_ground: bool,
+}
+
+/// The part of the flight the aircraft is in, it decides which weather matters most.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FlightPhase {
+    /// On the ground before the flight
+    Departure,
+    /// Airborne, more than [`APPROACH_RANGE_NM`] from the destination
+    Enroute,
+    /// Airborne within [`APPROACH_RANGE_NM`] of the destination
+    Approach,
+    /// On the ground at the destination
+    Arrived,
+}
+
+impl FlightPhase {
+    /// The English name, only logged.
+    pub fn name(&self) -> &'static str {
+        match self {
+            FlightPhase::Departure => "Departure",
+            FlightPhase::Enroute => "Enroute",
+            FlightPhase::Approach => "Approach",
+            FlightPhase::Arrived => "Arrived",
+        }
+    }
+
+    /// Whether the arrival weather matters more than the departure weather.
+    pub fn arrival_first(&self) -> bool {
+        matches!(self, FlightPhase::Approach | FlightPhase::Arrived)
+    }
+}
+
+/// What is heard from the simulator.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SimEvent {
+    /// The current state of the aircraft, sent about once a second
+    State(AircraftState),
+    /// The simulator is not running or was closed, with the reason
+    Disconnected(String),
+}
+
+/// Starts reading the aircraft from a simulator in the background.
+///
+/// The simulator is connected to once it runs and reconnected after it was restarted.
+/// Reading stops when the receiver is dropped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use get_flight_data::sim::{self, SimEvent, Simulator};
+///
+/// let events = sim::start(Simulator::Msfs);
+/// if let Ok(SimEvent::State(state)) = events.recv() {
+///     println!("At {:?}, {} ft", state.position, state.altitude_ft);
+/// }
+/// ```
+pub fn start(simulator: Simulator) -> mpsc::Receiver<SimEvent> {
+    match simulator {
+        Simulator::Msfs => simconnect::start(),
+        Simulator::XPlane => xplane::start(),
+    }
+}
+
+/// Checks whether the aircraft is airborne and close enough to the destination to refresh its weather.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::logic::Position;
+/// use get_flight_data::sim::{approaching, AircraftState};
+///
+/// let heathrow = Position { latitude: 51.471, longitude: -0.461 };
+/// let mut state = AircraftState {
+///     position: Position { latitude: 51.5, longitude: 1.0 },
+///     altitude_ft: 18000.0,
+///     ground_speed_kt: 380.0,
+///     on_ground: false,
+/// };
+/// assert!(approaching(&state, heathrow));
+///
+/// state.position = Position { latitude: 50.033, longitude: 8.571 };
+/// assert!(!approaching(&state, heathrow));
+/// ```
+pub fn approaching(state: &AircraftState, destination: Position) -> bool {
+    !state.on_ground && state.position.distance_nm(destination) <= APPROACH_RANGE_NM
+}
+
+/// Checks whether the aircraft is airborne and within [`DESCENT_RANGE_NM`] of the planned top of descent.
+pub fn nearing_descent(state: &AircraftState, top_of_descent: Position) -> bool {
+    !state.on_ground && state.position.distance_nm(top_of_descent) <= DESCENT_RANGE_NM
+}
+
+/// Tells the part of the flight from where the aircraft is.
+///
+/// # Arguments
+///
+/// * `state` - The current state of the aircraft.
+/// * `destination` - The position of the destination, `None` if unknown.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::logic::Position;
+/// use get_flight_data::sim::{phase, AircraftState, FlightPhase};
+///
+/// let frankfurt = Position { latitude: 50.033, longitude: 8.571 };
+/// let heathrow = Position { latitude: 51.471, longitude: -0.461 };
+/// let mut state = AircraftState {
+///     position: frankfurt,
+///     altitude_ft: 364.0,
+///     ground_speed_kt: 0.0,
+///     on_ground: true,
+/// };
+/// assert_eq!(phase(&state, Some(heathrow)), FlightPhase::Departure);
+///
+/// state.on_ground = false;
+/// assert_eq!(phase(&state, Some(heathrow)), FlightPhase::Enroute);
+///
+/// state.position = heathrow;
+/// assert_eq!(phase(&state, Some(heathrow)), FlightPhase::Approach);
+///
+/// state.on_ground = true;
+/// assert_eq!(phase(&state, Some(heathrow)), FlightPhase::Arrived);
+/// ```
+pub fn phase(state: &AircraftState, destination: Option<Position>) -> FlightPhase {
+    let distance = destination.map(|destination| state.position.distance_nm(destination));
+    match (state.on_ground, distance) {
+        (true, Some(distance)) if distance <= ARRIVED_RANGE_NM => FlightPhase::Arrived,
+        (true, _) => FlightPhase::Departure,
+        (false, Some(distance)) if distance <= APPROACH_RANGE_NM => FlightPhase::Approach,
+        (false, _) => FlightPhase::Enroute,
+    }
+}
@@ -0,0 +1,349 @@
+use std::str::FromStr;
+
+use chrono::DateTime;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use tracing::{debug, info};
+
+use crate::error::{GfdError, Result};
+use crate::logic::{FlightPlan, OfpFile, Position, ProfilePoint};
+
+/// The parts of a SimBrief OFP (`xml.fetcher.php?json=1`) we use.
+///
+/// SimBrief converts its XML to JSON, so numbers arrive as strings, empty values as `{}`
+/// and lists with a single entry as that entry. Values are kept as text and parsed where they are used.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Ofp {
+    pub fetch: Fetch,
+    /// `None` if SimBrief could not return a flight plan, `fetch` says why
+    pub origin: Option<OfpAirport>,
+    pub destination: Option<OfpAirport>,
+    #[serde(deserialize_with = "one_or_many")]
+    pub alternate: Vec<Alternate>,
+    pub general: General,
+    pub fuel: Fuel,
+    pub params: Params,
+    pub times: Times,
+    pub navlog: Navlog,
+    pub files: Files,
+}
+
+/// The outcome of the request, e.g. `Success` or `Error: Unknown UserID`.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Fetch {
+    #[serde(deserialize_with = "text")]
+    pub status: String,
+}
+
+/// The departure or arrival airport.
+#[derive(Deserialize, Debug)]
+pub struct OfpAirport {
+    #[serde(deserialize_with = "text")]
+    pub icao_code: String,
+    /// Elevation in feet
+    #[serde(default, deserialize_with = "text")]
+    pub elevation: String,
+    #[serde(default, deserialize_with = "text")]
+    pub pos_lat: String,
+    #[serde(default, deserialize_with = "text")]
+    pub pos_long: String,
+}
+
+/// A planned alternate, the code is empty if none is planned.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Alternate {
+    #[serde(deserialize_with = "text")]
+    pub icao_code: String,
+    #[serde(deserialize_with = "text")]
+    pub pos_lat: String,
+    #[serde(deserialize_with = "text")]
+    pub pos_long: String,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct General {
+    #[serde(deserialize_with = "text")]
+    pub icao_airline: String,
+    #[serde(deserialize_with = "text")]
+    pub flight_number: String,
+    #[serde(deserialize_with = "text")]
+    pub route: String,
+    /// Initial cruise altitude in feet
+    #[serde(deserialize_with = "text")]
+    pub initial_altitude: String,
+    /// True airspeed in cruise in knots
+    #[serde(deserialize_with = "text")]
+    pub cruise_tas: String,
+    #[serde(deserialize_with = "text")]
+    pub costindex: String,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Fuel {
+    /// Block fuel in the units of `params`
+    #[serde(deserialize_with = "text")]
+    pub plan_ramp: String,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Params {
+    /// `kgs` or `lbs`
+    #[serde(deserialize_with = "text")]
+    pub units: String,
+}
+
+/// Scheduled times as Unix timestamps and durations in seconds.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Times {
+    #[serde(deserialize_with = "text")]
+    pub sched_out: String,
+    #[serde(deserialize_with = "text")]
+    pub sched_in: String,
+    #[serde(deserialize_with = "text")]
+    pub est_time_enroute: String,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Navlog {
+    #[serde(deserialize_with = "one_or_many")]
+    pub fix: Vec<Fix>,
+}
+
+/// A fix of the navlog, distances are per leg.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Fix {
+    #[serde(deserialize_with = "text")]
+    pub ident: String,
+    #[serde(deserialize_with = "text")]
+    pub distance: String,
+    #[serde(deserialize_with = "text")]
+    pub altitude_feet: String,
+    #[serde(deserialize_with = "text")]
+    pub pos_lat: String,
+    #[serde(deserialize_with = "text")]
+    pub pos_long: String,
+    #[serde(deserialize_with = "text")]
+    pub track_true: String,
+    #[serde(deserialize_with = "text")]
+    pub wind_dir: String,
+    #[serde(deserialize_with = "text")]
+    pub wind_spd: String,
+}
+
+/// The files SimBrief generated, links are relative to `directory`.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Files {
+    #[serde(deserialize_with = "text")]
+    pub directory: String,
+    pub pdf: Link,
+    #[serde(deserialize_with = "one_or_many")]
+    pub file: Vec<Link>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Link {
+    /// e.g. `Microsoft Flight Simulator 2020`, empty for the PDF
+    #[serde(deserialize_with = "text")]
+    pub name: String,
+    #[serde(deserialize_with = "text")]
+    pub link: String,
+}
+
+/// Parses a SimBrief OFP into the flight plan shown in the briefing.
+/// Only the airports are required, everything else is left empty if missing.
+///
+/// # Arguments
+///
+/// * `response` - The body of the `xml.fetcher.php?json=1` response.
+///
+/// # Errors
+///
+/// Returns the SimBrief fetch status as [`GfdError::Api`] if the flight plan is missing,
+/// [`GfdError::MissingField`] if SimBrief did not explain why,
+/// or [`GfdError::InvalidJson`] if the response has an unexpected structure, e.g. an airport without code.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::error::GfdError;
+/// use get_flight_data::simbrief::parse_ofp;
+///
+/// let response = r#"{
+///     "fetch": { "status": "Success" },
+///     "origin": { "icao_code": "EDDB", "elevation": "157" },
+///     "destination": { "icao_code": "EGLL" },
+///     "alternate": {},
+///     "times": { "est_time_enroute": {} },
+///     "general": { "icao_airline": "DLH", "flight_number": "123", "initial_altitude": "36000" },
+///     "navlog": { "fix": { "ident": "TOD", "distance": "480", "pos_lat": "51.9", "pos_long": "0.7" } }
+/// }"#;
+/// let flight_plan = parse_ofp(response).unwrap();
+/// assert_eq!((flight_plan.origin.as_str(), flight_plan.destination.as_str()), ("EDDB", "EGLL"));
+/// assert_eq!(flight_plan.alternate, None);
+/// assert_eq!(flight_plan.callsign, "DLH123");
+/// assert_eq!(flight_plan.cruise_altitude, Some(36000));
+/// assert!(flight_plan.top_of_descent.is_some());
+/// assert_eq!(flight_plan.profile.len(), 2);
+///
+/// let unknown_user = parse_ofp(r#"{ "fetch": { "status": "Error: Unknown UserID" } }"#);
+/// assert!(matches!(unknown_user, Err(GfdError::Api { message, .. }) if message == "Error: Unknown UserID"));
+///
+/// let without_code = parse_ofp(r#"{ "origin": {}, "destination": { "icao_code": "EGLL" } }"#);
+/// assert!(matches!(without_code, Err(GfdError::InvalidJson { .. })));
+/// ```
+pub fn parse_ofp(response: &str) -> Result<FlightPlan> {
+    let ofp: Ofp = serde_json::from_str(response).map_err(|error| GfdError::InvalidJson {
+        source: String::from("Simbrief"),
+        error,
+    })?;
+    ofp.flight_plan()
+}
+
+impl Ofp {
+    /// Converts the OFP into the flight plan shown in the briefing, see [`parse_ofp`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the SimBrief fetch status as [`GfdError::Api`] if the airports are missing,
+    /// or [`GfdError::MissingField`] if SimBrief did not explain why.
+    pub fn flight_plan(self) -> Result<FlightPlan> {
+        let (Some(origin), Some(destination)) = (self.origin, self.destination) else {
+            // SimBrief explains problems like an unknown username in the fetch status
+            return Err(match self.fetch.status.as_str() {
+                "" => GfdError::MissingField {
+                    source: String::from("Simbrief"),
+                    field: String::from("icao_code"),
+                },
+                status => GfdError::Api {
+                    source: String::from("Simbrief"),
+                    message: status.to_string(),
+                },
+            });
+        };
+        debug!("Extracted Departure and Arrival from JSON");
+        info!("Departure: {}", origin.icao_code);
+        info!("Arrival: {}", destination.icao_code);
+
+        let fix_position = |fix: &Fix| position(&fix.pos_lat, &fix.pos_long);
+        let top_of_descent = self.navlog.fix.iter().find(|fix| fix.ident == "TOD").and_then(fix_position);
+
+        // Navlog distances are per leg, the departure itself is not a fix
+        let mut distance = 0.0;
+        let departure = ProfilePoint {
+            ident: origin.icao_code.clone(),
+            distance,
+            altitude: number(&origin.elevation).unwrap_or_default(),
+            position: position(&origin.pos_lat, &origin.pos_long),
+            track: None,
+            wind_direction: None,
+            wind_speed: None,
+        };
+        let profile = std::iter::once(departure)
+            .chain(self.navlog.fix.iter().map(|fix| {
+                distance += number::<f64>(&fix.distance).unwrap_or_default();
+                ProfilePoint {
+                    ident: fix.ident.clone(),
+                    distance,
+                    altitude: number::<f64>(&fix.altitude_feet).unwrap_or_default() as u32,
+                    position: fix_position(fix),
+                    track: number(&fix.track_true),
+                    wind_direction: number(&fix.wind_dir),
+                    wind_speed: number(&fix.wind_spd),
+                }
+            }))
+            .collect();
+
+        let directory = &self.files.directory;
+        let file = |link: &Link, name: &str| {
+            (!link.link.is_empty()).then(|| OfpFile {
+                name: name.to_string(),
+                url: format!("{directory}{}", link.link),
+            })
+        };
+        let files = file(&self.files.pdf, "PDF Document")
+            .into_iter()
+            .chain(self.files.file.iter().filter_map(|link| file(link, &link.name)))
+            .collect();
+
+        let time = |timestamp: &str| number(timestamp).and_then(|seconds| DateTime::from_timestamp(seconds, 0));
+        // The first of several alternates, an empty element if none is planned
+        let alternate = self.alternate.into_iter().find(|alternate| !alternate.icao_code.is_empty());
+        let alternate_position = alternate
+            .as_ref()
+            .and_then(|alternate| position(&alternate.pos_lat, &alternate.pos_long));
+        let alternate = alternate.map(|alternate| alternate.icao_code);
+        let general = self.general;
+
+        let flight_plan = FlightPlan {
+            alternate,
+            callsign: format!("{}{}", general.icao_airline, general.flight_number),
+            cruise_altitude: number(&general.initial_altitude),
+            cruise_speed: number(&general.cruise_tas),
+            cost_index: general.costindex,
+            block_fuel: number(&self.fuel.plan_ramp),
+            fuel_units: self.params.units,
+            etd: time(&self.times.sched_out),
+            eta: time(&self.times.sched_in),
+            enroute_time: number(&self.times.est_time_enroute),
+            origin_position: position(&origin.pos_lat, &origin.pos_long),
+            destination_position: position(&destination.pos_lat, &destination.pos_long),
+            alternate_position,
+            origin: origin.icao_code,
+            destination: destination.icao_code,
+            route: general.route,
+            top_of_descent,
+            profile,
+            files,
+        };
+        info!("Route: {}", flight_plan.route);
+
+        Ok(flight_plan)
+    }
+}
+
+fn number<T: FromStr>(text: &str) -> Option<T> {
+    text.parse().ok()
+}
+
+fn position(latitude: &str, longitude: &str) -> Option<Position> {
+    Some(Position { latitude: number(latitude)?, longitude: number(longitude)? })
+}
+
+/// Reads a value SimBrief sends as text, numbers as their digits and empty elements (`{}`) as an empty string.
+fn text<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<String, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        _ => String::new(),
+    })
+}
+
+/// Reads a list SimBrief sends as a single object if it has only one entry.
+fn one_or_many<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::Many(entries) => entries,
+        OneOrMany::One(entry) => vec![entry],
+    })
+}
@@ -0,0 +1,239 @@
+//! Reads the aircraft from Microsoft Flight Simulator via SimConnect.
+//!
+//! `SimConnect.dll` is loaded when connecting, it is found next to the executable
+//! or in the `PATH` (MSFS installs it with its SDK), so the app also runs without it.
+//! Only available on Windows.
+
+use std::sync::mpsc;
+
+use crate::sim::SimEvent;
+
+/// Starts reading the aircraft in the background, see [`crate::sim::start`].
+#[cfg(target_os = "windows")]
+pub fn start() -> mpsc::Receiver<SimEvent> {
+    use std::thread;
+    use std::time::Duration;
+
+    use tracing::{info, warn};
+
+    // Seconds between attempts to connect while MSFS is not running
+    const RECONNECT_INTERVAL: Duration = Duration::from_secs(10);
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut was_connected = false;
+        loop {
+            let reason = match windows::read(&tx, &mut was_connected) {
+                Ok(()) => String::from("MSFS was closed"),
+                Err(err) => err.to_string(),
+            };
+            if was_connected {
+                warn!("Lost SimConnect connection: {reason}");
+                was_connected = false;
+            }
+            if tx.send(SimEvent::Disconnected(reason)).is_err() {
+                info!("Stopped reading from MSFS");
+                return;
+            }
+            thread::sleep(RECONNECT_INTERVAL);
+        }
+    });
+    rx
+}
+
+/// Reports that SimConnect is not available, it only exists on Windows.
+#[cfg(not(target_os = "windows"))]
+pub fn start() -> mpsc::Receiver<SimEvent> {
+    let (tx, rx) = mpsc::channel();
+    let _ = tx.send(SimEvent::Disconnected(String::from("SimConnect is only available on Windows")));
+    rx
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::ffi::{c_char, c_void, CStr};
+    use std::io;
+    use std::ptr;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use tracing::info;
+
+    use crate::logic::Position;
+    use crate::sim::{AircraftState, SimEvent};
+
+    type Handle = *mut c_void;
+    type HResult = i32;
+
+    // Values from SimConnect.h
+    const DATATYPE_FLOAT64: u32 = 4;
+    const UNUSED: u32 = u32::MAX;
+    const OBJECT_ID_USER: u32 = 0;
+    const PERIOD_SECOND: u32 = 4;
+    const RECV_ID_EXCEPTION: u32 = 1;
+    const RECV_ID_QUIT: u32 = 3;
+    const RECV_ID_SIMOBJECT_DATA: u32 = 8;
+    // Header of SIMCONNECT_RECV_SIMOBJECT_DATA before the data, ten `DWORD`s
+    const DATA_OFFSET: usize = 40;
+
+    const DEFINITION: u32 = 0;
+    const REQUEST: u32 = 0;
+    // Simulation variables with their units, in the order they arrive
+    const VARIABLES: [(&CStr, &CStr); 5] = [
+        (c"PLANE LATITUDE", c"degrees"),
+        (c"PLANE LONGITUDE", c"degrees"),
+        (c"PLANE ALTITUDE", c"feet"),
+        (c"GROUND VELOCITY", c"knots"),
+        (c"SIM ON GROUND", c"bool"),
+    ];
+    const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    #[repr(C)]
+    struct Recv {
+        size: u32,
+        version: u32,
+        id: u32,
+    }
+
+    extern "system" {
+        fn LoadLibraryA(name: *const c_char) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *mut c_void;
+    }
+
+    type Open = unsafe extern "system" fn(*mut Handle, *const c_char, *mut c_void, u32, Handle, u32) -> HResult;
+    type Close = unsafe extern "system" fn(Handle) -> HResult;
+    type AddToDataDefinition =
+        unsafe extern "system" fn(Handle, u32, *const c_char, *const c_char, u32, f32, u32) -> HResult;
+    type RequestDataOnSimObject =
+        unsafe extern "system" fn(Handle, u32, u32, u32, u32, u32, u32, u32, u32) -> HResult;
+    type GetNextDispatch = unsafe extern "system" fn(Handle, *mut *mut Recv, *mut u32) -> HResult;
+
+    /// The functions of `SimConnect.dll`, which stays loaded once it was found.
+    struct Library {
+        open: Open,
+        close: Close,
+        add_to_data_definition: AddToDataDefinition,
+        request_data_on_sim_object: RequestDataOnSimObject,
+        get_next_dispatch: GetNextDispatch,
+    }
+
+    impl Library {
+        fn load() -> io::Result<Self> {
+            // SAFETY: The names are null-terminated and the functions have the signatures from SimConnect.h
+            unsafe {
+                let module = LoadLibraryA(c"SimConnect.dll".as_ptr());
+                if module.is_null() {
+                    return Err(io::Error::new(io::ErrorKind::NotFound, "SimConnect.dll not found"));
+                }
+                let function = |name: &CStr| {
+                    let address = GetProcAddress(module, name.as_ptr());
+                    if address.is_null() {
+                        Err(io::Error::other(format!("SimConnect.dll lacks {}", name.to_string_lossy())))
+                    } else {
+                        Ok(address)
+                    }
+                };
+                Ok(Library {
+                    open: std::mem::transmute::<*mut c_void, Open>(function(c"SimConnect_Open")?),
+                    close: std::mem::transmute::<*mut c_void, Close>(function(c"SimConnect_Close")?),
+                    add_to_data_definition: std::mem::transmute::<*mut c_void, AddToDataDefinition>(
+                        function(c"SimConnect_AddToDataDefinition")?),
+                    request_data_on_sim_object: std::mem::transmute::<*mut c_void, RequestDataOnSimObject>(
+                        function(c"SimConnect_RequestDataOnSimObject")?),
+                    get_next_dispatch: std::mem::transmute::<*mut c_void, GetNextDispatch>(
+                        function(c"SimConnect_GetNextDispatch")?),
+                })
+            }
+        }
+    }
+
+    /// An open SimConnect session, closed when dropped.
+    struct Session<'a> {
+        library: &'a Library,
+        handle: Handle,
+    }
+
+    impl Drop for Session<'_> {
+        fn drop(&mut self) {
+            // SAFETY: The handle was opened by this library and is not used afterwards
+            unsafe { (self.library.close)(self.handle) };
+        }
+    }
+
+    fn check(result: HResult, call: &str) -> io::Result<()> {
+        if result < 0 {
+            return Err(io::Error::other(format!("{call} failed with {result:#010x}")));
+        }
+        Ok(())
+    }
+
+    /// Reads the aircraft until MSFS quits or the receiver is dropped.
+    ///
+    /// `connected` is set once MSFS accepted the connection.
+    pub(super) fn read(tx: &mpsc::Sender<SimEvent>, connected: &mut bool) -> io::Result<()> {
+        let library = Library::load()?;
+
+        let mut handle: Handle = ptr::null_mut();
+        // SAFETY: All pointers are valid for the call, SimConnect copies the name
+        let result = unsafe {
+            (library.open)(&mut handle, c"Get Flight Data".as_ptr(), ptr::null_mut(), 0, ptr::null_mut(), 0)
+        };
+        // Fails while MSFS is not running
+        check(result, "SimConnect_Open")?;
+        let session = Session { library: &library, handle };
+        info!("Connected to MSFS");
+        *connected = true;
+
+        for (name, unit) in VARIABLES {
+            // SAFETY: The strings are null-terminated and copied by SimConnect
+            let result = unsafe {
+                (library.add_to_data_definition)(
+                    session.handle, DEFINITION, name.as_ptr(), unit.as_ptr(), DATATYPE_FLOAT64, 0.0, UNUSED)
+            };
+            check(result, "SimConnect_AddToDataDefinition")?;
+        }
+        // SAFETY: Plain values only
+        let result = unsafe {
+            (library.request_data_on_sim_object)(
+                session.handle, REQUEST, DEFINITION, OBJECT_ID_USER, PERIOD_SECOND, 0, 0, 0, 0)
+        };
+        check(result, "SimConnect_RequestDataOnSimObject")?;
+
+        loop {
+            let mut recv: *mut Recv = ptr::null_mut();
+            let mut size = 0;
+            // SAFETY: SimConnect points `recv` to a message of `size` bytes, valid until the next call
+            let result = unsafe { (library.get_next_dispatch)(session.handle, &mut recv, &mut size) };
+            // No message waiting
+            if result < 0 || recv.is_null() {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+
+            // SAFETY: Every message starts with the header
+            let id = unsafe { (*recv).id };
+            match id {
+                RECV_ID_QUIT => return Ok(()),
+                RECV_ID_EXCEPTION => return Err(io::Error::other("SimConnect reported an exception")),
+                RECV_ID_SIMOBJECT_DATA if size as usize >= DATA_OFFSET + VARIABLES.len() * 8 => {
+                    // SAFETY: The size was checked, the values may be unaligned
+                    let values: [f64; 5] = unsafe {
+                        let data = (recv as *const u8).add(DATA_OFFSET) as *const f64;
+                        std::array::from_fn(|index| data.add(index).read_unaligned())
+                    };
+                    let state = AircraftState {
+                        position: Position { latitude: values[0], longitude: values[1] },
+                        altitude_ft: values[2],
+                        ground_speed_kt: values[3],
+                        on_ground: values[4] != 0.0,
+                    };
+                    if tx.send(SimEvent::State(state)).is_err() {
+                        return Ok(());
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
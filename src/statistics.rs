@@ -0,0 +1,88 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::history::{MetarHistory, Observation};
+
+// The flight rules in the order they are listed, from best to worst
+const FLIGHT_RULES: [&str; 4] = ["VFR", "MVFR", "IFR", "LIFR"];
+
+/// How the weather of an airport was over a period, from the recorded METARs.
+#[derive(Debug)]
+pub struct Statistics {
+    /// Number of METARs the statistics are based on
+    pub observations: usize,
+    /// The time of the oldest of those METARs
+    pub since: Option<DateTime<Utc>>,
+    /// The flight rules and the share of METARs reporting them, from 0 to 1, best first
+    pub flight_rules: Vec<(&'static str, f64)>,
+    /// Average wind speed without gusts, in the units of the station
+    pub average_wind: Option<f64>,
+}
+
+/// Summarizes the METARs of an airport recorded in the last days.
+///
+/// METARs without a time or flight rules, recorded by older versions, are left out.
+///
+/// # Arguments
+///
+/// * `history` - The recorded METARs.
+/// * `icao` - The ICAO code of the airport.
+/// * `days` - How many days back to look.
+///
+/// # Returns
+///
+/// The statistics, `None` if no METAR of the airport was recorded in that period.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeDelta, Utc};
+/// use get_flight_data::history::{MetarHistory, Observation};
+/// use get_flight_data::statistics::statistics;
+///
+/// let mut history = MetarHistory::default();
+/// let metars = [(3, "IFR", 10.0), (2, "VFR", 6.0), (1, "VFR", 8.0), (0, "VFR", 4.0)];
+/// for (hours_ago, flight_rules, wind_speed) in metars {
+///     history.record("EGLL", Observation {
+///         raw: format!("METAR {hours_ago}"),
+///         wind_speed: Some(wind_speed),
+///         flight_rules: flight_rules.to_string(),
+///         time: Some(Utc::now() - TimeDelta::hours(hours_ago)),
+///         ..Observation::default()
+///     });
+/// }
+///
+/// let statistics = statistics(&history, "EGLL", 7).unwrap();
+/// assert_eq!(statistics.observations, 4);
+/// assert_eq!(statistics.flight_rules, [("VFR", 0.75), ("IFR", 0.25)]);
+/// assert_eq!(statistics.average_wind, Some(7.0));
+/// ```
+pub fn statistics(history: &MetarHistory, icao: &str, days: i64) -> Option<Statistics> {
+    let oldest = Utc::now() - TimeDelta::days(days);
+    let observations: Vec<&Observation> = history
+        .observations(icao)
+        .filter(|observation| observation.time.is_some_and(|time| time >= oldest))
+        .filter(|observation| !observation.flight_rules.is_empty())
+        .collect();
+    if observations.is_empty() {
+        return None;
+    }
+
+    let count = observations.len() as f64;
+    let flight_rules = FLIGHT_RULES
+        .into_iter()
+        .filter_map(|rules| {
+            let matching = observations.iter().filter(|observation| observation.flight_rules == rules).count();
+            (matching > 0).then(|| (rules, matching as f64 / count))
+        })
+        .collect();
+
+    let winds: Vec<f64> = observations.iter().filter_map(|observation| observation.wind_speed).collect();
+    let average_wind = (!winds.is_empty()).then(|| winds.iter().sum::<f64>() / winds.len() as f64);
+
+    Some(Statistics {
+        observations: observations.len(),
+        since: observations.iter().filter_map(|observation| observation.time).min(),
+        flight_rules,
+        average_wind,
+    })
+}
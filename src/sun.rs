@@ -0,0 +1,100 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::logic::Position;
+
+// Julian date of the J2000 epoch, 2000-01-01 12:00 UTC, and of the Unix epoch
+const J2000: f64 = 2_451_545.0;
+const UNIX_EPOCH: f64 = 2_440_587.5;
+// Altitudes of the sun's center at sunrise, including refraction and its radius, and at civil twilight
+const SUNRISE_ALTITUDE: f64 = -0.833;
+const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+
+/// Civil twilight, sunrise and sunset at a place on one day, `None` if the sun doesn't cross
+/// the altitude that day, e.g. during the polar night.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SunTimes {
+    pub civil_dawn: Option<DateTime<Utc>>,
+    pub sunrise: Option<DateTime<Utc>>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub civil_dusk: Option<DateTime<Utc>>,
+}
+
+/// How light it is at a time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Daylight {
+    Day,
+    Twilight,
+    Night,
+}
+
+impl SunTimes {
+    /// Whether it is day, twilight or night at a time of the same day.
+    /// With the sun always up or always down, the missing times count as day.
+    pub fn daylight_at(&self, time: DateTime<Utc>) -> Daylight {
+        let between = |start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>| match (start, end) {
+            (Some(start), Some(end)) => start <= time && time <= end,
+            _ => true,
+        };
+        if between(self.sunrise, self.sunset) {
+            Daylight::Day
+        } else if between(self.civil_dawn, self.civil_dusk) {
+            Daylight::Twilight
+        } else {
+            Daylight::Night
+        }
+    }
+}
+
+/// Calculates civil twilight, sunrise and sunset with the sunrise equation,
+/// accurate to a minute or two.
+///
+/// # Arguments
+///
+/// * `position` - The place, e.g. an airport.
+/// * `date` - The UTC date.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use get_flight_data::logic::Position;
+/// use get_flight_data::sun::sun_times;
+///
+/// let frankfurt = Position { latitude: 50.03, longitude: 8.57 };
+/// let times = sun_times(frankfurt, NaiveDate::from_ymd_opt(2024, 6, 21).unwrap());
+/// assert_eq!(times.sunrise.unwrap().format("%H:%M").to_string(), "03:16");
+/// assert_eq!(times.sunset.unwrap().format("%H:%M").to_string(), "19:38");
+///
+/// let svalbard = Position { latitude: 78.25, longitude: 15.47 };
+/// assert_eq!(sun_times(svalbard, NaiveDate::from_ymd_opt(2024, 6, 21).unwrap()).sunrise, None);
+/// ```
+pub fn sun_times(position: Position, date: NaiveDate) -> SunTimes {
+    let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).expect("J2000 should be a valid date");
+    let days = (date - epoch).num_days() as f64;
+
+    // Mean solar noon, east longitudes are earlier
+    let mean_noon = days - position.longitude / 360.0;
+    let anomaly = (357.5291 + 0.985_600_28 * mean_noon).rem_euclid(360.0).to_radians();
+    let center = 1.9148 * anomaly.sin() + 0.02 * (2.0 * anomaly).sin() + 0.0003 * (3.0 * anomaly).sin();
+    let ecliptic_longitude = (anomaly.to_degrees() + center + 180.0 + 102.9372).rem_euclid(360.0).to_radians();
+    let transit = J2000 + mean_noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic_longitude).sin();
+    let declination = (ecliptic_longitude.sin() * 23.4397_f64.to_radians().sin()).asin();
+
+    let latitude = position.latitude.to_radians();
+    let crossing = |altitude: f64, sign: f64| {
+        let cos_hour_angle = (altitude.to_radians().sin() - latitude.sin() * declination.sin())
+            / (latitude.cos() * declination.cos());
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            return None;
+        }
+        let julian = transit + sign * cos_hour_angle.acos().to_degrees() / 360.0;
+        DateTime::from_timestamp(((julian - UNIX_EPOCH) * 86_400.0).round() as i64, 0)
+    };
+
+    SunTimes {
+        civil_dawn: crossing(CIVIL_TWILIGHT_ALTITUDE, -1.0),
+        sunrise: crossing(SUNRISE_ALTITUDE, -1.0),
+        sunset: crossing(SUNRISE_ALTITUDE, 1.0),
+        civil_dusk: crossing(CIVIL_TWILIGHT_ALTITUDE, 1.0),
+    }
+}
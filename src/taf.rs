@@ -0,0 +1,267 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::avwx::{Cloud, WxCode};
+use crate::error::{GfdError, Result};
+use crate::logic::{endpoints, parse_json, ApiClient, ApiKey};
+
+/// The parts of an AVWX TAF we use.
+#[derive(Deserialize)]
+pub struct Taf {
+    pub raw: String,
+    pub forecast: Vec<ForecastGroup>,
+}
+
+/// One group of a TAF, e.g. `TEMPO 1618/1622 BKN004`.
+#[derive(Deserialize)]
+pub struct ForecastGroup {
+    /// `FROM`, `BECMG`, `TEMPO`, `PROB` or `INTER`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub raw: String,
+    #[serde(default)]
+    pub flight_rules: String,
+    pub probability: Option<Probability>,
+    pub start_time: Option<Timestamp>,
+    pub end_time: Option<Timestamp>,
+    #[serde(default)]
+    pub clouds: Vec<Cloud>,
+    #[serde(default)]
+    pub wx_codes: Vec<WxCode>,
+}
+
+#[derive(Deserialize)]
+pub struct Probability {
+    pub value: Option<u8>,
+}
+
+#[derive(Deserialize)]
+pub struct Timestamp {
+    pub dt: DateTime<Utc>,
+}
+
+impl ForecastGroup {
+    /// Whether the group is valid at the given time, start and end included.
+    fn spans(&self, time: DateTime<Utc>) -> bool {
+        match (&self.start_time, &self.end_time) {
+            (Some(start), Some(end)) => start.dt <= time && time <= end.dt,
+            _ => false,
+        }
+    }
+
+    /// Whether the group has begun at the given time, e.g. a `BECMG` group that is in effect since.
+    fn started(&self, time: DateTime<Utc>) -> bool {
+        self.start_time.as_ref().is_some_and(|start| start.dt <= time)
+    }
+}
+
+/// The conditions a TAF expects at a given time, leaving out temporary changes.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Forecast {
+    pub time: DateTime<Utc>,
+    /// e.g. `IFR`, empty if the TAF doesn't say
+    pub flight_rules: String,
+    /// The lowest broken or overcast layer, e.g. `BKN004`, `None` without a ceiling
+    pub ceiling: Option<String>,
+    /// Precipitation and obscurations, e.g. `-RA`
+    pub weather: Vec<String>,
+}
+
+impl Forecast {
+    /// The flight rules and ceiling, e.g. `IFR, BKN004`.
+    pub fn conditions(&self) -> String {
+        let rules = if self.flight_rules.is_empty() { "-" } else { self.flight_rules.as_str() };
+        match self.ceiling {
+            Some(ref ceiling) => format!("{rules}, {ceiling}"),
+            None => rules.to_string(),
+        }
+    }
+}
+
+/// Fetches the current TAF of an airport from AVWX.
+///
+/// # Arguments
+///
+/// * `client` - Sends the request, [`HttpClient`](crate::logic::HttpClient) for the real API.
+/// * `icao` - The ICAO code of the airport.
+/// * `token` - The AVWX API token.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or AVWX does not return a TAF,
+/// e.g. because the airport doesn't publish one.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::logic::{ApiKey, HttpClient, Source};
+///
+/// let token = ApiKey::new(Source::Avwx, "my-avwx-token");
+/// let taf = get_flight_data::taf::fetch_taf(&HttpClient, "EGLL", &token).await?;
+/// println!("{}", taf.raw);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_taf(client: &impl ApiClient, icao: &str, token: &ApiKey) -> Result<Taf> {
+    let avwx_uri = format!("{}taf/{icao}", endpoints().avwx);
+
+    debug!("Calling avwx API for TAF of {icao}");
+    let response = client.get_authorized(&avwx_uri, token).await?;
+    debug!("Got TAF of {icao} as JSON");
+
+    let json = parse_json(&response, &format!("TAF of {icao}"))?;
+    if let Some(message) = json["error"].as_str() {
+        return Err(GfdError::Api {
+            source: String::from("AVWX"),
+            message: message.to_string(),
+        });
+    }
+
+    serde_json::from_value(json).map_err(|error| GfdError::InvalidJson {
+        source: format!("TAF of {icao}"),
+        error,
+    })
+}
+
+/// Finds the conditions a TAF expects at a given time.
+///
+/// Those of the `FROM` group valid at the time, changed by the `BECMG` groups that began since.
+/// `TEMPO` and `PROB` groups are left out, see [`risks_at`] for them.
+///
+/// # Arguments
+///
+/// * `taf` - The TAF of the airport.
+/// * `time` - The time of interest, usually the ETA.
+///
+/// # Returns
+///
+/// The forecast, `None` if the TAF isn't valid at the time.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::taf::{forecast_at, Taf};
+///
+/// let taf: Taf = serde_json::from_str(r#"{
+///     "raw": "TAF EHAM 251700Z 2518/2624 20015KT 9999 SCT030 BECMG 2520/2522 4000 BKN004",
+///     "forecast": [{
+///         "type": "FROM",
+///         "raw": "2518/2624 20015KT 9999 SCT030",
+///         "flight_rules": "VFR",
+///         "start_time": { "dt": "2024-02-25T18:00:00Z" },
+///         "end_time": { "dt": "2024-02-27T00:00:00Z" },
+///         "clouds": [{ "type": "SCT", "altitude": 30 }]
+///     }, {
+///         "type": "BECMG",
+///         "raw": "BECMG 2520/2522 4000 BKN004",
+///         "flight_rules": "IFR",
+///         "start_time": { "dt": "2024-02-25T20:00:00Z" },
+///         "end_time": { "dt": "2024-02-25T22:00:00Z" },
+///         "clouds": [{ "type": "BKN", "altitude": 4 }]
+///     }]
+/// }"#).unwrap();
+///
+/// let early = forecast_at(&taf, "2024-02-25T19:00:00Z".parse().unwrap()).unwrap();
+/// assert_eq!(early.conditions(), "VFR");
+/// let eta = forecast_at(&taf, "2024-02-25T23:40:00Z".parse().unwrap()).unwrap();
+/// assert_eq!(eta.conditions(), "IFR, BKN004");
+/// assert!(forecast_at(&taf, "2024-02-27T06:00:00Z".parse().unwrap()).is_none());
+/// ```
+pub fn forecast_at(taf: &Taf, time: DateTime<Utc>) -> Option<Forecast> {
+    let start = taf.forecast.iter().rposition(|group| group.kind == "FROM" && group.spans(time))?;
+    let prevailing = std::iter::once(&taf.forecast[start]).chain(
+        taf.forecast[start + 1..].iter().filter(|group| group.kind == "BECMG" && group.started(time)));
+
+    // A change group only repeats what changes, the rest carries over
+    let mut forecast = Forecast { time, flight_rules: String::new(), ceiling: None, weather: Vec::new() };
+    for group in prevailing {
+        if !group.flight_rules.is_empty() {
+            forecast.flight_rules = group.flight_rules.clone();
+        }
+        if !group.clouds.is_empty() {
+            forecast.ceiling = group.clouds
+                .iter()
+                .filter(|cloud| matches!(cloud.kind.as_str(), "BKN" | "OVC" | "VV"))
+                .filter_map(|cloud| cloud.altitude.map(|altitude| (altitude, &cloud.kind)))
+                .min()
+                .map(|(altitude, kind)| format!("{kind}{altitude:03}"));
+        }
+        if !group.wx_codes.is_empty() {
+            forecast.weather = group.wx_codes.iter().map(|code| code.repr.clone()).collect();
+        }
+    }
+    Some(forecast)
+}
+
+/// A change group of a TAF that is valid at a given time, see [`risks_at`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Risk {
+    /// The group as written, e.g. `PROB30 TEMPO 1618/1622 BKN004`
+    pub raw: String,
+    /// e.g. `LIFR`, empty if the TAF doesn't say
+    pub flight_rules: String,
+    pub likelihood: Likelihood,
+}
+
+/// How likely the conditions of a change group are.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Likelihood {
+    /// A group with a probability in percent, e.g. `PROB30`
+    Probability(u8),
+    /// A `BECMG` group
+    Expected,
+    /// A `TEMPO` or `INTER` group without a probability
+    Temporary,
+}
+
+/// Finds the change groups of a TAF that are valid at a given time.
+///
+/// `PROB` groups give their probability, `TEMPO` and `INTER` groups without one
+/// are a temporary risk, `BECMG` groups are expected.
+///
+/// # Arguments
+///
+/// * `taf` - The TAF of the airport.
+/// * `time` - The time of interest, usually the ETA.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::taf::{risks_at, Likelihood, Taf};
+///
+/// let taf: Taf = serde_json::from_str(r#"{
+///     "raw": "TAF EGLL 161100Z 1612/1718 24010KT 9999 SCT030 PROB30 TEMPO 1618/1622 BKN004",
+///     "forecast": [{
+///         "type": "TEMPO",
+///         "raw": "PROB30 TEMPO 1618/1622 BKN004",
+///         "flight_rules": "LIFR",
+///         "probability": { "value": 30 },
+///         "start_time": { "dt": "2024-03-16T18:00:00Z" },
+///         "end_time": { "dt": "2024-03-16T22:00:00Z" }
+///     }]
+/// }"#).unwrap();
+///
+/// let eta = "2024-03-16T19:30:00Z".parse().unwrap();
+/// let risks = risks_at(&taf, eta);
+/// assert_eq!(risks[0].raw, "PROB30 TEMPO 1618/1622 BKN004");
+/// assert_eq!(risks[0].flight_rules, "LIFR");
+/// assert_eq!(risks[0].likelihood, Likelihood::Probability(30));
+/// ```
+pub fn risks_at(taf: &Taf, time: DateTime<Utc>) -> Vec<Risk> {
+    taf.forecast
+        .iter()
+        .filter(|group| matches!(group.kind.as_str(), "BECMG" | "TEMPO" | "PROB" | "INTER"))
+        .filter(|group| group.spans(time))
+        .map(|group| {
+            let probability = group.probability.as_ref().and_then(|probability| probability.value);
+            let likelihood = match (probability, group.kind.as_str()) {
+                (Some(percent), _) => Likelihood::Probability(percent),
+                (None, "BECMG") => Likelihood::Expected,
+                (None, _) => Likelihood::Temporary,
+            };
+            Risk { raw: group.raw.clone(), flight_rules: group.flight_rules.clone(), likelihood }
+        })
+        .collect()
+}
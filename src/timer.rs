@@ -0,0 +1,127 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// An event of a flight recorded by the [`FlightTimer`], in the order they happen.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Event {
+    OffBlock,
+    Takeoff,
+    Landing,
+    OnBlock,
+}
+
+impl Event {
+    /// The English name, for the log and the summary of times in the exported briefing.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::OffBlock => "Off block",
+            Event::Takeoff => "Takeoff",
+            Event::Landing => "Landing",
+            Event::OnBlock => "On block",
+        }
+    }
+}
+
+/// Records the block and flight times of one flight, started by hand.
+#[derive(Clone, Default, Debug)]
+pub struct FlightTimer {
+    pub off_block: Option<DateTime<Utc>>,
+    pub takeoff: Option<DateTime<Utc>>,
+    pub landing: Option<DateTime<Utc>>,
+    pub on_block: Option<DateTime<Utc>>,
+}
+
+impl FlightTimer {
+    /// The event to record next, `None` once the flight is on block.
+    pub fn next_event(&self) -> Option<Event> {
+        if self.off_block.is_none() {
+            Some(Event::OffBlock)
+        } else if self.takeoff.is_none() {
+            Some(Event::Takeoff)
+        } else if self.landing.is_none() {
+            Some(Event::Landing)
+        } else if self.on_block.is_none() {
+            Some(Event::OnBlock)
+        } else {
+            None
+        }
+    }
+
+    /// Records the next event at the given time, does nothing once the flight is on block.
+    pub fn record(&mut self, time: DateTime<Utc>) {
+        let slot = match self.next_event() {
+            Some(Event::OffBlock) => &mut self.off_block,
+            Some(Event::Takeoff) => &mut self.takeoff,
+            Some(Event::Landing) => &mut self.landing,
+            Some(Event::OnBlock) => &mut self.on_block,
+            None => return,
+        };
+        *slot = Some(time);
+    }
+
+    /// Whether the timer was started and the flight is not on block yet.
+    pub fn is_running(&self) -> bool {
+        self.off_block.is_some() && self.on_block.is_none()
+    }
+
+    /// The time since off block, up to on block or `now` while still running.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{TimeDelta, TimeZone, Utc};
+    /// use get_flight_data::timer::FlightTimer;
+    ///
+    /// let time = |hour, minute| Utc.with_ymd_and_hms(2024, 3, 1, hour, minute, 0).unwrap();
+    /// let mut timer = FlightTimer::default();
+    /// timer.record(time(10, 0));
+    /// timer.record(time(10, 15));
+    ///
+    /// assert_eq!(timer.block_time(time(11, 0)), Some(TimeDelta::hours(1)));
+    /// assert_eq!(timer.flight_time(time(11, 0)), Some(TimeDelta::minutes(45)));
+    /// ```
+    pub fn block_time(&self, now: DateTime<Utc>) -> Option<TimeDelta> {
+        Some(self.on_block.unwrap_or(now) - self.off_block?)
+    }
+
+    /// The time since takeoff, up to landing or `now` while still airborne.
+    pub fn flight_time(&self, now: DateTime<Utc>) -> Option<TimeDelta> {
+        Some(self.landing.unwrap_or(now) - self.takeoff?)
+    }
+
+    /// Lists the recorded events with their times and the block and flight time, one per line.
+    pub fn summary(&self, now: DateTime<Utc>) -> String {
+        let events = [
+            (Event::OffBlock, self.off_block),
+            (Event::Takeoff, self.takeoff),
+            (Event::Landing, self.landing),
+            (Event::OnBlock, self.on_block),
+        ];
+        let mut lines: Vec<String> = events
+            .into_iter()
+            .filter_map(|(event, time)| Some(format!("{}: {}", event.name(), time?.format("%H:%Mz"))))
+            .collect();
+
+        if let Some(block_time) = self.block_time(now) {
+            lines.push(format!("Block time: {}", format_duration(block_time)));
+        }
+        if let Some(flight_time) = self.flight_time(now) {
+            lines.push(format!("Flight time: {}", format_duration(flight_time)));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Formats a duration as hours and minutes, e.g. `1:05`.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::TimeDelta;
+/// use get_flight_data::timer::format_duration;
+///
+/// assert_eq!(format_duration(TimeDelta::minutes(65)), "1:05");
+/// ```
+pub fn format_duration(duration: TimeDelta) -> String {
+    let minutes = duration.num_minutes().max(0);
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
@@ -0,0 +1,140 @@
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::error::{GfdError, Result};
+use crate::logic::{http_client, parse_json, ApiKey, Atis};
+
+const DEEPL_FREE_URI: &str = "https://api-free.deepl.com/v2/translate";
+const DEEPL_PRO_URI: &str = "https://api.deepl.com/v2/translate";
+
+// Frequent in ATIS of non-English speaking countries, but not in English ones.
+// German, French, Spanish, Italian and Portuguese
+const FOREIGN_WORDS: [&str; 30] = [
+    "HIER", "IST", "UND", "BAHN", "WOLKEN", "SICHT", "TAUPUNKT",
+    "ICI", "VENT", "NUAGES", "VISIBILITE", "PISTE", "DEGRES",
+    "AQUI", "PISTA", "VIENTO", "NUBES", "VISIBILIDAD", "INFORMACION", "TEMPERATURA",
+    "QUI", "VENTO", "NUBI", "VISIBILITA", "INFORMAZIONE",
+    "NUVENS", "VISIBILIDADE", "INFORMACAO", "CEU", "ORVALHO",
+];
+
+#[derive(Deserialize)]
+struct DeeplResponse {
+    translations: Vec<DeeplTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeeplTranslation {
+    text: String,
+}
+
+/// Finds the lines of an ATIS that are probably not in English.
+///
+/// A line counts as non-English if it contains a non-ASCII letter
+/// or at least two words that are common in non-English ATIS.
+///
+/// # Arguments
+///
+/// * `atis` - The ATIS text.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::translation::non_english_lines;
+///
+/// let atis = "HIER IST FRANKFURT INFORMATION K\nTHIS IS FRANKFURT INFORMATION K";
+/// assert_eq!(non_english_lines(atis), ["HIER IST FRANKFURT INFORMATION K"]);
+/// assert!(non_english_lines("Visibilité 10 km").len() == 1);
+/// ```
+pub fn non_english_lines(atis: &str) -> Vec<&str> {
+    atis.lines()
+        .filter(|line| {
+            let non_ascii = line.chars().any(|c| c.is_alphabetic() && !c.is_ascii());
+            let foreign_words = line
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| FOREIGN_WORDS.contains(&word.to_uppercase().as_str()))
+                .count();
+            non_ascii || foreign_words >= 2
+        })
+        .collect()
+}
+
+/// Translates text to English with DeepL.
+///
+/// # Arguments
+///
+/// * `text` - The text to translate, the source language is detected by DeepL.
+/// * `api_key` - A DeepL API key, free keys end with `:fx`.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or DeepL does not return a translation,
+/// e.g. because the key is invalid or the quota is used up.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::logic::{ApiKey, Source};
+///
+/// let api_key = ApiKey::new(Source::DeepL, "my-key:fx");
+/// let english = get_flight_data::translation::translate("Piste 25 in Betrieb", &api_key).await?;
+/// println!("{english}");
+/// # Ok(())
+/// # }
+/// ```
+pub async fn translate(text: &str, api_key: &ApiKey) -> Result<String> {
+    let uri = if api_key.key.ends_with(":fx") { DEEPL_FREE_URI } else { DEEPL_PRO_URI };
+
+    debug!("Calling DeepL API");
+    let response = http_client()?
+        .post(uri)
+        .header(api_key.auth.header.trim(), api_key.auth.value(&api_key.key))
+        .form(&[("text", text), ("target_lang", "EN")])
+        .send()
+        .await?;
+
+    // DeepL explains errors only with the status, e.g. 403 for an invalid key
+    let status = response.status();
+    if !status.is_success() {
+        return Err(GfdError::Api {
+            source: String::from("DeepL"),
+            message: status.to_string(),
+        });
+    }
+    let body = response.text().await?;
+    debug!("Got translation from DeepL");
+
+    let json = parse_json(&body, "DeepL")?;
+    let response: DeeplResponse = serde_json::from_value(json).map_err(|error| GfdError::InvalidJson {
+        source: String::from("DeepL"),
+        error,
+    })?;
+
+    response.translations
+        .into_iter()
+        .next()
+        .map(|translation| translation.text)
+        .ok_or_else(|| GfdError::MissingField {
+            source: String::from("DeepL"),
+            field: String::from("translations"),
+        })
+}
+
+/// Translates the non-English lines of an ATIS and stores the result in it.
+/// A failed translation is only logged, the original ATIS is still useful.
+///
+/// # Arguments
+///
+/// * `atis` - The ATIS to translate.
+/// * `api_key` - A DeepL API key.
+pub async fn translate_atis(atis: &mut Atis, api_key: &ApiKey) {
+    let lines = non_english_lines(&atis.text);
+    if lines.is_empty() {
+        return;
+    }
+
+    match translate(&lines.join("\n"), api_key).await {
+        Ok(translation) => atis.translation = Some(translation),
+        Err(err) => warn!("Failed to translate ATIS: {err}"),
+    }
+}
@@ -0,0 +1,99 @@
+//! The tray icon of the app, so it can keep polling while hidden behind a full-screen simulator.
+//! Only available on Windows and macOS, Linux would need GTK.
+
+use std::sync::mpsc;
+
+use eframe::egui;
+use tray_icon::menu::{Menu, MenuEvent, MenuItem};
+use tray_icon::{Icon, MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+use get_flight_data::i18n::tr;
+
+/// What the user picked in the tray.
+pub enum TrayAction {
+    Show,
+    Refresh,
+    Quit,
+}
+
+pub struct Tray {
+    // The icon is removed from the tray when dropped
+    _icon: TrayIcon,
+    actions: mpsc::Receiver<TrayAction>,
+}
+
+impl Tray {
+    /// Adds the icon with its menu to the tray.
+    /// Must be called on the main thread once the event loop is running.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the menu or icon can't be created.
+    pub fn new(ctx: &egui::Context) -> Result<Self, String> {
+        let show = MenuItem::new(tr("tray-show"), true, None);
+        let refresh = MenuItem::new(tr("tray-refresh"), true, None);
+        let quit = MenuItem::new(tr("tray-quit"), true, None);
+        let menu = Menu::new();
+        menu.append_items(&[&show, &refresh, &quit]).map_err(|err| err.to_string())?;
+
+        // The handlers wake up the UI, which does not paint on its own while hidden
+        let (tx, rx) = mpsc::channel();
+        let menu_tx = tx.clone();
+        let menu_ctx = ctx.clone();
+        let (show_id, refresh_id) = (show.id().clone(), refresh.id().clone());
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            let action = if event.id == show_id {
+                TrayAction::Show
+            } else if event.id == refresh_id {
+                TrayAction::Refresh
+            } else {
+                TrayAction::Quit
+            };
+            let _ = menu_tx.send(action);
+            menu_ctx.request_repaint();
+        }));
+
+        let icon_ctx = ctx.clone();
+        TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event {
+                let _ = tx.send(TrayAction::Show);
+                icon_ctx.request_repaint();
+            }
+        }));
+
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip("Get Flight Data")
+            .with_icon(icon()?)
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        Ok(Self { _icon: icon, actions: rx })
+    }
+
+    /// Returns the actions picked since the last call.
+    pub fn actions(&self) -> mpsc::TryIter<'_, TrayAction> {
+        self.actions.try_iter()
+    }
+}
+
+/// Draws a sky blue dot, the app has no icon file.
+fn icon() -> Result<Icon, String> {
+    const SIZE: u32 = 32;
+    let center = (SIZE as f32 - 1.0) / 2.0;
+
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let distance = (x as f32 - center).hypot(y as f32 - center);
+            let alpha = if distance <= center { 255 } else { 0 };
+            rgba.extend_from_slice(&[0x4a, 0xa8, 0xe8, alpha]);
+        }
+    }
+
+    Icon::from_rgba(rgba, SIZE, SIZE).map_err(|err| err.to_string())
+}
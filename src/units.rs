@@ -0,0 +1,151 @@
+pub const KNOTS_PER_MPS: f64 = 1.943_844;
+const LBS_PER_KG: f64 = 2.204_623;
+const METERS_PER_FOOT: f64 = 0.3048;
+const KMH_PER_KNOT: f64 = 1.852;
+// The standard atmosphere at sea level, and the rules of thumb for the lowest few thousand feet
+const STANDARD_PRESSURE_HPA: f64 = 1013.25;
+const STANDARD_TEMPERATURE_C: f64 = 15.0;
+const HPA_PER_INHG: f64 = 33.8639;
+const FEET_PER_HPA: f64 = 1000.0 / HPA_PER_INHG;
+const LAPSE_RATE_PER_1000_FT: f64 = 1.98;
+const FEET_PER_DEGREE: f64 = 118.8;
+
+/// Formats an altimeter setting as in a METAR, `Q` with hectopascals or `A` with inches of mercury.
+///
+/// The unit is told from the value, as the stations report either.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::units::format_altimeter;
+///
+/// assert_eq!(format_altimeter(1013.0), "Q1013");
+/// assert_eq!(format_altimeter(29.92), "A29.92");
+/// ```
+pub fn format_altimeter(value: f64) -> String {
+    // No altimeter setting in inches of mercury gets anywhere close to 100
+    if value < 100.0 {
+        format!("A{value:.2}")
+    } else {
+        format!("Q{value:.0}")
+    }
+}
+
+/// Formats how an altimeter setting changed, with its sign and unit, e.g. `-2 hPa` or `+0.03 inHg`.
+///
+/// The unit is told from the value like in [`format_altimeter`].
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::units::format_altimeter_change;
+///
+/// assert_eq!(format_altimeter_change(1015.0, 1013.0), "-2 hPa");
+/// assert_eq!(format_altimeter_change(29.92, 29.95), "+0.03 inHg");
+/// assert_eq!(format_altimeter_change(1013.0, 1013.0), "±0 hPa");
+/// ```
+pub fn format_altimeter_change(previous: f64, current: f64) -> String {
+    let (decimals, unit) = if current < 100.0 { (2, "inHg") } else { (0, "hPa") };
+    let change = current - previous;
+    // Differences below the reported precision are rounding noise
+    if change.abs() < 0.5 / 10_f64.powi(decimals as i32) {
+        format!("±0 {unit}")
+    } else {
+        format!("{change:+.decimals$} {unit}")
+    }
+}
+
+/// Calculates the density altitude of an airport with the rules of thumb of pilot training.
+///
+/// The pressure altitude changes by about 30 ft per hPa of QNH below or above standard,
+/// and the density altitude by about 120 ft per °C the temperature is above or below ISA.
+///
+/// # Arguments
+///
+/// * `elevation_ft` - The field elevation.
+/// * `qnh` - The altimeter setting, in hectopascals or inches of mercury like in [`format_altimeter`].
+/// * `temperature` - The outside air temperature in °C.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::units::density_altitude;
+///
+/// // Standard day at sea level
+/// assert_eq!(density_altitude(0.0, 1013.25, 15.0).round(), 0.0);
+/// // Denver on a hot summer afternoon
+/// assert_eq!(density_altitude(5434.0, 30.12, 32.0).round(), 8486.0);
+/// ```
+pub fn density_altitude(elevation_ft: f64, qnh: f64, temperature: f64) -> f64 {
+    let qnh_hpa = if qnh < 100.0 { qnh * HPA_PER_INHG } else { qnh };
+    let pressure_altitude = elevation_ft + (STANDARD_PRESSURE_HPA - qnh_hpa) * FEET_PER_HPA;
+    let isa_temperature = STANDARD_TEMPERATURE_C - LAPSE_RATE_PER_1000_FT * pressure_altitude / 1000.0;
+    pressure_altitude + FEET_PER_DEGREE * (temperature - isa_temperature)
+}
+
+/// A pair of units the Tools section converts between, in both directions.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Conversion {
+    Weight,
+    Length,
+    Temperature,
+    Speed,
+}
+
+impl Conversion {
+    pub const ALL: [Conversion; 4] = [
+        Conversion::Weight,
+        Conversion::Length,
+        Conversion::Temperature,
+        Conversion::Speed,
+    ];
+
+    /// The units converted from and to, e.g. `("kg", "lbs")`.
+    pub fn units(&self) -> (&'static str, &'static str) {
+        match self {
+            Conversion::Weight => ("kg", "lbs"),
+            Conversion::Length => ("ft", "m"),
+            Conversion::Temperature => ("°C", "°F"),
+            Conversion::Speed => ("kt", "km/h"),
+        }
+    }
+
+    /// Converts a value from the first unit to the second.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::units::Conversion;
+    ///
+    /// assert_eq!(Conversion::Temperature.forward(15.0), 59.0);
+    /// assert_eq!(Conversion::Length.forward(1000.0).round(), 305.0);
+    /// assert_eq!(Conversion::Speed.forward(100.0).round(), 185.0);
+    /// ```
+    pub fn forward(&self, value: f64) -> f64 {
+        match self {
+            Conversion::Weight => value * LBS_PER_KG,
+            Conversion::Length => value * METERS_PER_FOOT,
+            Conversion::Temperature => value * 9.0 / 5.0 + 32.0,
+            Conversion::Speed => value * KMH_PER_KNOT,
+        }
+    }
+
+    /// Converts a value from the second unit back to the first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use get_flight_data::units::Conversion;
+    ///
+    /// assert_eq!(Conversion::Temperature.backward(212.0), 100.0);
+    /// assert_eq!(Conversion::Weight.backward(1000.0).round(), 454.0);
+    /// ```
+    pub fn backward(&self, value: f64) -> f64 {
+        match self {
+            Conversion::Weight => value / LBS_PER_KG,
+            Conversion::Length => value / METERS_PER_FOOT,
+            Conversion::Temperature => (value - 32.0) * 5.0 / 9.0,
+            Conversion::Speed => value / KMH_PER_KNOT,
+        }
+    }
+}
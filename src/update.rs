@@ -0,0 +1,88 @@
+//! Checks GitHub for a newer release, so users of old builds learn about fixed endpoints and features.
+
+use tracing::{debug, info};
+
+use crate::error::{GfdError, Result};
+use crate::logic::{parse_json, send_request, truncate, MAX_LOGGED_RESPONSE};
+
+const RELEASES_URI: &str = "https://api.github.com/repos/HaDeSMonsta/get_flight_data/releases/latest";
+
+/// The version of this build, e.g. `0.1.0`.
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A published release of the app.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Release {
+    /// The version without a leading `v`, e.g. `0.2.0`
+    pub version: String,
+    /// The release page with the notes and downloads
+    pub url: String,
+}
+
+/// Fetches the latest release and returns it if it is newer than this build.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response lacks the tag or page of the release.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// if let Some(release) = get_flight_data::update::check().await? {
+///     println!("Version {} is available at {}", release.version, release.url);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn check() -> Result<Option<Release>> {
+    debug!("Checking for a new release");
+    let response = send_request(RELEASES_URI).await?;
+    debug!("Raw latest release: {}", truncate(&response, MAX_LOGGED_RESPONSE));
+    let json = parse_json(&response, "GitHub release")?;
+
+    let field = |field: &str| json[field].as_str().map(str::to_string).ok_or_else(|| GfdError::MissingField {
+        source: String::from("GitHub release"),
+        field: field.to_string(),
+    });
+    let release = Release {
+        version: field("tag_name")?.trim().trim_start_matches('v').to_string(),
+        url: field("html_url")?,
+    };
+
+    if is_newer(&release.version, CURRENT_VERSION) {
+        info!("Version {} is available, this is {CURRENT_VERSION}", release.version);
+        Ok(Some(release))
+    } else {
+        debug!("Version {CURRENT_VERSION} is up to date");
+        Ok(None)
+    }
+}
+
+/// Compares two versions of the form `major.minor.patch`, a leading `v` is ignored.
+///
+/// Pre-release suffixes like `-beta.1` are ignored, so they never count as newer than their release.
+/// Versions that can't be read are never newer.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::update::is_newer;
+///
+/// assert!(is_newer("v0.2.0", "0.1.0"));
+/// assert!(is_newer("0.10.0", "0.9.3"));
+/// assert!(!is_newer("0.1.0", "0.1.0"));
+/// assert!(!is_newer("0.1.0-beta.1", "0.1.0"));
+/// assert!(!is_newer("nightly", "0.1.0"));
+/// ```
+pub fn is_newer(version: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Option<Vec<u64>> {
+        let version = version.trim().trim_start_matches('v');
+        let release = version.split(['-', '+']).next()?;
+        release.split('.').map(|part| part.parse().ok()).collect()
+    };
+    match (parse(version), parse(current)) {
+        (Some(version), Some(current)) => version > current,
+        _ => false,
+    }
+}
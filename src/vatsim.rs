@@ -0,0 +1,412 @@
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use crate::error::{GfdError, Result};
+use crate::logic::{self, atis_letter, endpoints, preferred_atis, ApiClient, HttpClient};
+
+/// The parts of the VATSIM datafeed (v3) we use.
+#[derive(Deserialize, Default)]
+pub struct Datafeed {
+    pub controllers: Vec<Controller>,
+    pub atis: Vec<Atis>,
+    pub pilots: Vec<Pilot>,
+}
+
+/// A controller entry of the VATSIM datafeed.
+#[derive(Deserialize)]
+pub struct Controller {
+    pub callsign: String,
+    pub frequency: String,
+    pub facility: u8,
+}
+
+/// An ATIS entry of the VATSIM datafeed.
+#[derive(Deserialize)]
+pub struct Atis {
+    pub callsign: String,
+    pub frequency: String,
+    /// The information letter, `None` if the controller hasn't set one
+    #[serde(default)]
+    pub atis_code: Option<String>,
+    /// The lines of the ATIS, `None` while it is being set up
+    #[serde(default)]
+    pub text_atis: Option<Vec<String>>,
+}
+
+/// A connected pilot of the VATSIM datafeed.
+#[derive(Deserialize)]
+pub struct Pilot {
+    pub callsign: String,
+    /// `None` if the pilot hasn't filed a flight plan
+    pub flight_plan: Option<PilotFlightPlan>,
+}
+
+/// The filed flight plan of a pilot.
+#[derive(Deserialize)]
+pub struct PilotFlightPlan {
+    pub departure: String,
+    pub arrival: String,
+}
+
+/// How many connected pilots filed a flight plan to or from an airport.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Traffic {
+    pub inbound: usize,
+    pub outbound: usize,
+}
+
+/// The kind of an ATC position, ordered from the ground up.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Facility {
+    Delivery,
+    Ground,
+    Tower,
+    Approach,
+    Center,
+}
+
+/// An online ATC station relevant for an airport.
+#[derive(Clone)]
+pub struct Station {
+    pub callsign: String,
+    pub frequency: String,
+    pub facility: Facility,
+    /// `true` if the station was only matched by the region heuristic
+    /// and may not actually cover the airport
+    pub estimated: bool,
+}
+
+/// Who is providing top-down service for an airport.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Coverage {
+    Tower,
+    ApproachOnly,
+    CenterOnly,
+    Unicom,
+}
+
+impl Facility {
+    /// Maps the numeric facility of the datafeed, `None` for observers and FSS.
+    fn from_datafeed(facility: u8) -> Option<Self> {
+        match facility {
+            2 => Some(Facility::Delivery),
+            3 => Some(Facility::Ground),
+            4 => Some(Facility::Tower),
+            5 => Some(Facility::Approach),
+            6 => Some(Facility::Center),
+            _ => None,
+        }
+    }
+}
+
+/// Downloads the current VATSIM datafeed.
+/// It is one big document, so it is reused like any response within the TTL of the [`crate::cache`].
+///
+/// # Arguments
+///
+/// * `client` - Sends the request, [`HttpClient`](crate::logic::HttpClient) for the real API.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response is not a valid datafeed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::logic::HttpClient;
+///
+/// let datafeed = get_flight_data::vatsim::fetch_datafeed(&HttpClient).await?;
+/// println!("{} controllers online", datafeed.controllers.len());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_datafeed(client: &impl ApiClient) -> Result<Datafeed> {
+    debug!("Calling Vatsim datafeed");
+    let response = client.get(&endpoints().vatsim_datafeed).await?;
+    debug!("Got Vatsim datafeed");
+
+    serde_json::from_str(&response).map_err(|error| GfdError::InvalidJson {
+        source: String::from("Vatsim datafeed"),
+        error,
+    })
+}
+
+/// Checks whether an airport has at least one ATIS online (`EDDF_ATIS`, `EDDF_D_ATIS`, ...).
+///
+/// # Arguments
+///
+/// * `datafeed` - The current VATSIM datafeed.
+/// * `icao` - The ICAO code of the airport.
+pub fn has_atis(datafeed: &Datafeed, icao: &str) -> bool {
+    let icao = icao.trim().to_uppercase();
+    datafeed.atis
+        .iter()
+        .any(|atis| atis.callsign.split('_').next() == Some(icao.as_str()))
+}
+
+/// Finds the ATIS of an airport in the datafeed, preferring the departure or arrival ATIS
+/// as described in [`preferred_atis`].
+///
+/// # Arguments
+///
+/// * `datafeed` - The current VATSIM datafeed.
+/// * `icao` - The ICAO code of the airport.
+/// * `departure` - Whether the departure ATIS is wanted, otherwise the arrival ATIS.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::vatsim::{atis_for_airport, Datafeed};
+///
+/// let datafeed: Datafeed = serde_json::from_str(r#"{
+///     "controllers": [],
+///     "pilots": [],
+///     "atis": [
+///         { "callsign": "EDDF_A_ATIS", "frequency": "118.025", "atis_code": "K",
+///           "text_atis": ["FRANKFURT INFORMATION KILO", "LANDING RUNWAY 25L"] },
+///         { "callsign": "EDDF_D_ATIS", "frequency": "118.725", "atis_code": null, "text_atis": null }
+///     ]
+/// }"#).unwrap();
+///
+/// let arrival = atis_for_airport(&datafeed, "EDDF", false);
+/// assert_eq!(arrival.text, "FRANKFURT INFORMATION KILO\nLANDING RUNWAY 25L");
+/// assert_eq!(arrival.letter, Some('K'));
+///
+/// // Not set up yet, the other one is better than none
+/// assert_eq!(atis_for_airport(&datafeed, "EDDF", true).letter, None);
+/// assert_eq!(atis_for_airport(&datafeed, "EGLL", true).text, "No vatsim ATIS available");
+/// ```
+pub fn atis_for_airport(datafeed: &Datafeed, icao: &str, departure: bool) -> logic::Atis {
+    let icao = icao.trim().to_uppercase();
+    let stations: Vec<&Atis> = datafeed.atis
+        .iter()
+        .filter(|atis| atis.callsign.split('_').next() == Some(icao.as_str()))
+        .collect();
+    let callsigns: Vec<&str> = stations.iter().map(|atis| atis.callsign.as_str()).collect();
+
+    let Some(station) = preferred_atis(&callsigns, departure).map(|index| stations[index]) else {
+        return logic::Atis { text: String::from("No vatsim ATIS available"), letter: None, translation: None };
+    };
+
+    let text = station.text_atis.as_deref().unwrap_or_default().join("\n");
+    let letter = station.atis_code
+        .as_deref()
+        .and_then(|code| code.trim().chars().next())
+        .filter(char::is_ascii_alphabetic)
+        .map(|letter| letter.to_ascii_uppercase())
+        .or_else(|| atis_letter(&text));
+    info!("Information letter of {}: {letter:?}", station.callsign);
+
+    logic::Atis { text, letter, translation: None }
+}
+
+/// Fetches the datafeed and checks whether an airport has an ATIS online.
+/// Used to watch for an ATIS without calling the ATIS or METAR APIs.
+///
+/// # Errors
+///
+/// Returns an error if the datafeed can't be fetched.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// if get_flight_data::vatsim::atis_online("EHAM").await? {
+///     println!("EHAM ATIS is online");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn atis_online(icao: &str) -> Result<bool> {
+    let datafeed = fetch_datafeed(&HttpClient).await?;
+    let online = has_atis(&datafeed, icao);
+    info!("ATIS of {icao} online: {online}");
+    Ok(online)
+}
+
+/// Collects all online stations covering an airport, sorted from DEL to CTR.
+///
+/// Local positions (DEL/GND/TWR/APP) are matched by their callsign prefix,
+/// which is either the ICAO code (`EDDF_TWR`) or, in the US,
+/// the code without the leading `K` (`JFK_TWR`).
+/// The datafeed contains no sector boundaries, so CTR stations are matched
+/// by their FIR prefix sharing the ICAO region (`ED` for `EDGG_CTR` and `EDDF`)
+/// and marked as estimated.
+///
+/// # Arguments
+///
+/// * `datafeed` - The current VATSIM datafeed.
+/// * `icao` - The ICAO code of the airport.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::logic::HttpClient;
+/// use get_flight_data::vatsim::{fetch_datafeed, stations_for_airport};
+///
+/// let datafeed = fetch_datafeed(&HttpClient).await?;
+/// let stations = stations_for_airport(&datafeed, "EDDF");
+/// for station in stations {
+///     println!("{} {}", station.callsign, station.frequency);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn stations_for_airport(datafeed: &Datafeed, icao: &str) -> Vec<Station> {
+    let icao = icao.trim().to_uppercase();
+    let us_code = icao.strip_prefix('K').filter(|_| icao.len() == 4);
+    let region = icao.get(..2).unwrap_or_default();
+
+    let mut stations: Vec<Station> = datafeed.controllers
+        .iter()
+        .filter_map(|controller| {
+            let facility = Facility::from_datafeed(controller.facility)?;
+            let prefix = controller.callsign.split('_').next().unwrap_or_default();
+
+            let estimated = match facility {
+                Facility::Center => {
+                    if prefix.len() != 4 || region.is_empty() || !prefix.starts_with(region) {
+                        return None;
+                    }
+                    true
+                }
+                _ => {
+                    if prefix != icao && Some(prefix) != us_code {
+                        return None;
+                    }
+                    false
+                }
+            };
+
+            Some(Station {
+                callsign: controller.callsign.clone(),
+                frequency: controller.frequency.clone(),
+                facility,
+                estimated,
+            })
+        })
+        .collect();
+
+    stations.sort_by(|a, b| a.facility.cmp(&b.facility)
+        .then_with(|| a.callsign.cmp(&b.callsign)));
+
+    info!("Found {} online stations for {icao}", stations.len());
+
+    stations
+}
+
+/// Counts the connected pilots flying to and from an airport, according to their flight plans.
+///
+/// # Arguments
+///
+/// * `datafeed` - The current VATSIM datafeed.
+/// * `icao` - The ICAO code of the airport.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::vatsim::{traffic, Datafeed, Traffic};
+///
+/// let datafeed: Datafeed = serde_json::from_str(r#"{
+///     "controllers": [],
+///     "atis": [],
+///     "pilots": [
+///         { "callsign": "DLH1", "flight_plan": { "departure": "EDDF", "arrival": "LOWW" } },
+///         { "callsign": "AUA2", "flight_plan": { "departure": "LOWW", "arrival": "EDDF" } },
+///         { "callsign": "DLH3", "flight_plan": { "departure": "EDDM", "arrival": "EDDF" } },
+///         { "callsign": "DEABC", "flight_plan": null }
+///     ]
+/// }"#).unwrap();
+///
+/// assert_eq!(traffic(&datafeed, "EDDF"), Traffic { inbound: 2, outbound: 1 });
+/// ```
+pub fn traffic(datafeed: &Datafeed, icao: &str) -> Traffic {
+    let icao = icao.trim().to_uppercase();
+    let flight_plans = datafeed.pilots.iter().filter_map(|pilot| pilot.flight_plan.as_ref());
+
+    let mut traffic = Traffic::default();
+    for flight_plan in flight_plans {
+        if flight_plan.arrival == icao {
+            traffic.inbound += 1;
+        }
+        if flight_plan.departure == icao {
+            traffic.outbound += 1;
+        }
+    }
+
+    info!("Traffic at {icao}: {} inbound, {} outbound", traffic.inbound, traffic.outbound);
+    traffic
+}
+
+/// Determines the top-down coverage of an airport from its online stations.
+/// The highest ranking local position wins, DEL and GND alone don't cover the airspace.
+///
+/// # Arguments
+///
+/// * `stations` - The online stations of the airport, as returned by [`stations_for_airport`].
+pub fn coverage(stations: &[Station]) -> Coverage {
+    let online = |facility| stations.iter().any(|station| station.facility == facility);
+
+    if online(Facility::Tower) {
+        Coverage::Tower
+    } else if online(Facility::Approach) {
+        Coverage::ApproachOnly
+    } else if online(Facility::Center) {
+        Coverage::CenterOnly
+    } else {
+        Coverage::Unicom
+    }
+}
+
+/// A station that logged on or off between two refreshes, by callsign.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum StationChange {
+    LoggedOn(String),
+    LoggedOff(String),
+}
+
+/// Compares two station lists of the same airport and finds who logged on or off.
+///
+/// # Arguments
+///
+/// * `old` - The stations of the previous refresh.
+/// * `new` - The stations of the current refresh.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::vatsim::{station_changes, Facility, Station, StationChange};
+///
+/// let station = |callsign: &str, facility| Station {
+///     callsign: callsign.to_string(),
+///     frequency: String::from("118.100"),
+///     facility,
+///     estimated: false,
+/// };
+/// let previous = [station("EHAM_APP", Facility::Approach)];
+/// let current = [station("EHAM_TWR", Facility::Tower)];
+///
+/// let changes = station_changes(&previous, &current);
+/// assert_eq!(changes, [
+///     StationChange::LoggedOn(String::from("EHAM_TWR")),
+///     StationChange::LoggedOff(String::from("EHAM_APP")),
+/// ]);
+/// ```
+pub fn station_changes(old: &[Station], new: &[Station]) -> Vec<StationChange> {
+    let contains = |stations: &[Station], callsign: &str| stations
+        .iter()
+        .any(|station| station.callsign == callsign);
+
+    let logged_on = new
+        .iter()
+        .filter(|station| !contains(old, &station.callsign))
+        .map(|station| StationChange::LoggedOn(station.callsign.clone()));
+    let logged_off = old
+        .iter()
+        .filter(|station| !contains(new, &station.callsign))
+        .map(|station| StationChange::LoggedOff(station.callsign.clone()));
+
+    logged_on.chain(logged_off).collect()
+}
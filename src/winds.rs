@@ -0,0 +1,292 @@
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use reqwest::Url;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::error::{GfdError, Result};
+use crate::logic::{parse_json, send_request, FlightPlan, Position, ProfilePoint};
+
+const FORECAST_URI: &str = "https://api.open-meteo.com/v1/forecast";
+// The pressure levels Open-Meteo forecasts, from the ground up
+const PRESSURE_LEVELS: [u16; 15] = [
+    1000, 975, 950, 925, 900, 850, 800, 700, 600, 500, 400, 300, 250, 200, 150,
+];
+
+/// The forecast wind and temperature at cruise level over one point of the route.
+pub struct WindAloft {
+    /// e.g. `Departure EDDF` or `Top of descent`
+    pub point: String,
+    pub position: Position,
+    /// The pressure level closest to the cruise altitude, in hPa
+    pub pressure_level: u16,
+    /// Direction the wind blows from, in degrees true
+    pub direction: u16,
+    /// Speed in knots
+    pub speed: u16,
+    /// Temperature in °C
+    pub temperature: i16,
+}
+
+/// Converts an altitude to the closest pressure level with a forecast,
+/// using the standard atmosphere.
+///
+/// # Arguments
+///
+/// * `altitude` - The pressure altitude in feet.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::winds::pressure_level;
+///
+/// assert_eq!(pressure_level(35_000), 250);
+/// assert_eq!(pressure_level(39_000), 200);
+/// assert_eq!(pressure_level(10_000), 700);
+/// ```
+pub fn pressure_level(altitude: u32) -> u16 {
+    // The tropopause of the standard atmosphere
+    const TROPOPAUSE: f64 = 36_089.0;
+
+    let altitude = f64::from(altitude);
+    let pressure = if altitude <= TROPOPAUSE {
+        1013.25 * (1.0 - 6.8756e-6 * altitude).powf(5.2559)
+    } else {
+        226.32 * (-(altitude - TROPOPAUSE) / 20_806.0).exp()
+    };
+
+    PRESSURE_LEVELS
+        .into_iter()
+        .min_by(|a, b| (f64::from(*a) - pressure).abs().total_cmp(&(f64::from(*b) - pressure).abs()))
+        .unwrap_or(PRESSURE_LEVELS[0])
+}
+
+/// Fetches the forecast winds and temperatures at the cruise level of a flight plan
+/// over departure, top of climb, the middle of the cruise, top of descent and destination from Open-Meteo.
+///
+/// The forecasts up to the middle of the cruise are for the scheduled departure,
+/// the others for the scheduled arrival.
+/// Points without a position in the flight plan are left out.
+///
+/// # Errors
+///
+/// Returns an error if the flight plan has no cruise altitude,
+/// the request fails or the response can't be parsed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # async fn example() -> get_flight_data::error::Result<()> {
+/// use get_flight_data::logic::{update_fp, HttpClient};
+/// use get_flight_data::winds::fetch_enroute_winds;
+///
+/// let flight_plan = update_fp(&HttpClient).await?;
+/// for wind in fetch_enroute_winds(&flight_plan).await? {
+///     println!("{}: {:03}/{}kt {}°C", wind.point, wind.direction, wind.speed, wind.temperature);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_enroute_winds(flight_plan: &FlightPlan) -> Result<Vec<WindAloft>> {
+    let altitude = flight_plan.cruise_altitude.ok_or_else(|| GfdError::MissingField {
+        source: String::from("SimBrief"),
+        field: String::from("initial_altitude"),
+    })?;
+    let level = pressure_level(altitude);
+
+    // The cruise between top of climb and descent, for comparing with the planned winds
+    let fix = |ident: &str| flight_plan.profile.iter().find(|fix| fix.ident == ident);
+    let top_of_climb = fix("TOC");
+    let midway = top_of_climb.zip(fix("TOD")).and_then(|(climb, descent)| {
+        let halfway = (climb.distance + descent.distance) / 2.0;
+        flight_plan.profile
+            .iter()
+            .filter(|fix| fix.distance > climb.distance && fix.distance < descent.distance)
+            .min_by(|a, b| (a.distance - halfway).abs().total_cmp(&(b.distance - halfway).abs()))
+    });
+
+    let points: Vec<(String, Position, Option<DateTime<Utc>>)> = [
+        (format!("Departure {}", flight_plan.origin), flight_plan.origin_position, flight_plan.etd),
+        (String::from("Top of climb"), top_of_climb.and_then(|fix| fix.position), flight_plan.etd),
+        (midway.map_or_else(String::new, |fix| format!("Midway {}", fix.ident)),
+         midway.and_then(|fix| fix.position),
+         flight_plan.etd),
+        (String::from("Top of descent"), flight_plan.top_of_descent, flight_plan.eta),
+        (format!("Destination {}", flight_plan.destination),
+         flight_plan.destination_position,
+         flight_plan.eta),
+    ]
+        .into_iter()
+        .filter_map(|(point, position, time)| Some((point, position?, time)))
+        .collect();
+    if points.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let coordinates = |coordinate: fn(&Position) -> f64| points
+        .iter()
+        .map(|(_, position, _)| coordinate(position).to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let variables = format!("wind_speed_{level}hPa,wind_direction_{level}hPa,temperature_{level}hPa");
+    let uri = Url::parse_with_params(FORECAST_URI, &[
+        ("latitude", coordinates(|position| position.latitude).as_str()),
+        ("longitude", coordinates(|position| position.longitude).as_str()),
+        ("hourly", variables.as_str()),
+        ("wind_speed_unit", "kn"),
+        ("forecast_days", "3"),
+        ("timezone", "GMT"),
+    ]).expect("Open-Meteo URI should be valid");
+
+    debug!("Calling Open-Meteo for winds at {level} hPa");
+    let response = send_request(uri.as_str()).await?;
+    debug!("Got winds aloft");
+
+    let json = parse_json(&response, "Open-Meteo")?;
+    if let Some(reason) = json["reason"].as_str() {
+        return Err(GfdError::Api { source: String::from("Open-Meteo"), message: reason.to_string() });
+    }
+    // Several points are answered with one forecast each, a single point without the array
+    let forecasts = match json {
+        Value::Array(forecasts) => forecasts,
+        forecast => vec![forecast],
+    };
+
+    points
+        .into_iter()
+        .zip(&forecasts)
+        .map(|((point, position, time), forecast)| {
+            let (direction, speed, temperature) = wind_at(forecast, level, time.unwrap_or_else(Utc::now))
+                .ok_or_else(|| GfdError::MissingField {
+                    source: String::from("Open-Meteo"),
+                    field: format!("wind_speed_{level}hPa"),
+                })?;
+            debug!("Wind at {point}: {direction:03}/{speed}kt {temperature}°C");
+            Ok(WindAloft { point, position, pressure_level: level, direction, speed, temperature })
+        })
+        .collect()
+}
+
+/// The planned and forecast wind along the track at one point of the cruise.
+#[derive(Debug)]
+pub struct WindDeviation {
+    /// The point of the forecast, e.g. `Top of descent`
+    pub point: String,
+    /// The wind along the track in knots, positive for a tailwind
+    pub planned: f64,
+    pub forecast: f64,
+}
+
+impl WindDeviation {
+    /// How much more tailwind the forecast has than planned, negative for more headwind.
+    pub fn difference(&self) -> f64 {
+        self.forecast - self.planned
+    }
+}
+
+/// Compares the forecast winds aloft with the winds the flight plan was planned with.
+///
+/// Each forecast is compared at the cruise fix of the navlog closest to it,
+/// forecasts far from any cruise fix, like departure and destination, are left out.
+///
+/// # Arguments
+///
+/// * `flight_plan` - The flight plan with its navlog.
+/// * `winds` - The forecast winds aloft, as returned by [`fetch_enroute_winds`].
+pub fn compare_with_plan(flight_plan: &FlightPlan, winds: &[WindAloft]) -> Vec<WindDeviation> {
+    // Closer than this in degrees of latitude and longitude, about 30 NM
+    const NEAR: f64 = 0.5;
+
+    let cruise_floor = flight_plan.cruise_altitude.unwrap_or_default().saturating_sub(2000);
+    let cruise: Vec<&ProfilePoint> = flight_plan.profile
+        .iter()
+        .filter(|fix| fix.altitude >= cruise_floor && fix.altitude > 0)
+        .collect();
+
+    winds
+        .iter()
+        .filter_map(|wind| {
+            let (fix, distance) = cruise
+                .iter()
+                .filter_map(|fix| {
+                    let position = fix.position?;
+                    let distance = (position.latitude - wind.position.latitude)
+                        .hypot(position.longitude - wind.position.longitude);
+                    Some((fix, distance))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+            if distance > NEAR {
+                return None;
+            }
+
+            let track = fix.track?;
+            let planned = tailwind(fix.wind_direction?, fix.wind_speed?, track);
+            let forecast = tailwind(f64::from(wind.direction), f64::from(wind.speed), track);
+            Some(WindDeviation { point: wind.point.clone(), planned, forecast })
+        })
+        .collect()
+}
+
+/// Estimates the arrival with the forecast winds instead of the planned ones,
+/// assuming the average difference applies to the whole flight.
+///
+/// # Returns
+///
+/// The new estimated arrival, `None` if the flight plan lacks the times or distance.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::winds::{revised_eta, WindDeviation};
+///
+/// let deviation = WindDeviation { point: String::from("Top of descent"), planned: 20.0, forecast: -20.0 };
+/// // 900 NM in two hours planned with 450 kt, 410 kt with the forecast winds
+/// let eta = "2024-03-16T12:00:00Z".parse().unwrap();
+/// let revised = revised_eta(eta, 2 * 3600, 900.0, &[deviation]).unwrap();
+/// assert_eq!(revised.format("%H:%M").to_string(), "12:11");
+/// ```
+pub fn revised_eta(
+    eta: DateTime<Utc>,
+    enroute_time: u32,
+    distance: f64,
+    deviations: &[WindDeviation],
+) -> Option<DateTime<Utc>> {
+    if deviations.is_empty() || enroute_time == 0 {
+        return None;
+    }
+    let difference = deviations.iter().map(WindDeviation::difference).sum::<f64>() / deviations.len() as f64;
+
+    let planned_speed = distance / (f64::from(enroute_time) / 3600.0);
+    let forecast_speed = planned_speed + difference;
+    if forecast_speed <= 0.0 {
+        return None;
+    }
+    let delay = f64::from(enroute_time) * (planned_speed / forecast_speed - 1.0);
+    Some(eta + TimeDelta::seconds(delay.round() as i64))
+}
+
+/// The wind component along a track in knots, positive for a tailwind.
+fn tailwind(direction: f64, speed: f64, track: f64) -> f64 {
+    -speed * (direction - track).to_radians().cos()
+}
+
+/// Reads the forecast hour closest to a time from an hourly Open-Meteo forecast.
+fn wind_at(forecast: &Value, level: u16, time: DateTime<Utc>) -> Option<(u16, u16, i16)> {
+    let hourly = &forecast["hourly"];
+    let index = hourly["time"]
+        .as_array()?
+        .iter()
+        .enumerate()
+        .filter_map(|(index, hour)| {
+            let hour = NaiveDateTime::parse_from_str(hour.as_str()?, "%Y-%m-%dT%H:%M").ok()?;
+            Some((index, (hour.and_utc() - time).num_minutes().abs()))
+        })
+        .min_by_key(|(_, distance)| *distance)?
+        .0;
+
+    let value = |variable: &str| hourly[format!("{variable}_{level}hPa")][index].as_f64();
+    let direction = value("wind_direction")?.round() as u16 % 360;
+    let speed = value("wind_speed")?.round() as u16;
+    let temperature = value("temperature")?.round() as i16;
+
+    Some((direction, speed, temperature))
+}
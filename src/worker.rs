@@ -0,0 +1,196 @@
+//! One long-lived worker for refreshes and flight plan reloads.
+//!
+//! The UI sends [`Command`]s and receives [`WorkerEvent`]s, so all of its requests run in one place.
+//! At most one refresh, one refresh of the monitored airports and one flight plan reload run at a time,
+//! a new command cancels the running one of the same kind.
+
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use tokio::runtime::Handle;
+use tokio::task::AbortHandle;
+use tracing::{debug, info, warn};
+
+use crate::error::Result;
+use crate::logic::{self, AirportData, ApiClient, FlightPlan, LoadItem, LoadState, Providers};
+
+/// What the worker is asked to do.
+#[derive(Clone, Debug)]
+pub enum Command {
+    /// Loads METAR, ATIS and ATC of both airports
+    RefreshWeather { departure: String, arrival: String, providers: Providers },
+    /// Loads METAR and ATIS of the airports monitored besides departure and arrival
+    RefreshAirports { icaos: Vec<String>, providers: Providers },
+    /// Loads the latest SimBrief flight plan of a user
+    ReloadFlightPlan { username: String },
+    /// Cancels the running requests and stops the worker
+    Shutdown,
+}
+
+/// The progress or result of a request, with the id [`Worker::send`] returned for it.
+// Only a few are sent per request, so the size of the weather doesn't matter
+#[allow(clippy::large_enum_variant)]
+pub enum WorkerEvent {
+    /// A part of a refresh finished, the result follows once all are done
+    Progress { id: u64, item: LoadItem, state: LoadState },
+    Weather { id: u64, result: Result<(AirportData, AirportData)> },
+    /// The monitored airports in the order they were sent, each with its data or why it failed
+    Airports { id: u64, icaos: Vec<String>, result: Result<Vec<Result<AirportData>>> },
+    FlightPlan { id: u64, result: Result<FlightPlan> },
+    /// The request ended without a result, e.g. because it panicked
+    Lost { id: u64 },
+}
+
+impl WorkerEvent {
+    pub fn id(&self) -> u64 {
+        match self {
+            WorkerEvent::Progress { id, .. }
+            | WorkerEvent::Weather { id, .. }
+            | WorkerEvent::Airports { id, .. }
+            | WorkerEvent::FlightPlan { id, .. }
+            | WorkerEvent::Lost { id } => *id,
+        }
+    }
+}
+
+/// The sending end of the worker, it stops when this is dropped.
+pub struct Worker {
+    commands: mpsc::Sender<(u64, Command)>,
+    events: mpsc::Receiver<WorkerEvent>,
+    last_id: u64,
+}
+
+impl Worker {
+    /// Starts the worker thread, its requests run on the given runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Sends the requests, [`HttpClient`](logic::HttpClient) for the real APIs.
+    /// * `runtime` - The runtime the requests are spawned on.
+    /// * `notify` - Called after each event, e.g. to wake up the UI.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use get_flight_data::logic::HttpClient;
+    /// use get_flight_data::worker::{Command, Worker, WorkerEvent};
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let mut worker = Worker::start(HttpClient, runtime.handle().clone(), || ());
+    /// let id = worker.send(Command::ReloadFlightPlan { username: String::from("my-simbrief-name") });
+    /// if let Some(WorkerEvent::FlightPlan { id: event_id, result }) = worker.recv() {
+    ///     assert_eq!(event_id, id);
+    ///     println!("{:?}", result.map(|flight_plan| flight_plan.origin));
+    /// }
+    /// ```
+    pub fn start<C, N>(client: C, runtime: Handle, notify: N) -> Self
+    where
+        C: ApiClient + Send + 'static,
+        N: Fn() + Send + Sync + 'static,
+    {
+        let (commands, command_rx) = mpsc::channel();
+        let (event_tx, events) = mpsc::channel();
+        let client = Arc::new(client);
+        let notify = Arc::new(notify);
+
+        thread::spawn(move || {
+            // A new command only makes the result of the running one of the same kind outdated
+            let mut weather: Option<AbortHandle> = None;
+            let mut airports: Option<AbortHandle> = None;
+            let mut flight_plan: Option<AbortHandle> = None;
+            for (id, command) in command_rx {
+                debug!("Worker got request {id}: {command:?}");
+
+                let client = Arc::clone(&client);
+                let (request, running) = match command {
+                    Command::RefreshWeather { departure, arrival, providers } => {
+                        let (event_tx, notify) = (event_tx.clone(), Arc::clone(&notify));
+                        let request = runtime.spawn(async move {
+                            let progress = |item, state| {
+                                let _ = event_tx.send(WorkerEvent::Progress { id, item, state });
+                                notify();
+                            };
+                            let result = logic::fetch_data_with_progress(
+                                &*client, &departure, &arrival, &providers, &progress).await;
+                            WorkerEvent::Weather { id, result }
+                        });
+                        (request, &mut weather)
+                    }
+                    Command::RefreshAirports { icaos, providers } => {
+                        let request = runtime.spawn(async move {
+                            let result = logic::fetch_airports(&*client, &icaos, &providers).await;
+                            WorkerEvent::Airports { id, icaos, result }
+                        });
+                        (request, &mut airports)
+                    }
+                    Command::ReloadFlightPlan { username } => {
+                        let request = runtime.spawn(async move {
+                            let result = logic::fetch_ofp(&*client, &username).await;
+                            WorkerEvent::FlightPlan { id, result }
+                        });
+                        (request, &mut flight_plan)
+                    }
+                    Command::Shutdown => break,
+                };
+                if let Some(outdated) = running.replace(request.abort_handle()) {
+                    outdated.abort();
+                }
+
+                let (event_tx, notify) = (event_tx.clone(), Arc::clone(&notify));
+                runtime.spawn(async move {
+                    let event = match request.await {
+                        Ok(event) => event,
+                        Err(err) if err.is_cancelled() => return,
+                        Err(err) => {
+                            warn!("Request {id} ended without a result: {err}");
+                            WorkerEvent::Lost { id }
+                        }
+                    };
+                    // It's fine if nobody listens anymore
+                    let _ = event_tx.send(event);
+                    notify();
+                });
+            }
+            for request in [weather, airports, flight_plan].into_iter().flatten() {
+                request.abort();
+            }
+            info!("Worker stopped");
+        });
+
+        Worker { commands, events, last_id: 0 }
+    }
+
+    /// Hands a command to the worker, cancelling the running request of the same kind.
+    ///
+    /// # Returns
+    ///
+    /// The id the result of the command will carry.
+    pub fn send(&mut self, command: Command) -> u64 {
+        self.last_id += 1;
+        if self.commands.send((self.last_id, command)).is_err() {
+            warn!("The worker has stopped, request {} is dropped", self.last_id);
+        }
+        self.last_id
+    }
+
+    /// Returns the next event without waiting, `None` if there is none yet.
+    pub fn try_recv(&self) -> Option<WorkerEvent> {
+        self.events.try_recv().ok()
+    }
+
+    /// Waits for the next event, `None` if the worker has stopped.
+    pub fn recv(&self) -> Option<WorkerEvent> {
+        self.events.recv().ok()
+    }
+
+    /// Cancels the running requests and stops the worker.
+    pub fn shutdown(&self) {
+        let _ = self.commands.send((self.last_id, Command::Shutdown));
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,132 @@
+//! Reads the aircraft from the UDP data output of X-Plane.
+//!
+//! In X-Plane, open Settings → Data Output, tick "Network via UDP" for
+//! "Speeds" (index 3) and "Latitude, longitude & altitude" (index 20),
+//! and send the data to the IP address of this computer on port [`PORT`].
+
+use std::io;
+use std::net::UdpSocket;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::logic::Position;
+use crate::sim::{AircraftState, SimEvent};
+
+/// The port data output is received on, the one X-Plane suggests for it.
+pub const PORT: u16 = 49003;
+
+// The data output rows we use, the index X-Plane lists them with
+const SPEEDS: i32 = 3;
+const POSITION: i32 = 20;
+// Seconds without data after which X-Plane is considered closed
+const TIMEOUT: Duration = Duration::from_secs(5);
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+// Height above ground in feet below which the aircraft is taken to be on the ground
+const ON_GROUND_AGL: f32 = 10.0;
+
+/// Starts receiving the aircraft in the background, see [`crate::sim::start`].
+pub fn start() -> mpsc::Receiver<SimEvent> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || loop {
+        let reason = match receive(&tx) {
+            Ok(()) => return,
+            Err(err) => err,
+        };
+        if tx.send(SimEvent::Disconnected(format!("Can't receive data on port {PORT}: {reason}"))).is_err() {
+            return;
+        }
+        thread::sleep(RETRY_INTERVAL);
+    });
+    rx
+}
+
+/// Receives data until the receiver is dropped.
+fn receive(tx: &mpsc::Sender<SimEvent>) -> io::Result<()> {
+    // X-Plane may run on another computer
+    let socket = UdpSocket::bind(("0.0.0.0", PORT))?;
+    socket.set_read_timeout(Some(TIMEOUT))?;
+    info!("Listening for X-Plane data on port {PORT}");
+
+    let mut buffer = [0; 2048];
+    let mut ground_speed_kt = 0.0;
+    let mut receiving = false;
+    loop {
+        let event = match socket.recv(&mut buffer) {
+            Ok(length) => {
+                let rows = parse_data(&buffer[..length]);
+                if let Some(speeds) = rows.iter().find(|(index, _)| *index == SPEEDS) {
+                    ground_speed_kt = f64::from(speeds.1[3]);
+                }
+                let Some((_, values)) = rows.iter().find(|(index, _)| *index == POSITION) else {
+                    continue;
+                };
+                if !receiving {
+                    info!("Receiving data from X-Plane");
+                    receiving = true;
+                }
+                SimEvent::State(AircraftState {
+                    position: Position { latitude: f64::from(values[0]), longitude: f64::from(values[1]) },
+                    altitude_ft: f64::from(values[2]),
+                    ground_speed_kt,
+                    on_ground: values[3] < ON_GROUND_AGL,
+                })
+            }
+            Err(err) if matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+                if receiving {
+                    warn!("No more data from X-Plane");
+                    receiving = false;
+                }
+                SimEvent::Disconnected(format!("Waiting for data on port {PORT}"))
+            }
+            Err(err) => return Err(err),
+        };
+        if tx.send(event).is_err() {
+            info!("Stopped receiving X-Plane data");
+            return Ok(());
+        }
+    }
+}
+
+/// Splits a data output packet into its rows, each an index with eight values.
+///
+/// A packet starts with `DATA` and one more byte, followed by rows of 36 bytes:
+/// the index as a 32-bit integer and eight 32-bit floats, all little endian.
+///
+/// # Returns
+///
+/// The rows of the packet, empty if it is no data output packet.
+///
+/// # Examples
+///
+/// ```rust
+/// use get_flight_data::xplane::parse_data;
+///
+/// let mut packet = b"DATA*".to_vec();
+/// packet.extend_from_slice(&20i32.to_le_bytes());
+/// for value in [50.03f32, 8.57, 364.0, 2.5, 1.0, 364.0, 0.0, 0.0] {
+///     packet.extend_from_slice(&value.to_le_bytes());
+/// }
+///
+/// let rows = parse_data(&packet);
+/// assert_eq!(rows.len(), 1);
+/// assert_eq!(rows[0].0, 20);
+/// assert_eq!(rows[0].1[2], 364.0);
+/// assert!(parse_data(b"RREF,").is_empty());
+/// ```
+pub fn parse_data(packet: &[u8]) -> Vec<(i32, [f32; 8])> {
+    let Some(rows) = packet.strip_prefix(b"DATA").and_then(|rest| rest.get(1..)) else {
+        return Vec::new();
+    };
+    let word = |bytes: &[u8]| [bytes[0], bytes[1], bytes[2], bytes[3]];
+    rows.chunks_exact(36)
+        .map(|row| {
+            let values = std::array::from_fn(|column| {
+                f32::from_le_bytes(word(&row[4 + column * 4..]))
+            });
+            (i32::from_le_bytes(word(row)), values)
+        })
+        .collect()
+}
@@ -0,0 +1,409 @@
+//! Refreshes against a local mock server that serves recorded SimBrief, AVWX and VATSIM responses,
+//! so changes to the parsing and formatting show up without calling the real APIs.
+//!
+//! The recorded responses are in `tests/fixtures/`, error responses are written out below.
+
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use get_flight_data::dir_manager::set_config_dir;
+use get_flight_data::engine::BriefingEngine;
+use get_flight_data::error::{GfdError, Result};
+use get_flight_data::logic::{
+    fetch_airports, fetch_atis, fetch_data, fetch_data_with_progress, fetch_ofp, set_endpoints, ApiClient,
+    ApiKey, Endpoints, HttpClient, LoadItem, LoadState, Network, Providers, Source,
+};
+use get_flight_data::retry::{self, RetryPolicy};
+use get_flight_data::worker::{Command, Worker, WorkerEvent};
+use httpmock::Method::GET;
+use httpmock::MockServer;
+
+const METAR_EDDB: &str = include_str!("fixtures/metar_eddb.json");
+const METAR_EHAM: &str = include_str!("fixtures/metar_eham.json");
+const DATAFEED: &str = include_str!("fixtures/datafeed.json");
+const OFP: &str = include_str!("fixtures/ofp.json");
+
+/// Sends requests for the mock server over HTTP and refuses all others,
+/// e.g. the SIGMETs, so no test depends on the real APIs.
+struct LocalOnly;
+
+impl ApiClient for LocalOnly {
+    async fn get(&self, uri: &str) -> Result<String> {
+        check_local(uri)?;
+        HttpClient.get(uri).await
+    }
+
+    async fn get_authorized(&self, uri: &str, api_key: &ApiKey) -> Result<String> {
+        check_local(uri)?;
+        HttpClient.get_authorized(uri, api_key).await
+    }
+
+    async fn get_fresh(&self, uri: &str) -> Result<String> {
+        check_local(uri)?;
+        HttpClient.get_fresh(uri).await
+    }
+}
+
+/// Like [`LocalOnly`], but the VATSIM datafeed is down, so each ATIS is fetched from the mirror.
+struct DatafeedDown;
+
+impl ApiClient for DatafeedDown {
+    async fn get(&self, uri: &str) -> Result<String> {
+        check_datafeed(uri)?;
+        LocalOnly.get(uri).await
+    }
+
+    async fn get_authorized(&self, uri: &str, api_key: &ApiKey) -> Result<String> {
+        check_datafeed(uri)?;
+        LocalOnly.get_authorized(uri, api_key).await
+    }
+
+    async fn get_fresh(&self, uri: &str) -> Result<String> {
+        check_datafeed(uri)?;
+        LocalOnly.get_fresh(uri).await
+    }
+}
+
+fn check_datafeed(uri: &str) -> Result<()> {
+    if uri == server().url("/datafeed") {
+        return Err(GfdError::Api { source: uri.to_string(), message: String::from("Datafeed down") });
+    }
+    Ok(())
+}
+
+fn check_local(uri: &str) -> Result<()> {
+    if uri.starts_with(&server().base_url()) {
+        return Ok(());
+    }
+    Err(GfdError::Api { source: uri.to_string(), message: String::from("Not served in tests") })
+}
+
+/// Starts the mock server once for all tests and points the endpoints to it.
+/// Requests without a mock are answered with `404 Not Found`.
+fn server() -> &'static MockServer {
+    static SERVER: OnceLock<MockServer> = OnceLock::new();
+    SERVER.get_or_init(|| {
+        // The AVWX calls are counted in the config directory, keep them out of the real one.
+        // Cargo keeps this directory in `target/`, the files of the previous run are removed.
+        let config = Path::new(env!("CARGO_TARGET_TMPDIR")).join("refresh_config");
+        let _ = fs::remove_dir_all(&config);
+        set_config_dir(Some(config));
+
+        let server = MockServer::start();
+        mock_avwx(&server);
+        mock_vatsim(&server);
+        mock_simbrief(&server);
+
+        set_endpoints(Endpoints {
+            avwx: server.url("/avwx/"),
+            simbrief: server.url("/simbrief"),
+            vatsim_atis: server.url("/atis/"),
+            vatsim_datafeed: server.url("/datafeed"),
+        })
+        .unwrap();
+        // Server errors are expected, waiting for retries would only slow the tests down
+        retry::set_policy(RetryPolicy { attempts: 1, ..RetryPolicy::DEFAULT });
+        server
+    })
+}
+
+fn mock_avwx(server: &MockServer) {
+    let metars = [
+        ("EDDB", 200, METAR_EDDB),
+        ("EHAM", 200, METAR_EHAM),
+        ("XXXX", 400, r#"{ "error": "XXXX is not a valid ICAO or IATA code" }"#),
+    ];
+    for (icao, status, body) in metars {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/avwx/metar/{icao}"))
+                .query_param("options", "info")
+                .header("Authorization", "Bearer test-token");
+            then.status(status).header("Content-Type", "application/json").body(body);
+        });
+    }
+    server.mock(|when, then| {
+        when.method(GET).path("/avwx/taf/EHAM");
+        then.status(204);
+    });
+}
+
+fn mock_vatsim(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(GET).path("/datafeed");
+        then.status(200).header("Content-Type", "application/json").body(DATAFEED);
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/atis/EDDM");
+        then.status(200).header("Content-Type", "application/json").body("[]");
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/atis/EDDF");
+        then.status(200).header("Content-Type", "application/json").body(r#"[
+            { "callsign": "EDDF_A_ATIS", "text_atis": ["FRANKFURT INFORMATION BRAVO", "ARRIVAL RUNWAY 25L"] },
+            { "callsign": "EDDF_D_ATIS", "text_atis": ["FRANKFURT INFORMATION TANGO", "DEPARTURE RUNWAY 18"] }
+        ]"#);
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/atis/EHAM");
+        then.status(200).header("Content-Type", "application/json").body(r#"[
+            { "callsign": "EHAM_ATIS", "text_atis": ["THIS IS SCHIPHOL INFORMATION ZULU"] }
+        ]"#);
+    });
+    for icao in ["EDDB", "ZZZZ"] {
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/atis/{icao}"));
+            then.status(500).body("Internal Server Error");
+        });
+    }
+}
+
+fn mock_simbrief(server: &MockServer) {
+    let users = [("pilot", OFP), ("nobody", r#"{ "fetch": { "status": "Error: Unknown UserID" } }"#)];
+    for (username, body) in users {
+        server.mock(|when, then| {
+            when.method(GET).path("/simbrief").query_param("username", username).query_param("json", "1");
+            then.status(200).header("Content-Type", "application/json").body(body);
+        });
+    }
+}
+
+fn providers() -> Providers {
+    Providers {
+        avwx: ApiKey::new(Source::Avwx, "test-token"),
+        network: Network::Vatsim,
+        translation: None,
+    }
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Runtime::new().unwrap()
+}
+
+#[test]
+fn refresh_formats_both_airports() {
+    server();
+    let providers = providers();
+    let (departure, arrival) = runtime().block_on(fetch_data(&LocalOnly, "EDDB", "EHAM", &providers)).unwrap();
+
+    assert_eq!(departure.summary, "ICAO: EDDB\n\n\
+        ATIS [VATSIM]: BERLIN BRANDENBURG INFORMATION KILO MET REPORT TIME 1820\n\
+        EXPECT ILS APPROACH RUNWAY 25R DEPARTURE RUNWAY 25L\n\
+        METAR [AVWX/NOAA]: EDDB 251820Z AUTO 24010KT 9999 VCSH SCT027 BKN039CB 09/06 Q1005 NOSIG\n\
+        Flight rules [AVWX]: VFR");
+    assert_eq!(departure.atis_letter, Some('K'));
+    assert!(departure.atis_online);
+    assert_eq!(departure.runways.len(), 2);
+    assert_eq!(departure.elevation_ft, Some(157.0));
+    assert_eq!(departure.stations.len(), 2);
+    assert_eq!((departure.traffic.outbound, departure.traffic.inbound), (1, 1));
+
+    // An ATIS being set up has neither text nor letter yet
+    assert_eq!(arrival.summary, "ICAO: EHAM\n\n\
+        ATIS [VATSIM]: \n\
+        METAR [AVWX/NOAA]: EHAM 251825Z 20018G29KT 3000 -RA BR BKN004 OVC009 08/07 Q0998 TEMPO 1500\n\
+        Flight rules [AVWX]: LIFR");
+    assert_eq!(arrival.atis_letter, None);
+    assert_eq!(arrival.flight_rules, "LIFR");
+    assert_eq!((arrival.temperature, arrival.dewpoint), (Some(8.0), Some(7.0)));
+    assert_eq!(arrival.weather, ["-RA", "BR"]);
+    // No TAF and no SIGMETs don't fail the refresh
+    assert!(arrival.taf.is_none());
+    assert!(arrival.hazards.is_empty());
+}
+
+#[test]
+fn refresh_keeps_the_airport_with_a_metar() {
+    server();
+    let providers = providers();
+    let (departure, arrival) = runtime().block_on(fetch_data(&LocalOnly, "EDDB", "XXXX", &providers)).unwrap();
+
+    assert!(departure.errors.is_empty());
+    assert_eq!(arrival.errors, ["METAR: AVWX returned an error: XXXX is not a valid ICAO or IATA code"]);
+    assert!(arrival.summary.ends_with("METAR [AVWX/NOAA]: Unavailable\nFlight rules [AVWX]: -"));
+    assert_eq!(arrival.observation.raw, "");
+}
+
+#[test]
+fn refresh_fails_without_any_metar() {
+    server();
+    let result = runtime().block_on(fetch_data(&LocalOnly, "XXXX", "YYYY", &providers()));
+    assert!(matches!(result, Err(GfdError::Api { message, .. }) if message.contains("not a valid ICAO")));
+}
+
+#[test]
+fn refresh_without_network_data() {
+    server();
+    // The IVAO Whazzup isn't served, so both ATIS are missing
+    let providers = Providers { network: Network::Ivao, ..providers() };
+    let (departure, arrival) = runtime().block_on(fetch_data(&LocalOnly, "EDDB", "EHAM", &providers)).unwrap();
+
+    for airport in [&departure, &arrival] {
+        assert_eq!(airport.errors.len(), 1);
+        assert!(airport.errors[0].starts_with("ATIS: "));
+        assert!(airport.summary.contains("ATIS [IVAO]: Unavailable\n"));
+        assert_eq!(airport.atis_letter, None);
+    }
+    assert_eq!(departure.flight_rules, "VFR");
+    assert_eq!(arrival.flight_rules, "LIFR");
+}
+
+#[test]
+fn refresh_reports_each_part() {
+    server();
+    let providers = providers();
+    let reported = Mutex::new(Vec::new());
+    let progress = |item, state| reported.lock().unwrap().push((item, state));
+    runtime().block_on(fetch_data_with_progress(&LocalOnly, "EDDB", "XXXX", &providers, &progress)).unwrap();
+
+    let mut reported = reported.into_inner().unwrap();
+    reported.sort_by_key(|(item, _)| LoadItem::WEATHER.iter().position(|weather| weather == item));
+    assert_eq!(reported, [
+        (LoadItem::DepartureMetar, LoadState::Done),
+        (LoadItem::ArrivalMetar, LoadState::Failed),
+        (LoadItem::DepartureAtis, LoadState::Done),
+        (LoadItem::ArrivalAtis, LoadState::Done),
+    ]);
+}
+
+#[test]
+fn more_airports_keep_their_own_atis_failure() {
+    server();
+    let icaos = [String::from("EDDB"), String::from("EHAM")];
+    let airports = runtime().block_on(fetch_airports(&DatafeedDown, &icaos, &providers())).unwrap();
+
+    let [departure, arrival] = [&airports[0], &airports[1]].map(|airport| airport.as_ref().unwrap());
+    assert_eq!(departure.errors.len(), 1);
+    assert!(departure.errors[0].starts_with("ATIS: "));
+    assert_eq!(departure.atis_letter, None);
+    assert_eq!(departure.flight_rules, "VFR");
+    assert!(arrival.errors.is_empty());
+    assert_eq!(arrival.atis_letter, Some('Z'));
+}
+
+#[test]
+fn atis_from_mirror() {
+    server();
+    let runtime = runtime();
+
+    let departure = runtime.block_on(fetch_atis(&LocalOnly, "EDDF", true)).unwrap();
+    assert_eq!(departure.text, "FRANKFURT INFORMATION TANGO\nDEPARTURE RUNWAY 18");
+    assert_eq!(departure.letter, Some('T'));
+    let arrival = runtime.block_on(fetch_atis(&LocalOnly, "EDDF", false)).unwrap();
+    assert_eq!(arrival.letter, Some('B'));
+
+    let none_online = runtime.block_on(fetch_atis(&LocalOnly, "EDDM", true)).unwrap();
+    assert_eq!(none_online.text, "No vatsim ATIS available");
+    assert_eq!(none_online.letter, None);
+
+    let server_error = runtime.block_on(fetch_atis(&LocalOnly, "ZZZZ", true));
+    assert!(matches!(server_error, Err(GfdError::Request(_))));
+}
+
+#[test]
+fn flight_plan_from_simbrief() {
+    server();
+    let runtime = runtime();
+
+    let flight_plan = runtime.block_on(fetch_ofp(&LocalOnly, "pilot")).unwrap();
+    assert_eq!((flight_plan.origin.as_str(), flight_plan.destination.as_str()), ("EDDB", "EHAM"));
+    assert_eq!(flight_plan.alternate.as_deref(), Some("EHRD"));
+    assert_eq!(flight_plan.callsign, "DLH123");
+    assert_eq!(flight_plan.cruise_level(), "FL360");
+    assert!(flight_plan.top_of_descent.is_some());
+
+    let unknown_user = runtime.block_on(fetch_ofp(&LocalOnly, "nobody"));
+    assert!(matches!(unknown_user, Err(GfdError::Api { message, .. }) if message == "Error: Unknown UserID"));
+}
+
+#[test]
+fn engine_briefs_a_simbrief_flight() {
+    server();
+    let engine = BriefingEngine::for_simbrief("pilot").providers(providers()).build();
+    let briefing = runtime().block_on(engine.refresh(&LocalOnly)).unwrap();
+
+    assert_eq!(briefing.flight_plan.map(|flight_plan| flight_plan.callsign).as_deref(), Some("DLH123"));
+    assert_eq!((briefing.departure.icao.as_str(), briefing.arrival.icao.as_str()), ("EDDB", "EHAM"));
+    assert!(briefing.text.contains("EHAM 251825Z"));
+}
+
+#[test]
+fn worker_answers_the_latest_command() {
+    server();
+    let runtime = runtime();
+    let mut worker = Worker::start(LocalOnly, runtime.handle().clone(), || ());
+
+    worker.send(Command::ReloadFlightPlan { username: String::from("nobody") });
+    let id = worker.send(Command::ReloadFlightPlan { username: String::from("pilot") });
+    // The first request may finish before it is cancelled, its result is skipped
+    let flight_plan = loop {
+        match worker.recv() {
+            Some(WorkerEvent::FlightPlan { id: event_id, result }) if event_id == id => break result.unwrap(),
+            Some(event) => assert!(event.id() < id),
+            None => panic!("The worker stopped without an answer"),
+        }
+    };
+    assert_eq!(flight_plan.origin, "EDDB");
+}
+
+#[test]
+fn worker_keeps_the_flight_plan_during_a_refresh() {
+    server();
+    let runtime = runtime();
+    let mut worker = Worker::start(LocalOnly, runtime.handle().clone(), || ());
+
+    let flight_plan_id = worker.send(Command::ReloadFlightPlan { username: String::from("pilot") });
+    let weather_id = worker.send(Command::RefreshWeather {
+        departure: String::from("EDDB"),
+        arrival: String::from("EHAM"),
+        providers: providers(),
+    });
+    // A refresh only cancels another refresh
+    let (mut flight_plan, mut weather) = (None, None);
+    while flight_plan.is_none() || weather.is_none() {
+        match worker.recv() {
+            Some(WorkerEvent::FlightPlan { id, result }) if id == flight_plan_id => flight_plan = Some(result),
+            Some(WorkerEvent::Weather { id, result }) if id == weather_id => weather = Some(result),
+            Some(WorkerEvent::Progress { .. }) => (),
+            Some(_) => panic!("Unexpected event"),
+            None => panic!("The worker stopped without an answer"),
+        }
+    }
+    assert_eq!(flight_plan.unwrap().unwrap().callsign, "DLH123");
+    assert_eq!(weather.unwrap().unwrap().1.flight_rules, "LIFR");
+}
+
+#[test]
+fn worker_refreshes_the_monitored_airports_alongside() {
+    server();
+    let runtime = runtime();
+    let mut worker = Worker::start(LocalOnly, runtime.handle().clone(), || ());
+
+    let icaos = vec![String::from("EDDB"), String::from("XXXX")];
+    worker.send(Command::RefreshAirports { icaos: icaos.clone(), providers: providers() });
+    let airports_id = worker.send(Command::RefreshAirports { icaos: icaos.clone(), providers: providers() });
+    let weather_id = worker.send(Command::RefreshWeather {
+        departure: String::from("EDDB"),
+        arrival: String::from("EHAM"),
+        providers: providers(),
+    });
+    // A newer refresh of the airports replaces the first, the weather refresh doesn't
+    let (mut airports, mut weather) = (None, None);
+    while airports.is_none() || weather.is_none() {
+        match worker.recv() {
+            Some(WorkerEvent::Airports { id, icaos: event_icaos, result }) if id == airports_id => {
+                assert_eq!(event_icaos, icaos);
+                airports = Some(result);
+            }
+            Some(WorkerEvent::Weather { id, result }) if id == weather_id => weather = Some(result),
+            Some(WorkerEvent::Progress { .. }) => (),
+            Some(event) => assert!(event.id() < airports_id),
+            None => panic!("The worker stopped without an answer"),
+        }
+    }
+    let airports = airports.unwrap().unwrap();
+    assert_eq!(airports[0].as_ref().unwrap().flight_rules, "VFR");
+    assert!(airports[1].is_err());
+    assert!(weather.unwrap().is_ok());
+}